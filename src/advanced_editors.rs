@@ -8,12 +8,14 @@ use crate::{
         WorkspaceSelector, WorkspaceType, animation::parse_animation, bezier_curve::parse_bezier,
         bind_right::parse_bind_right, monitor::parse_monitor, workspace::parse_workspace,
     },
+    rule_preview::create_window_rule_preview,
     utils::{
         MARGIN_NORMAL, MAX_SAFE_INTEGER_F64, MAX_SAFE_STEP_0_01_F64, MIN_SAFE_INTEGER_F64,
         after_second_comma, cow_to_static_str, get_available_monitors,
         get_available_resolutions_for_monitor, is_modifier, join_with_separator, keycode_to_en_key,
         parse_coordinates,
     },
+    workspace_matcher::{create_workspace_match_preview, match_window_rule, match_workspace_rule},
 };
 use gio::glib::{self, SignalHandlerId};
 use gtk::{
@@ -2557,6 +2559,10 @@ fn fill_fancy_value_entry(
             workspace_selector_box.append(&selectors_ui_box);
 
             fancy_value_entry.append(&workspace_selector_box);
+            fancy_value_entry.append(&create_workspace_match_preview(
+                value_entry,
+                match_workspace_rule,
+            ));
 
             let is_updating_clone = is_updating.clone();
             let value_entry_clone = value_entry.clone();
@@ -4281,6 +4287,11 @@ fn fill_fancy_value_entry(
         "windowrule" => {
             let window_rule_box = Vec::<WindowRuleEffectOrProp>::to_gtk_box(value_entry, ',');
             fancy_value_entry.append(&window_rule_box);
+            fancy_value_entry.append(&create_window_rule_preview(value_entry));
+            fancy_value_entry.append(&create_workspace_match_preview(
+                value_entry,
+                match_window_rule,
+            ));
         }
         "layerrule" => {
             let layer_rule_box = Vec::<LayerRuleEffectOrProp>::to_gtk_box(value_entry, ',');
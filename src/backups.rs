@@ -0,0 +1,219 @@
+use crate::{
+    diff::{add_diff_tags, set_unified_diff_text, unified_diff},
+    gui::format_change_time,
+    utils::{
+        Backup, BackupSettings, MARGIN_NORMAL, display_path, list_backups, load_backup_settings,
+        save_backup_settings,
+    },
+};
+use gtk::{
+    AlertDialog, Align, Box, Button, Label, ListBox, Orientation, Paned, ScrolledWindow,
+    SelectionMode, SpinButton, TextView, Window, gio, glib, prelude::*,
+};
+use rust_i18n::t;
+use std::{fs, rc::Rc};
+
+fn backup_row(backup: &Backup) -> Box {
+    let row_box = Box::new(Orientation::Vertical, 2);
+    row_box.set_margin_start(MARGIN_NORMAL / 2);
+    row_box.set_margin_end(MARGIN_NORMAL / 2);
+    row_box.set_margin_top(MARGIN_NORMAL / 3);
+    row_box.set_margin_bottom(MARGIN_NORMAL / 3);
+
+    let path_label = Label::new(Some(&display_path(&backup.original)));
+    path_label.set_halign(Align::Start);
+    path_label.set_ellipsize(gtk::pango::EllipsizeMode::Start);
+    row_box.append(&path_label);
+
+    let time_label = Label::new(Some(&format_change_time(backup.created_ms / 1000)));
+    time_label.set_halign(Align::Start);
+    time_label.add_css_class("dim-label");
+    time_label.add_css_class("caption");
+    row_box.append(&time_label);
+
+    row_box
+}
+
+/// Lists the backups hyprviz made before overwriting files, with the diff of each against the
+/// file as it is now. `on_restore` is called with the backup to restore once confirmed.
+pub fn show_backup_browser<F>(parent: &impl IsA<Window>, on_restore: F)
+where
+    F: Fn(&Backup) + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(t!("backups.title").to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(860)
+        .default_height(600)
+        .build();
+
+    let main_box = Box::new(Orientation::Vertical, 10);
+    main_box.set_margin_top(MARGIN_NORMAL);
+    main_box.set_margin_bottom(MARGIN_NORMAL);
+    main_box.set_margin_start(MARGIN_NORMAL);
+    main_box.set_margin_end(MARGIN_NORMAL);
+
+    let settings = load_backup_settings();
+    let settings_box = Box::new(Orientation::Horizontal, 6);
+    let count_spin = SpinButton::with_range(0.0, 1000.0, 1.0);
+    count_spin.set_value(settings.max_count as f64);
+    let age_spin = SpinButton::with_range(0.0, 3650.0, 1.0);
+    age_spin.set_value(settings.max_age_days as f64);
+    settings_box.append(&Label::new(Some(&t!("backups.keep"))));
+    settings_box.append(&count_spin);
+    settings_box.append(&Label::new(Some(&t!("backups.backups_per_file_for"))));
+    settings_box.append(&age_spin);
+    settings_box.append(&Label::new(Some(&t!("backups.days"))));
+    settings_box.set_tooltip_text(Some(&t!("backups.zero_keeps_all")));
+    main_box.append(&settings_box);
+
+    let backups = Rc::new(list_backups());
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::Single);
+    list_box.add_css_class("boxed-list");
+    for backup in backups.iter() {
+        list_box.append(&backup_row(backup));
+    }
+
+    let empty_label = Label::new(Some(&t!("backups.no_backups")));
+    empty_label.add_css_class("dim-label");
+    list_box.set_placeholder(Some(&empty_label));
+
+    let list_scrolled_window = ScrolledWindow::new();
+    list_scrolled_window.set_min_content_width(260);
+    list_scrolled_window.set_child(Some(&list_box));
+
+    let text_view = TextView::new();
+    text_view.set_editable(false);
+    text_view.set_cursor_visible(false);
+    text_view.set_monospace(true);
+    add_diff_tags(&text_view.buffer());
+
+    let diff_scrolled_window = ScrolledWindow::new();
+    diff_scrolled_window.set_child(Some(&text_view));
+
+    let paned = Paned::new(Orientation::Horizontal);
+    paned.set_start_child(Some(&list_scrolled_window));
+    paned.set_end_child(Some(&diff_scrolled_window));
+    paned.set_position(300);
+    paned.set_vexpand(true);
+    main_box.append(&paned);
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+
+    let close_button = Button::with_label(&t!("backups.close"));
+    let restore_button = Button::with_label(&t!("backups.restore"));
+    restore_button.add_css_class("suggested-action");
+    restore_button.set_sensitive(false);
+    buttons_box.append(&close_button);
+    buttons_box.append(&restore_button);
+    main_box.append(&buttons_box);
+
+    dialog_window.set_child(Some(&main_box));
+
+    let save_settings = {
+        let count_spin = count_spin.clone();
+        let age_spin = age_spin.clone();
+        Rc::new(move || {
+            let settings = BackupSettings {
+                max_count: count_spin.value_as_int().max(0) as usize,
+                max_age_days: age_spin.value_as_int().max(0) as u64,
+            };
+            if let Err(e) = save_backup_settings(&settings) {
+                eprintln!("Failed to save backup settings: {}", e);
+            }
+        })
+    };
+    let save_settings_clone = save_settings.clone();
+    count_spin.connect_value_changed(move |_| save_settings_clone());
+    age_spin.connect_value_changed(move |_| save_settings());
+
+    let backups_clone = backups.clone();
+    let restore_button_clone = restore_button.clone();
+    list_box.connect_row_selected(move |_, row| {
+        let backup = row.and_then(|row| backups_clone.get(row.index() as usize));
+        restore_button_clone.set_sensitive(backup.is_some());
+        let Some(backup) = backup else {
+            text_view.buffer().set_text("");
+            return;
+        };
+
+        let old = match fs::read_to_string(&backup.path) {
+            Ok(old) => old,
+            Err(e) => {
+                text_view
+                    .buffer()
+                    .set_text(&t!("backups.failed_to_read_", error = e));
+                return;
+            }
+        };
+        let current = fs::read_to_string(&backup.original).unwrap_or_default();
+        let label = display_path(&backup.original);
+        let diff = unified_diff(
+            &old,
+            &current,
+            &t!("backups.backup_of_", file = &label),
+            &label,
+        );
+
+        if diff.is_empty() {
+            text_view.buffer().set_text(&t!("backups.same_as_current"));
+        } else {
+            set_unified_diff_text(&text_view.buffer(), &diff);
+        }
+    });
+
+    if let Some(first_row) = list_box.row_at_index(0) {
+        list_box.select_row(Some(&first_row));
+    }
+
+    let dialog_window_clone = dialog_window.clone();
+    close_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    let on_restore = Rc::new(on_restore);
+    let dialog_window_clone = dialog_window.clone();
+    restore_button.connect_clicked(move |_| {
+        let Some(backup) = list_box
+            .selected_row()
+            .and_then(|row| backups.get(row.index() as usize))
+            .cloned()
+        else {
+            return;
+        };
+
+        let confirmation = AlertDialog::builder()
+            .message(t!("backups.restore_backup").to_string())
+            .detail(
+                t!(
+                    "backups.restore_backup_text_",
+                    file = display_path(&backup.original),
+                    time = format_change_time(backup.created_ms / 1000)
+                )
+                .to_string(),
+            )
+            .buttons([&*t!("backups.cancel"), &*t!("backups.restore")])
+            .modal(true)
+            .build();
+
+        let dialog_window = dialog_window_clone.clone();
+        let on_restore = on_restore.clone();
+        confirmation.choose(
+            Some(&dialog_window_clone),
+            None::<&gio::Cancellable>,
+            move |res: Result<i32, glib::Error>| {
+                if res.ok() == Some(1) {
+                    dialog_window.close();
+                    on_restore(&backup);
+                }
+            },
+        );
+    });
+
+    dialog_window.present();
+}
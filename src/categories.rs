@@ -0,0 +1,101 @@
+use rust_i18n::t;
+
+/// What the page of a category edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CategoryKind {
+    /// Options written in a block of the same name.
+    Options,
+    /// Options of the blocks in [`LAYOUT_BLOCKS`].
+    Layouts,
+    /// Top-level rows whose name starts with the category.
+    Rows,
+    /// Every top-level row.
+    TopLevel,
+    /// Information that is not read from the config.
+    Info,
+}
+
+/// Every category page, in sidebar order.
+pub const CATEGORIES: &[(&str, CategoryKind)] = &[
+    ("general", CategoryKind::Options),
+    ("decoration", CategoryKind::Options),
+    ("animations", CategoryKind::Options),
+    ("input", CategoryKind::Options),
+    ("gestures", CategoryKind::Options),
+    ("misc", CategoryKind::Options),
+    ("binds", CategoryKind::Options),
+    ("group", CategoryKind::Options),
+    ("layouts", CategoryKind::Layouts),
+    ("xwayland", CategoryKind::Options),
+    ("opengl", CategoryKind::Options),
+    ("render", CategoryKind::Options),
+    ("cursor", CategoryKind::Options),
+    ("ecosystem", CategoryKind::Options),
+    ("quirks", CategoryKind::Options),
+    ("debug", CategoryKind::Options),
+    ("monitor", CategoryKind::Rows),
+    ("workspace", CategoryKind::Rows),
+    ("animation", CategoryKind::Rows),
+    ("bind", CategoryKind::Rows),
+    ("gesture", CategoryKind::Rows),
+    ("windowrule", CategoryKind::Rows),
+    ("layerrule", CategoryKind::Rows),
+    ("exec", CategoryKind::Rows),
+    ("permission", CategoryKind::Rows),
+    ("env", CategoryKind::Rows),
+    ("top_level", CategoryKind::TopLevel),
+    ("systeminfo", CategoryKind::Info),
+];
+
+/// Blocks whose options are shown on the `layouts` page.
+pub const LAYOUT_BLOCKS: &[&str] = &["dwindle", "master", "scrolling", "monocle"];
+
+fn display_name(category: &str) -> String {
+    match category {
+        "general" => t!("gui.general").to_string(),
+        "decoration" => t!("gui.decoration").to_string(),
+        "animations" => t!("gui.animations_settings").to_string(),
+        "input" => t!("gui.input").to_string(),
+        "gestures" => t!("gui.gestures_settings").to_string(),
+        "misc" => t!("gui.misc").to_string(),
+        "binds" => t!("gui.bind_settings").to_string(),
+        "group" => t!("gui.group").to_string(),
+        "layouts" => t!("gui.layouts").to_string(),
+        "xwayland" => "XWayland".to_string(),
+        "opengl" => "OpenGL".to_string(),
+        "render" => t!("gui.render").to_string(),
+        "cursor" => t!("gui.cursor").to_string(),
+        "ecosystem" => t!("gui.ecosystem").to_string(),
+        "quirks" => t!("gui.quirks").to_string(),
+        "debug" => t!("gui.debug").to_string(),
+        "monitor" => t!("gui.monitors").to_string(),
+        "workspace" => t!("gui.workspaces").to_string(),
+        "animation" => t!("gui.animations").to_string(),
+        "bind" => t!("gui.binds").to_string(),
+        "gesture" => t!("gui.gestures").to_string(),
+        "windowrule" => t!("gui.window_rules").to_string(),
+        "layerrule" => t!("gui.layer_rules").to_string(),
+        "exec" => t!("gui.execs").to_string(),
+        "permission" => t!("gui.permissions").to_string(),
+        "env" => t!("gui.envs").to_string(),
+        "top_level" => t!("gui.all_top_level").to_string(),
+        "systeminfo" => t!("gui.system_info").to_string(),
+        _ => category.to_string(),
+    }
+}
+
+/// Display name and id of every category page, in sidebar order.
+pub fn category_list() -> Vec<(String, &'static str)> {
+    CATEGORIES
+        .iter()
+        .map(|(category, _)| (display_name(category), *category))
+        .collect()
+}
+
+/// Ids of the categories of `kind`, in sidebar order.
+pub fn categories_of(kind: CategoryKind) -> impl Iterator<Item = &'static str> {
+    CATEGORIES
+        .iter()
+        .filter(move |(_, category_kind)| *category_kind == kind)
+        .map(|(category, _)| *category)
+}
@@ -0,0 +1,259 @@
+use crate::{
+    search::{FieldKind, score_fields},
+    utils::{MARGIN_NORMAL, load_ui_settings, save_ui_settings},
+};
+use gtk::{
+    Align, Box, Button, Label, ListBox, ListBoxRow, Orientation, ScrolledWindow, SearchEntry,
+    SelectionMode, Widget, Window, gdk, glib, prelude::*,
+};
+use rust_i18n::t;
+use std::{cell::RefCell, rc::Rc};
+
+/// What running a command does.
+#[derive(Clone)]
+pub enum CommandAction {
+    /// Shows a category, option, row or guide.
+    Jump {
+        category: String,
+        widget: Widget,
+    },
+    /// Clicks one of the buttons of the main window.
+    Click(Button),
+    SwitchProfile(String),
+    /// Sets an option back to its default value.
+    ResetOption {
+        category: String,
+        widget: Widget,
+        default: String,
+    },
+    CreateBind,
+    TogglePreviews,
+}
+
+#[derive(Clone)]
+pub struct PaletteCommand {
+    /// Stable id, used to remember recent commands.
+    pub id: String,
+    pub title: String,
+    pub subtitle: String,
+    pub fields: Vec<(FieldKind, String)>,
+    pub action: CommandAction,
+}
+
+impl PaletteCommand {
+    /// Actions are listed when nothing is typed; jumps and resets only show up in results.
+    fn is_action(&self) -> bool {
+        !matches!(
+            self.action,
+            CommandAction::Jump { .. } | CommandAction::ResetOption { .. }
+        )
+    }
+}
+
+/// Most results shown at once.
+const MAX_RESULTS: usize = 50;
+
+/// Commands matching `query`, best first. Without a query, the recently used commands come
+/// first, followed by every action.
+fn matching_commands<'a>(
+    commands: &'a [PaletteCommand],
+    recent: &[String],
+    query: &str,
+) -> Vec<(&'a PaletteCommand, bool)> {
+    if query.trim().is_empty() {
+        let recent_commands = recent
+            .iter()
+            .filter_map(|id| commands.iter().find(|command| command.id == *id))
+            .map(|command| (command, true));
+        let actions = commands
+            .iter()
+            .filter(|command| command.is_action() && !recent.contains(&command.id))
+            .map(|command| (command, false));
+        return recent_commands.chain(actions).take(MAX_RESULTS).collect();
+    }
+
+    let mut scored: Vec<(i64, &PaletteCommand)> = commands
+        .iter()
+        .filter_map(|command| {
+            let mut score = score_fields(&command.fields, query)?;
+            // Recently used commands win ties and near-ties.
+            if let Some(position) = recent.iter().position(|id| *id == command.id) {
+                score += (recent.len() - position) as i64 * 10;
+            }
+            Some((score, command))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.title.cmp(&b.1.title)));
+    scored
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, command)| (command, recent.contains(&command.id)))
+        .collect()
+}
+
+fn command_row(command: &PaletteCommand, is_recent: bool) -> Box {
+    let row_box = Box::new(Orientation::Horizontal, 6);
+    row_box.set_margin_start(MARGIN_NORMAL / 2);
+    row_box.set_margin_end(MARGIN_NORMAL / 2);
+    row_box.set_margin_top(MARGIN_NORMAL / 3);
+    row_box.set_margin_bottom(MARGIN_NORMAL / 3);
+
+    let text_box = Box::new(Orientation::Vertical, 2);
+    text_box.set_hexpand(true);
+
+    let title_label = Label::new(Some(&command.title));
+    title_label.set_halign(Align::Start);
+    title_label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+    text_box.append(&title_label);
+
+    let subtitle_label = Label::new(Some(&command.subtitle));
+    subtitle_label.set_halign(Align::Start);
+    subtitle_label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+    subtitle_label.add_css_class("dim-label");
+    subtitle_label.add_css_class("caption");
+    text_box.append(&subtitle_label);
+    row_box.append(&text_box);
+
+    if is_recent {
+        let recent_label = Label::new(Some(&t!("command_palette.recent")));
+        recent_label.set_valign(Align::Center);
+        recent_label.add_css_class("dim-label");
+        recent_label.add_css_class("caption");
+        row_box.append(&recent_label);
+    }
+
+    row_box
+}
+
+/// Opens the command palette over `parent`. `on_run` is called with the command picked with
+/// Enter or a click, after the palette closes.
+pub fn show_command_palette<F>(parent: &impl IsA<Window>, commands: Vec<PaletteCommand>, on_run: F)
+where
+    F: Fn(&PaletteCommand) + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(t!("command_palette.title").to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .decorated(false)
+        .default_width(560)
+        .build();
+
+    let main_box = Box::new(Orientation::Vertical, 6);
+    main_box.set_margin_top(MARGIN_NORMAL / 2);
+    main_box.set_margin_bottom(MARGIN_NORMAL / 2);
+    main_box.set_margin_start(MARGIN_NORMAL / 2);
+    main_box.set_margin_end(MARGIN_NORMAL / 2);
+
+    let search_entry = SearchEntry::new();
+    search_entry.set_placeholder_text(Some(&t!("command_palette.placeholder")));
+    main_box.append(&search_entry);
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::Browse);
+
+    let empty_label = Label::new(Some(&t!("command_palette.no_commands")));
+    empty_label.add_css_class("dim-label");
+    empty_label.set_margin_top(MARGIN_NORMAL / 2);
+    empty_label.set_margin_bottom(MARGIN_NORMAL / 2);
+    list_box.set_placeholder(Some(&empty_label));
+
+    let scrolled_window = ScrolledWindow::new();
+    scrolled_window.set_min_content_height(360);
+    scrolled_window.set_child(Some(&list_box));
+    main_box.append(&scrolled_window);
+
+    let hint_label = Label::new(Some(&t!("command_palette.hint")));
+    hint_label.set_halign(Align::Start);
+    hint_label.add_css_class("dim-label");
+    hint_label.add_css_class("caption");
+    main_box.append(&hint_label);
+
+    dialog_window.set_child(Some(&main_box));
+
+    let commands = Rc::new(commands);
+    let recent = load_ui_settings().recent_commands;
+    let shown: Rc<RefCell<Vec<PaletteCommand>>> = Rc::new(RefCell::new(Vec::new()));
+
+    let fill = {
+        let list_box = list_box.clone();
+        let shown = shown.clone();
+        move |query: &str| {
+            while let Some(child) = list_box.first_child() {
+                list_box.remove(&child);
+            }
+
+            let mut shown = shown.borrow_mut();
+            shown.clear();
+            for (command, is_recent) in matching_commands(&commands, &recent, query) {
+                list_box.append(&command_row(command, is_recent));
+                shown.push(command.clone());
+            }
+
+            if let Some(first_row) = list_box.row_at_index(0) {
+                list_box.select_row(Some(&first_row));
+            }
+        }
+    };
+    fill("");
+    search_entry.connect_search_changed(move |entry| fill(&entry.text()));
+
+    let run = {
+        let dialog_window = dialog_window.clone();
+        Rc::new(move |row: &ListBoxRow| {
+            let Some(command) = shown.borrow().get(row.index() as usize).cloned() else {
+                return;
+            };
+
+            let mut settings = load_ui_settings();
+            settings.push_recent_command(&command.id);
+            if let Err(e) = save_ui_settings(&settings) {
+                eprintln!("Failed to save recent commands: {}", e);
+            }
+
+            dialog_window.close();
+            on_run(&command);
+        })
+    };
+
+    let run_clone = run.clone();
+    list_box.connect_row_activated(move |_, row| run_clone(row));
+
+    let list_box_clone = list_box.clone();
+    search_entry.connect_activate(move |_| {
+        if let Some(row) = list_box_clone.selected_row() {
+            run(&row);
+        }
+    });
+
+    // Up and Down move the selection while the entry keeps the focus.
+    let dialog_window_clone = dialog_window.clone();
+    let key_controller = gtk::EventControllerKey::new();
+    key_controller.set_propagation_phase(gtk::PropagationPhase::Capture);
+    key_controller.connect_key_pressed(move |_, keyval, _, _| {
+        let step = match keyval {
+            gdk::Key::Escape => {
+                dialog_window_clone.close();
+                return glib::Propagation::Stop;
+            }
+            gdk::Key::Down => 1,
+            gdk::Key::Up => -1,
+            _ => return glib::Propagation::Proceed,
+        };
+
+        let index = list_box
+            .selected_row()
+            .map_or(-1, |row| row.index() + step)
+            .max(0);
+        if let Some(row) = list_box.row_at_index(index) {
+            list_box.select_row(Some(&row));
+            row.grab_focus();
+            search_entry.grab_focus_without_selecting();
+        }
+        glib::Propagation::Stop
+    });
+    dialog_window.add_controller(key_controller);
+
+    dialog_window.present();
+}
@@ -0,0 +1,509 @@
+use std::{fmt, ops::Range};
+
+const DEFAULT_INDENT: &str = "    ";
+
+/// Byte ranges of the key and value of a `key = value` line, excluding surrounding
+/// whitespace and trailing comments.
+#[derive(Debug, Clone)]
+struct Assignment {
+    key: Range<usize>,
+    value: Range<usize>,
+}
+
+/// Returns where the code part of `line` ends, i.e. the start of a `#` comment. `##` is an
+/// escaped `#` and does not start a comment.
+fn code_end(line: &str) -> usize {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'#' {
+            if bytes.get(i + 1) == Some(&b'#') {
+                i += 2;
+                continue;
+            }
+            return i;
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+fn parse_assignment(line: &str) -> Option<Assignment> {
+    let end = line[..code_end(line)].trim_end().len();
+    let eq = line[..end].find('=')?;
+
+    let key_start = line.len() - line.trim_start().len();
+    let key_end = line[..eq].trim_end().len();
+    if key_start >= key_end || line[key_start..key_end].contains(['{', '}']) {
+        return None;
+    }
+
+    let value_start =
+        (eq + 1 + line[eq + 1..end].len() - line[eq + 1..end].trim_start().len()).min(end);
+
+    Some(Assignment {
+        key: key_start..key_end,
+        value: value_start..end,
+    })
+}
+
+/// Path of the option `name` of a hyprviz category, as written in the config. Layout options
+/// already carry their category (`dwindle:pseudotile`).
+pub fn option_path(category: &str, name: &str) -> String {
+    if category == "layouts" {
+        name.to_string()
+    } else {
+        format!("{category}:{name}")
+    }
+}
+
+/// Like [`parse_assignment`], for the part of `line` in `span`, which holds no comment.
+fn parse_assignment_in(line: &str, span: Range<usize>) -> Option<Assignment> {
+    let assignment = parse_assignment(&line[span.clone()])?;
+    Some(Assignment {
+        key: assignment.key.start + span.start..assignment.key.end + span.start,
+        value: assignment.value.start + span.start..assignment.value.end + span.start,
+    })
+}
+
+struct Section {
+    path: String,
+    close: Option<usize>,
+}
+
+/// An option definition found by [`ConfigDocument::outline`].
+struct OptionSpan {
+    line: usize,
+    path: String,
+    assignment: Assignment,
+}
+
+/// The block structure of a document.
+struct Outline {
+    options: Vec<OptionSpan>,
+    /// Every `name {` block with the line it opens on.
+    sections: Vec<(usize, Section)>,
+    /// Number of blocks open at the start of each line.
+    depths: Vec<usize>,
+}
+
+/// A hyprlang file kept as its original lines. Edits only replace the spans they change,
+/// so comments, blank lines, alignment and ordering of everything else stay byte-identical.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDocument {
+    lines: Vec<String>,
+    trailing_newline: bool,
+    crlf: bool,
+}
+
+impl ConfigDocument {
+    pub fn parse(text: &str) -> Self {
+        let trailing_newline = text.ends_with('\n');
+        let body = text.strip_suffix('\n').unwrap_or(text);
+        let lines: Vec<String> = if text.is_empty() {
+            Vec::new()
+        } else {
+            body.split('\n').map(str::to_string).collect()
+        };
+        let crlf = lines.first().is_some_and(|line| line.ends_with('\r'));
+
+        Self {
+            lines,
+            trailing_newline,
+            crlf,
+        }
+    }
+
+    fn new_line(&self, text: String) -> String {
+        if self.crlf { text + "\r" } else { text }
+    }
+
+    fn indent_unit(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| &line[..line.len() - line.trim_start().len()])
+            .find(|indent| !indent.is_empty())
+            .map_or(DEFAULT_INDENT.to_string(), str::to_string)
+    }
+
+    /// Walks the file once, returning every option with its full `category:key` path and
+    /// every `name {` block with its path and closing line. Blocks may open and close on one
+    /// line (`general { gaps_in = 5 }`); braces inside values are left alone.
+    fn outline(&self) -> Outline {
+        let mut outline = Outline {
+            options: Vec::new(),
+            sections: Vec::new(),
+            depths: Vec::with_capacity(self.lines.len()),
+        };
+        let mut stack: Vec<usize> = Vec::new();
+
+        for (index, line) in self.lines.iter().enumerate() {
+            outline.depths.push(stack.len());
+
+            let end = code_end(line);
+            let mut segment_start = 0;
+            let mut opened_here = 0usize;
+            let mut value_braces = 0;
+
+            let push_option = |stack: &[usize], outline: &mut Outline, span: Range<usize>| {
+                if let Some(assignment) = parse_assignment_in(line, span) {
+                    let key = &line[assignment.key.clone()];
+                    let path = match stack.last() {
+                        Some(&s) => format!("{}:{key}", outline.sections[s].1.path),
+                        None => key.to_string(),
+                    };
+                    outline.options.push(OptionSpan {
+                        line: index,
+                        path,
+                        assignment,
+                    });
+                }
+            };
+
+            for (i, c) in line[..end].char_indices() {
+                let segment = &line[segment_start..i];
+                match c {
+                    '{' if value_braces == 0 && !segment.contains('=') => {
+                        let name = segment.trim();
+                        let path = match stack.last() {
+                            Some(&s) => format!("{}:{name}", outline.sections[s].1.path),
+                            None => name.to_string(),
+                        };
+                        stack.push(outline.sections.len());
+                        outline
+                            .sections
+                            .push((index, Section { path, close: None }));
+                        opened_here += 1;
+                        segment_start = i + 1;
+                    }
+                    '{' => value_braces += 1,
+                    '}' if value_braces > 0 => value_braces -= 1,
+                    // A closing brace after a value only ends a block opened on this line.
+                    '}' if segment.trim().is_empty() || opened_here > 0 => {
+                        push_option(&stack, &mut outline, segment_start..i);
+                        if let Some(open) = stack.pop() {
+                            outline.sections[open].1.close = Some(index);
+                        }
+                        opened_here = opened_here.saturating_sub(1);
+                        segment_start = i + 1;
+                    }
+                    _ => {}
+                }
+            }
+
+            push_option(&stack, &mut outline, segment_start..end);
+        }
+
+        outline
+    }
+
+    /// Returns every option line with the full path of its key, in file order.
+    pub fn option_lines(&self) -> Vec<(usize, String)> {
+        self.outline()
+            .options
+            .into_iter()
+            .map(|option| (option.line, option.path))
+            .collect()
+    }
+
+    /// Returns the last definition of `path`, which is the one Hyprland uses.
+    fn find_assignment(&self, path: &str) -> Option<(usize, Assignment)> {
+        self.outline()
+            .options
+            .into_iter()
+            .rev()
+            .find(|option| option.path == path)
+            .map(|option| (option.line, option.assignment))
+    }
+
+    /// Returns the line of the last definition of `path`, which is the one Hyprland uses.
+    pub fn find_option(&self, path: &str) -> Option<usize> {
+        self.find_assignment(path).map(|(index, _)| index)
+    }
+
+    pub fn line(&self, index: usize) -> &str {
+        self.lines[index].trim_end_matches('\r')
+    }
+
+    /// Returns the first top-level line whose content equals `raw`, ignoring indentation.
+    /// Lines inside `name { }` blocks never match.
+    pub fn find_line(&self, raw: &str) -> Option<usize> {
+        let raw = raw.trim();
+        let depths = self.outline().depths;
+        self.lines
+            .iter()
+            .zip(depths)
+            .position(|(line, depth)| depth == 0 && line.trim() == raw)
+    }
+
+    fn replace_value(&mut self, index: usize, assignment: Assignment, value: &str) {
+        let line = &mut self.lines[index];
+        let needs_space =
+            assignment.value.is_empty() && line[..assignment.value.start].ends_with('=');
+        let value = if needs_space {
+            format!(" {value}")
+        } else {
+            value.to_string()
+        };
+        line.replace_range(assignment.value, &value);
+    }
+
+    /// Sets `path` (e.g. `general:snap:enabled`) to `value`. An existing definition keeps its
+    /// formatting; otherwise the option is added to the innermost existing block of its
+    /// category, creating the missing blocks.
+    pub fn set_option(&mut self, path: &str, value: &str) {
+        if let Some((index, assignment)) = self.find_assignment(path) {
+            self.replace_value(index, assignment, value);
+            return;
+        }
+
+        // Blocks that close on the line they open on have no room for another line.
+        let sections = self.outline().sections;
+        let parent = sections
+            .iter()
+            .filter(|(open, section)| {
+                section.close.is_some_and(|close| close > *open)
+                    && path.starts_with(&format!("{}:", section.path))
+            })
+            .max_by_key(|(open, section)| (section.path.len(), *open));
+
+        let indent_unit = self.indent_unit();
+
+        let (insert_at, depth, remainder) = match parent {
+            Some((open, section)) => {
+                let close = section.close.unwrap_or(self.lines.len());
+                let base = &self.lines[*open]
+                    [..self.lines[*open].len() - self.lines[*open].trim_start().len()];
+                let depth = base.len() / indent_unit.len().max(1) + 1;
+                (close, depth, &path[section.path.len() + 1..])
+            }
+            None => {
+                if self
+                    .lines
+                    .last()
+                    .is_some_and(|line| !line.trim().is_empty())
+                {
+                    let blank = self.new_line(String::new());
+                    self.lines.push(blank);
+                }
+                (self.lines.len(), 0, path)
+            }
+        };
+
+        let mut blocks: Vec<&str> = remainder.split(':').collect();
+        let key = blocks.pop().unwrap_or(remainder);
+
+        let mut new_lines = Vec::new();
+        for (i, block) in blocks.iter().enumerate() {
+            new_lines.push(format!("{}{block} {{", indent_unit.repeat(depth + i)));
+        }
+        new_lines.push(format!(
+            "{}{key} = {value}",
+            indent_unit.repeat(depth + blocks.len())
+        ));
+        for i in (0..blocks.len()).rev() {
+            new_lines.push(format!("{}}}", indent_unit.repeat(depth + i)));
+        }
+
+        let new_lines: Vec<String> = new_lines
+            .into_iter()
+            .map(|line| self.new_line(line))
+            .collect();
+        self.lines.splice(insert_at..insert_at, new_lines);
+    }
+
+    /// Replaces the keyword and/or value of the line at `index`, keeping everything else.
+    pub fn update_line(&mut self, index: usize, name: Option<&str>, value: Option<&str>) {
+        if let Some(value) = value
+            && let Some(assignment) = parse_assignment(&self.lines[index])
+        {
+            self.replace_value(index, assignment, value);
+        }
+        if let Some(name) = name
+            && let Some(assignment) = parse_assignment(&self.lines[index])
+        {
+            self.lines[index].replace_range(assignment.key, name);
+        }
+    }
+
+    /// Removes the first top-level line equal to `raw`. Returns `false` if there is none.
+    pub fn remove_line(&mut self, raw: &str) -> bool {
+        match self.find_line(raw) {
+            Some(index) => {
+                self.lines.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Appends a top-level `name = value` line and returns its index.
+    pub fn push_line(&mut self, name: &str, value: &str) -> usize {
+        self.push_raw_line(&format!("{name} = {value}"))
+    }
+
+    /// Appends `text` as a new line and returns its index.
+    pub fn push_raw_line(&mut self, text: &str) -> usize {
+        let line = self.new_line(text.to_string());
+        self.lines.push(line);
+        self.lines.len() - 1
+    }
+
+    /// Inserts `text` as a new line before `index`.
+    pub fn insert_raw_line(&mut self, index: usize, text: &str) {
+        let line = self.new_line(text.to_string());
+        self.lines.insert(index.min(self.lines.len()), line);
+        if self.lines.len() == 1 {
+            self.trailing_newline = true;
+        }
+    }
+}
+
+impl fmt::Display for ConfigDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.lines.join("\n"))?;
+        if self.trailing_newline {
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! fixture {
+        ($name:literal) => {
+            include_str!(concat!("../tests/fixtures/config_document/", $name))
+        };
+    }
+
+    fn crlf(text: &str) -> String {
+        text.replace('\n', "\r\n")
+    }
+
+    fn edit_formatting(text: &str) -> String {
+        let mut document = ConfigDocument::parse(text);
+        document.set_option("general:gaps_in", "8");
+        document.set_option("decoration:rounding", "4");
+        document.to_string()
+    }
+
+    fn edit_nested_blocks(text: &str) -> String {
+        let mut document = ConfigDocument::parse(text);
+        document.set_option("general:snap:enabled", "true");
+        document.set_option("decoration:blur:size", "4");
+        document.set_option("input:touchpad:natural_scroll", "true");
+        document.to_string()
+    }
+
+    #[test]
+    fn round_trip_is_byte_identical() {
+        for text in [
+            fixture!("formatting.conf"),
+            fixture!("nested_blocks.conf"),
+            fixture!("one_line_blocks.conf"),
+            fixture!("rows.conf"),
+            "",
+            "no trailing newline",
+        ] {
+            assert_eq!(ConfigDocument::parse(text).to_string(), text);
+            assert_eq!(ConfigDocument::parse(&crlf(text)).to_string(), crlf(text));
+        }
+    }
+
+    #[test]
+    fn edits_keep_comments_blank_lines_and_alignment() {
+        assert_eq!(
+            edit_formatting(fixture!("formatting.conf")),
+            fixture!("formatting.expected.conf")
+        );
+    }
+
+    #[test]
+    fn crlf_files_stay_crlf() {
+        assert_eq!(
+            edit_formatting(&crlf(fixture!("formatting.conf"))),
+            crlf(fixture!("formatting.expected.conf"))
+        );
+        assert_eq!(
+            edit_nested_blocks(&crlf(fixture!("nested_blocks.conf"))),
+            crlf(fixture!("nested_blocks.expected.conf"))
+        );
+    }
+
+    #[test]
+    fn set_option_creates_nested_blocks() {
+        assert_eq!(
+            edit_nested_blocks(fixture!("nested_blocks.conf")),
+            fixture!("nested_blocks.expected.conf")
+        );
+    }
+
+    #[test]
+    fn key_prefix_does_not_match_longer_key() {
+        let mut document = ConfigDocument::parse(fixture!("prefix_keys.conf"));
+        assert_eq!(document.find_option("general:gaps"), None);
+        document.set_option("general:gaps", "3");
+        assert_eq!(document.to_string(), fixture!("prefix_keys.expected.conf"));
+    }
+
+    #[test]
+    fn last_definition_wins() {
+        let mut document = ConfigDocument::parse(fixture!("last_definition.conf"));
+        assert_eq!(document.find_option("general:border_size"), Some(7));
+        document.set_option("general:border_size", "4");
+        assert_eq!(
+            document.to_string(),
+            fixture!("last_definition.expected.conf")
+        );
+    }
+
+    #[test]
+    fn rows_are_matched_at_the_top_level_only() {
+        let mut document = ConfigDocument::parse(fixture!("rows.conf"));
+        assert_eq!(
+            document.find_line("bind = SUPER, TAB, hyprexpo:expo, toggle"),
+            Some(6)
+        );
+
+        let index = document
+            .find_line("  bind = SUPER, Q, killactive # close")
+            .unwrap();
+        document.update_line(index, None, Some("SUPER, C, killactive"));
+        assert!(document.remove_line("bind = SUPER, TAB, hyprexpo:expo, toggle"));
+        assert!(!document.remove_line("bind = SUPER, TAB, hyprexpo:expo, toggle"));
+        assert!(!document.remove_line("bind = SUPER, X, exit"));
+
+        assert_eq!(document.to_string(), fixture!("rows.expected.conf"));
+    }
+
+    #[test]
+    fn update_line_renames_the_keyword() {
+        let mut document = ConfigDocument::parse("bind = SUPER, Q, killactive\n");
+        document.update_line(0, Some("bindr"), None);
+        assert_eq!(document.to_string(), "bindr = SUPER, Q, killactive\n");
+    }
+
+    #[test]
+    fn one_line_blocks_are_outlined() {
+        let mut document = ConfigDocument::parse(fixture!("one_line_blocks.conf"));
+        assert_eq!(
+            document.option_lines(),
+            vec![
+                (0, "general:gaps_in".to_string()),
+                (1, "windowrule".to_string()),
+                (2, "decoration:blur:size".to_string()),
+            ]
+        );
+
+        document.set_option("general:gaps_in", "7");
+        document.set_option("decoration:blur:size", "6");
+        document.set_option("general:border_size", "2");
+        assert_eq!(
+            document.to_string(),
+            fixture!("one_line_blocks.expected.conf")
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use crate::utils::apply_profile_rules;
+use std::{
+    env,
+    error::Error,
+    io::{BufRead, BufReader},
+    os::unix::net::UnixStream,
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+
+/// Monitors usually come and go in bursts, e.g. when a dock is plugged in.
+const SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+/// Path of the socket Hyprland publishes its events on.
+fn event_socket_path() -> Result<PathBuf, Box<dyn Error>> {
+    let signature = env::var("HYPRLAND_INSTANCE_SIGNATURE")
+        .map_err(|_| "HYPRLAND_INSTANCE_SIGNATURE is not set, is Hyprland running?")?;
+
+    let mut candidates = Vec::new();
+    if let Ok(runtime_dir) = env::var("XDG_RUNTIME_DIR") {
+        candidates.push(PathBuf::from(runtime_dir).join("hypr").join(&signature));
+    }
+    candidates.push(PathBuf::from("/tmp/hypr").join(&signature));
+
+    candidates
+        .into_iter()
+        .map(|dir| dir.join(".socket2.sock"))
+        .find(|path| path.exists())
+        .ok_or_else(|| "Hyprland event socket not found".into())
+}
+
+fn apply() {
+    match apply_profile_rules() {
+        Ok(Some(profile)) => println!("Switched to profile: {}", profile),
+        Ok(None) => {}
+        Err(e) => eprintln!("Failed to apply profile rules: {}", e),
+    }
+}
+
+/// Runs `hyprviz --daemon`: applies the profile rules once, then again whenever a monitor is
+/// added or removed. Returns when Hyprland closes the event socket.
+pub fn run_daemon() -> Result<(), Box<dyn Error>> {
+    let socket_path = event_socket_path()?;
+    let stream = UnixStream::connect(&socket_path)
+        .map_err(|e| format!("failed to connect to {}: {}", socket_path.display(), e))?;
+
+    println!("Listening for monitor changes on {}", socket_path.display());
+    apply();
+
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        let event = line
+            .split_once(">>")
+            .map_or(line.as_str(), |(event, _)| event);
+
+        // The v2 events are sent alongside these, so they are not handled separately.
+        if matches!(event, "monitoradded" | "monitorremoved") {
+            thread::sleep(SETTLE_DELAY);
+            apply();
+        }
+    }
+
+    Ok(())
+}
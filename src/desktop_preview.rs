@@ -0,0 +1,584 @@
+use crate::{
+    hyprland::{Angle, CssGaps, HyprColor, HyprGradient, Vec2},
+    utils::{HistoryManager, MARGIN_NORMAL, TransformedConfig, parse_bool, transform_config},
+    widget::WidgetData,
+};
+use gtk::{
+    Align, Box, ColorDialogButton, DrawingArea, DropDown, Entry, Label, Orientation, SpinButton,
+    Switch,
+    cairo::{Context, FillRule, LinearGradient},
+    glib,
+    prelude::*,
+};
+use hyprparser::HyprlandConfig;
+use rust_i18n::t;
+use std::{cell::RefCell, collections::HashMap, f64::consts::FRAC_PI_2, rc::Rc, str::FromStr};
+
+pub const DESKTOP_PREVIEW_CATEGORIES: [&str; 3] = ["general", "decoration", "group"];
+
+const MONITOR_WIDTH: f64 = 1280.0;
+const MONITOR_HEIGHT: f64 = 720.0;
+const CORNER_STEPS: usize = 12;
+
+struct PreviewValues {
+    gaps_in: (f64, f64, f64, f64),
+    gaps_out: (f64, f64, f64, f64),
+    border_size: f64,
+    active_border: HyprGradient,
+    inactive_border: HyprGradient,
+    rounding: f64,
+    rounding_power: f64,
+    active_opacity: f64,
+    inactive_opacity: f64,
+    dim_inactive: bool,
+    dim_strength: f64,
+    shadow_enabled: bool,
+    shadow_range: f64,
+    shadow_render_power: f64,
+    shadow_sharp: bool,
+    shadow_color: HyprColor,
+    shadow_color_inactive: HyprColor,
+    shadow_offset: Vec2,
+    shadow_scale: f64,
+    group_border_active: HyprGradient,
+    group_border_inactive: HyprGradient,
+    groupbar_enabled: bool,
+    groupbar_gradients: bool,
+    groupbar_height: f64,
+    groupbar_indicator_height: f64,
+    groupbar_rounding: f64,
+    groupbar_rounding_power: f64,
+    groupbar_gaps_in: f64,
+    groupbar_gaps_out: f64,
+    groupbar_active: HyprGradient,
+    groupbar_inactive: HyprGradient,
+}
+
+impl PreviewValues {
+    fn resolve(history: &HistoryManager, config: &TransformedConfig) -> Self {
+        let get = |category: &str, name: &str, default: &str| {
+            history.resolve_value_with_history(config, category, name, default)
+        };
+        let float = |category: &str, name: &str, default: f64| {
+            get(category, name, &default.to_string())
+                .trim()
+                .parse::<f64>()
+                .unwrap_or(default)
+        };
+        let boolean = |category: &str, name: &str, default: bool| {
+            parse_bool(&get(category, name, &default.to_string())).unwrap_or(default)
+        };
+        let gaps = |name: &str, default: u32| {
+            let (top, right, bottom, left) =
+                CssGaps::from_str(&get("general", name, &default.to_string()))
+                    .unwrap_or(CssGaps::All(default))
+                    .to_sides();
+            (top as f64, right as f64, bottom as f64, left as f64)
+        };
+        let gradient = |category: &str, name: &str, default: &str| {
+            HyprGradient::from_str(&get(category, name, default))
+                .or_else(|_| HyprGradient::from_str(default))
+                .unwrap_or_default()
+        };
+        let color = |category: &str, name: &str, default: &str| {
+            HyprColor::from_str(&get(category, name, default)).ok()
+        };
+
+        let shadow_color = color("decoration", "shadow:color", "#1A1A1AEE")
+            .unwrap_or(HyprColor::Rgba(0x1A, 0x1A, 0x1A, 0xEE));
+
+        Self {
+            gaps_in: gaps("gaps_in", 5),
+            gaps_out: gaps("gaps_out", 20),
+            border_size: float("general", "border_size", 1.0),
+            active_border: gradient("general", "col.active_border", "#FFFFFFFF"),
+            inactive_border: gradient("general", "col.inactive_border", "#444444FF"),
+            rounding: float("decoration", "rounding", 0.0),
+            rounding_power: float("decoration", "rounding_power", 2.0),
+            active_opacity: float("decoration", "active_opacity", 1.0),
+            inactive_opacity: float("decoration", "inactive_opacity", 1.0),
+            dim_inactive: boolean("decoration", "dim_inactive", false),
+            dim_strength: float("decoration", "dim_strength", 0.5),
+            shadow_enabled: boolean("decoration", "shadow:enabled", true),
+            shadow_range: float("decoration", "shadow:range", 4.0),
+            shadow_render_power: float("decoration", "shadow:render_power", 3.0),
+            shadow_sharp: boolean("decoration", "shadow:sharp", false),
+            shadow_color,
+            shadow_color_inactive: color("decoration", "shadow:color_inactive", "")
+                .unwrap_or(shadow_color),
+            shadow_offset: Vec2::from_str(&get("decoration", "shadow:offset", "0 0"))
+                .unwrap_or_default(),
+            shadow_scale: float("decoration", "shadow:scale", 1.0),
+            group_border_active: gradient("group", "col.border_active", "#FFFF0066"),
+            group_border_inactive: gradient("group", "col.border_inactive", "#77770066"),
+            groupbar_enabled: boolean("group", "groupbar:enabled", true),
+            groupbar_gradients: boolean("group", "groupbar:gradients", false),
+            groupbar_height: float("group", "groupbar:height", 14.0),
+            groupbar_indicator_height: float("group", "groupbar:indicator_height", 3.0),
+            groupbar_rounding: float("group", "groupbar:rounding", 1.0),
+            groupbar_rounding_power: float("group", "groupbar:rounding_power", 2.0),
+            groupbar_gaps_in: float("group", "groupbar:gaps_in", 2.0),
+            groupbar_gaps_out: float("group", "groupbar:gaps_out", 2.0),
+            groupbar_active: gradient("group", "groupbar:col.active", "#66FFFF00"),
+            groupbar_inactive: gradient("group", "groupbar:col.inactive", "#77770066"),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Rect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Rect {
+    fn from_edges(x0: f64, y0: f64, x1: f64, y1: f64) -> Self {
+        Self {
+            x: x0,
+            y: y0,
+            width: (x1 - x0).max(0.0),
+            height: (y1 - y0).max(0.0),
+        }
+    }
+
+    fn inset(self, amount: f64) -> Self {
+        Self::from_edges(
+            self.x + amount,
+            self.y + amount,
+            self.x + self.width - amount,
+            self.y + self.height - amount,
+        )
+    }
+}
+
+/// Adds a rectangle with superellipse corners to the current path.
+/// A `power` of 2 produces regular circular corners.
+fn rounded_rect_path(cr: &Context, rect: Rect, radius: f64, power: f64) {
+    let r = radius.min(rect.width / 2.0).min(rect.height / 2.0).max(0.0);
+
+    if r <= f64::EPSILON {
+        cr.rectangle(rect.x, rect.y, rect.width, rect.height);
+        return;
+    }
+
+    let exponent = 2.0 / power.max(1.0);
+    let (x, y, w, h) = (rect.x, rect.y, rect.width, rect.height);
+    let corners = [
+        (x + w - r, y + r, 1.0, -1.0, true),
+        (x + w - r, y + h - r, 1.0, 1.0, false),
+        (x + r, y + h - r, -1.0, 1.0, true),
+        (x + r, y + r, -1.0, -1.0, false),
+    ];
+
+    cr.new_sub_path();
+    for (cx, cy, sx, sy, descending) in corners {
+        for i in 0..=CORNER_STEPS {
+            let step = if descending { CORNER_STEPS - i } else { i };
+            let t = step as f64 / CORNER_STEPS as f64 * FRAC_PI_2;
+            cr.line_to(
+                cx + sx * r * t.cos().max(0.0).powf(exponent),
+                cy + sy * r * t.sin().max(0.0).powf(exponent),
+            );
+        }
+    }
+    cr.close_path();
+}
+
+fn set_source_gradient(
+    cr: &Context,
+    gradient: &HyprGradient,
+    rect: Rect,
+    alpha: f64,
+) -> Result<(), gtk::cairo::Error> {
+    let colors: Vec<_> = gradient.colors.iter().map(|c| c.to_gtk_rgba()).collect();
+
+    if colors.len() <= 1 {
+        let rgba = colors
+            .first()
+            .copied()
+            .unwrap_or_else(|| HyprColor::default().to_gtk_rgba());
+        cr.set_source_rgba(
+            rgba.red() as f64,
+            rgba.green() as f64,
+            rgba.blue() as f64,
+            rgba.alpha() as f64 * alpha,
+        );
+        return Ok(());
+    }
+
+    let degrees = match gradient.angle {
+        Some(Angle::Degrees(degrees)) => degrees as f64,
+        None => 0.0,
+    };
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let half = (rect.width * cos.abs() + rect.height * sin.abs()) / 2.0;
+    let (cx, cy) = (rect.x + rect.width / 2.0, rect.y + rect.height / 2.0);
+
+    let pattern = LinearGradient::new(
+        cx - cos * half,
+        cy + sin * half,
+        cx + cos * half,
+        cy - sin * half,
+    );
+    let last = (colors.len() - 1) as f64;
+    for (i, rgba) in colors.iter().enumerate() {
+        pattern.add_color_stop_rgba(
+            i as f64 / last,
+            rgba.red() as f64,
+            rgba.green() as f64,
+            rgba.blue() as f64,
+            rgba.alpha() as f64 * alpha,
+        );
+    }
+
+    cr.set_source(&pattern)
+}
+
+fn draw_shadow(
+    cr: &Context,
+    values: &PreviewValues,
+    rect: Rect,
+    radius: f64,
+    color: HyprColor,
+) -> Result<(), gtk::cairo::Error> {
+    if !values.shadow_enabled || values.shadow_range <= 0.0 {
+        return Ok(());
+    }
+
+    let rgba = color.to_gtk_rgba();
+    let scale = values.shadow_scale.clamp(0.0, 1.0);
+    let shadow_rect = Rect {
+        x: rect.x + rect.width * (1.0 - scale) / 2.0 + values.shadow_offset.0,
+        y: rect.y + rect.height * (1.0 - scale) / 2.0 + values.shadow_offset.1,
+        width: rect.width * scale,
+        height: rect.height * scale,
+    };
+
+    if values.shadow_sharp {
+        cr.set_source_rgba(
+            rgba.red() as f64,
+            rgba.green() as f64,
+            rgba.blue() as f64,
+            rgba.alpha() as f64,
+        );
+        rounded_rect_path(
+            cr,
+            shadow_rect.inset(-values.shadow_range),
+            radius + values.shadow_range,
+            values.rounding_power,
+        );
+        return cr.fill();
+    }
+
+    let steps = values.shadow_range.ceil().clamp(1.0, 24.0) as usize;
+    for i in (0..steps).rev() {
+        let spread = values.shadow_range * (i + 1) as f64 / steps as f64;
+        let falloff = (1.0 - i as f64 / steps as f64).powf(values.shadow_render_power);
+        cr.set_source_rgba(
+            rgba.red() as f64,
+            rgba.green() as f64,
+            rgba.blue() as f64,
+            rgba.alpha() as f64 * falloff * 2.0 / steps as f64,
+        );
+        rounded_rect_path(
+            cr,
+            shadow_rect.inset(-spread),
+            radius + spread,
+            values.rounding_power,
+        );
+        cr.fill()?;
+    }
+
+    Ok(())
+}
+
+fn draw_window(
+    cr: &Context,
+    values: &PreviewValues,
+    rect: Rect,
+    border: &HyprGradient,
+    is_active: bool,
+    surface: (f64, f64, f64),
+) -> Result<(), gtk::cairo::Error> {
+    let outer_radius = if values.rounding > 0.0 {
+        values.rounding + values.border_size
+    } else {
+        0.0
+    };
+    let shadow_color = if is_active {
+        values.shadow_color
+    } else {
+        values.shadow_color_inactive
+    };
+    draw_shadow(cr, values, rect, outer_radius, shadow_color)?;
+
+    let content = rect.inset(values.border_size);
+    let opacity = if is_active {
+        values.active_opacity
+    } else {
+        values.inactive_opacity
+    };
+
+    cr.set_source_rgba(surface.0, surface.1, surface.2, opacity.clamp(0.0, 1.0));
+    rounded_rect_path(cr, content, values.rounding, values.rounding_power);
+    cr.fill()?;
+
+    cr.set_source_rgba(1.0, 1.0, 1.0, 0.12 * opacity);
+    let line_height = 8.0;
+    for i in 0..3 {
+        let line_y = content.y + values.rounding.max(12.0) + i as f64 * line_height * 2.0;
+        if line_y + line_height > content.y + content.height - 6.0 {
+            break;
+        }
+        let line_width = (content.width - 24.0) * [0.6, 0.85, 0.4][i];
+        cr.rectangle(content.x + 12.0, line_y, line_width.max(0.0), line_height);
+    }
+    cr.fill()?;
+
+    if !is_active && values.dim_inactive {
+        cr.set_source_rgba(0.0, 0.0, 0.0, values.dim_strength.clamp(0.0, 1.0));
+        rounded_rect_path(cr, content, values.rounding, values.rounding_power);
+        cr.fill()?;
+    }
+
+    if values.border_size > 0.0 {
+        set_source_gradient(cr, border, rect, 1.0)?;
+        cr.set_fill_rule(FillRule::EvenOdd);
+        rounded_rect_path(cr, rect, outer_radius, values.rounding_power);
+        rounded_rect_path(cr, content, values.rounding, values.rounding_power);
+        cr.fill()?;
+        cr.set_fill_rule(FillRule::Winding);
+    }
+
+    Ok(())
+}
+
+fn draw_groupbar(
+    cr: &Context,
+    values: &PreviewValues,
+    rect: Rect,
+    tabs: usize,
+    active_tab: usize,
+) -> Result<(), gtk::cairo::Error> {
+    let tab_width =
+        (rect.width - values.groupbar_gaps_in * (tabs.saturating_sub(1)) as f64) / tabs as f64;
+
+    for tab in 0..tabs {
+        let gradient = if tab == active_tab {
+            &values.groupbar_active
+        } else {
+            &values.groupbar_inactive
+        };
+        let tab_rect = Rect {
+            x: rect.x + tab as f64 * (tab_width + values.groupbar_gaps_in),
+            y: rect.y,
+            width: tab_width.max(0.0),
+            height: values.groupbar_height,
+        };
+
+        if values.groupbar_gradients {
+            set_source_gradient(cr, gradient, tab_rect, 1.0)?;
+            rounded_rect_path(
+                cr,
+                tab_rect,
+                values.groupbar_rounding,
+                values.groupbar_rounding_power,
+            );
+            cr.fill()?;
+        }
+
+        let indicator_rect = Rect {
+            y: tab_rect.y + tab_rect.height - values.groupbar_indicator_height,
+            height: values.groupbar_indicator_height,
+            ..tab_rect
+        };
+        set_source_gradient(cr, gradient, indicator_rect, 1.0)?;
+        rounded_rect_path(
+            cr,
+            indicator_rect,
+            values.groupbar_rounding,
+            values.groupbar_rounding_power,
+        );
+        cr.fill()?;
+    }
+
+    Ok(())
+}
+
+fn draw_desktop(
+    cr: &Context,
+    width: f64,
+    height: f64,
+    values: &PreviewValues,
+) -> Result<(), gtk::cairo::Error> {
+    let get_theme_color = |name: &str, default: (f64, f64, f64)| {
+        gtk::gdk::RGBA::parse(name)
+            .ok()
+            .map(|rgba| (rgba.red() as f64, rgba.green() as f64, rgba.blue() as f64))
+            .unwrap_or(default)
+    };
+    let (accent_r, accent_g, accent_b) =
+        get_theme_color("@theme_selected_bg_color", (0.2, 0.5, 1.0));
+    let surface = get_theme_color("@theme_bg_color", (0.18, 0.18, 0.2));
+
+    let scale = (width / MONITOR_WIDTH).min(height / MONITOR_HEIGHT);
+    cr.translate(
+        (width - MONITOR_WIDTH * scale) / 2.0,
+        (height - MONITOR_HEIGHT * scale) / 2.0,
+    );
+    cr.scale(scale, scale);
+
+    let wallpaper = LinearGradient::new(0.0, 0.0, MONITOR_WIDTH, MONITOR_HEIGHT);
+    wallpaper.add_color_stop_rgb(0.0, accent_r * 0.6, accent_g * 0.6, accent_b * 0.6);
+    wallpaper.add_color_stop_rgb(1.0, accent_b * 0.4, accent_r * 0.3, accent_g * 0.5);
+    cr.set_source(&wallpaper)?;
+    cr.rectangle(0.0, 0.0, MONITOR_WIDTH, MONITOR_HEIGHT);
+    cr.fill()?;
+
+    let (out_top, out_right, out_bottom, out_left) = values.gaps_out;
+    let (in_top, in_right, in_bottom, in_left) = values.gaps_in;
+
+    let x0 = out_left;
+    let y0 = out_top;
+    let x1 = MONITOR_WIDTH - out_right;
+    let y1 = MONITOR_HEIGHT - out_bottom;
+    let mid_x = (x0 + x1) / 2.0;
+    let mid_y = (y0 + y1) / 2.0;
+
+    // A group on the left, the focused window top right and a regular window below it
+    let mut group_rect = Rect::from_edges(x0, y0, mid_x - in_right, y1);
+    let active_rect = Rect::from_edges(mid_x + in_left, y0, x1, mid_y - in_bottom);
+    let inactive_rect = Rect::from_edges(mid_x + in_left, mid_y + in_top, x1, y1);
+
+    if values.groupbar_enabled {
+        let bar_rect = Rect {
+            height: values.groupbar_height,
+            ..group_rect
+        };
+        let bar_space = values.groupbar_height + values.groupbar_gaps_out;
+        group_rect = Rect::from_edges(
+            group_rect.x,
+            group_rect.y + bar_space,
+            group_rect.x + group_rect.width,
+            group_rect.y + group_rect.height,
+        );
+        draw_groupbar(cr, values, bar_rect, 2, 0)?;
+    }
+
+    draw_window(
+        cr,
+        values,
+        group_rect,
+        &values.group_border_inactive,
+        false,
+        surface,
+    )?;
+    draw_window(
+        cr,
+        values,
+        inactive_rect,
+        &values.inactive_border,
+        false,
+        surface,
+    )?;
+    draw_window(
+        cr,
+        values,
+        active_rect,
+        &values.active_border,
+        true,
+        surface,
+    )?;
+
+    Ok(())
+}
+
+pub fn connect_redraw(drawing_area: &DrawingArea, options: &HashMap<String, WidgetData>) {
+    for widget_data in options.values() {
+        let widget = &widget_data.widget;
+
+        if let Some(spin_button) = widget.downcast_ref::<SpinButton>() {
+            spin_button.connect_value_changed(glib::clone!(
+                #[weak]
+                drawing_area,
+                move |_| drawing_area.queue_draw()
+            ));
+        } else if let Some(entry) = widget.downcast_ref::<Entry>() {
+            entry.connect_changed(glib::clone!(
+                #[weak]
+                drawing_area,
+                move |_| drawing_area.queue_draw()
+            ));
+        } else if let Some(switch) = widget.downcast_ref::<Switch>() {
+            switch.connect_active_notify(glib::clone!(
+                #[weak]
+                drawing_area,
+                move |_| drawing_area.queue_draw()
+            ));
+        } else if let Some(color_button) = widget.downcast_ref::<ColorDialogButton>() {
+            color_button.connect_rgba_notify(glib::clone!(
+                #[weak]
+                drawing_area,
+                move |_| drawing_area.queue_draw()
+            ));
+        } else if let Some(dropdown) = widget.downcast_ref::<DropDown>() {
+            dropdown.connect_selected_notify(glib::clone!(
+                #[weak]
+                drawing_area,
+                move |_| drawing_area.queue_draw()
+            ));
+        }
+    }
+}
+
+/// Builds a card with a mock desktop drawn from the pending values in `history`.
+/// It redraws whenever one of `options` changes, so it must be called after the
+/// option widgets have been bound to `history`.
+pub fn create_desktop_preview(
+    history: Rc<RefCell<HistoryManager>>,
+    config: &HyprlandConfig,
+    options: &HashMap<String, WidgetData>,
+) -> Box {
+    let card = Box::new(Orientation::Vertical, 6);
+    card.add_css_class("card");
+    card.set_margin_bottom(MARGIN_NORMAL * 2 / 3);
+
+    let title_label = Label::new(Some(&t!("desktop_preview.preview")));
+    title_label.set_halign(Align::Start);
+    title_label.add_css_class("heading");
+    title_label.set_margin_start(MARGIN_NORMAL);
+    title_label.set_margin_top(MARGIN_NORMAL);
+
+    let hint_label = Label::new(Some(&t!("desktop_preview.preview_description")));
+    hint_label.set_halign(Align::Start);
+    hint_label.set_wrap(true);
+    hint_label.add_css_class("dim-label");
+    hint_label.set_margin_start(MARGIN_NORMAL);
+    hint_label.set_margin_end(MARGIN_NORMAL);
+
+    let drawing_area = DrawingArea::new();
+    drawing_area.set_content_height(240);
+    drawing_area.set_hexpand(true);
+    drawing_area.set_margin_start(MARGIN_NORMAL);
+    drawing_area.set_margin_end(MARGIN_NORMAL);
+    drawing_area.set_margin_bottom(MARGIN_NORMAL);
+
+    let transformed_config = transform_config(config.to_string());
+    drawing_area.set_draw_func(move |_, cr, width, height| {
+        let values = PreviewValues::resolve(&history.borrow(), &transformed_config);
+
+        if let Err(e) = draw_desktop(cr, width as f64, height as f64, &values) {
+            glib::g_warning!("hyprviz", "Cairo draw error (desktop preview): {}", e);
+        }
+    });
+
+    connect_redraw(&drawing_area, options);
+
+    card.append(&title_label);
+    card.append(&hint_label);
+    card.append(&drawing_area);
+
+    card
+}
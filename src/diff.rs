@@ -0,0 +1,243 @@
+use gtk::{Align, Label, TextBuffer, TextTag, prelude::*};
+
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Remove,
+    Add,
+}
+
+/// Line-level edit script between `old` and `new` based on their longest common subsequence.
+/// Lines shared at the start and end are matched up front, so the table only covers the part
+/// that changed.
+fn diff_ops<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Op, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (old_middle, new_middle) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+
+    let (n, m) = (old_middle.len(), new_middle.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_middle[i] == new_middle[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len().max(new.len()));
+    ops.extend(old[..prefix].iter().map(|line| (Op::Equal, *line)));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_middle[i] == new_middle[j] {
+            ops.push((Op::Equal, old_middle[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push((Op::Remove, old_middle[i]));
+            i += 1;
+        } else {
+            ops.push((Op::Add, new_middle[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old_middle[i..].iter().map(|line| (Op::Remove, *line)));
+    ops.extend(new_middle[j..].iter().map(|line| (Op::Add, *line)));
+    ops.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| (Op::Equal, *line)),
+    );
+
+    ops
+}
+
+/// Renders a unified diff with three lines of context, or an empty string when the texts
+/// have the same lines.
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_ops(&old_lines, &new_lines);
+
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _))| *op != Op::Equal)
+        .map(|(i, _)| i)
+        .collect();
+
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    // Merge changes whose context would overlap into one hunk.
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &i in &changed {
+        let start = i.saturating_sub(CONTEXT_LINES);
+        let end = (i + CONTEXT_LINES + 1).min(ops.len());
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut output = format!("--- {old_label}\n+++ {new_label}\n");
+
+    for (start, end) in hunks {
+        let old_start = ops[..start].iter().filter(|(op, _)| *op != Op::Add).count();
+        let new_start = ops[..start]
+            .iter()
+            .filter(|(op, _)| *op != Op::Remove)
+            .count();
+        let old_count = ops[start..end]
+            .iter()
+            .filter(|(op, _)| *op != Op::Add)
+            .count();
+        let new_count = ops[start..end]
+            .iter()
+            .filter(|(op, _)| *op != Op::Remove)
+            .count();
+
+        // Empty ranges point at the line before them, as in diff(1).
+        let line_number = |start: usize, count: usize| if count == 0 { start } else { start + 1 };
+        output.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            line_number(old_start, old_count),
+            old_count,
+            line_number(new_start, new_count),
+            new_count
+        ));
+
+        for (op, line) in &ops[start..end] {
+            let prefix = match op {
+                Op::Equal => ' ',
+                Op::Remove => '-',
+                Op::Add => '+',
+            };
+            output.push(prefix);
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+/// Adds the `header`, `removed` and `added` tags used by [`append_diff_line`].
+pub fn add_diff_tags(buffer: &TextBuffer) {
+    let tag_table = buffer.tag_table();
+    tag_table.add(&TextTag::builder().name("header").weight(700).build());
+    tag_table.add(
+        &TextTag::builder()
+            .name("removed")
+            .foreground("#c01c28")
+            .build(),
+    );
+    tag_table.add(
+        &TextTag::builder()
+            .name("added")
+            .foreground("#26a269")
+            .build(),
+    );
+}
+
+pub fn append_diff_line(buffer: &TextBuffer, tag: Option<&str>, text: &str) {
+    let mut end = buffer.end_iter();
+    match tag {
+        Some(tag) => buffer.insert_with_tags_by_name(&mut end, &format!("{text}\n"), &[tag]),
+        None => buffer.insert(&mut end, &format!("{text}\n")),
+    }
+}
+
+/// Replaces the contents of `buffer` with `diff`, coloring lines by their unified diff prefix.
+pub fn set_unified_diff_text(buffer: &TextBuffer, diff: &str) {
+    buffer.set_text("");
+
+    for line in diff.lines() {
+        let tag = if line.starts_with("@@") || line.starts_with("---") || line.starts_with("+++") {
+            Some("header")
+        } else if line.starts_with('-') {
+            Some("removed")
+        } else if line.starts_with('+') {
+            Some("added")
+        } else {
+            None
+        };
+        append_diff_line(buffer, tag, line);
+    }
+}
+
+/// Selectable label for a value in a comparison grid, or `not_set` dimmed when there is no
+/// value.
+pub fn value_label(value: Option<&str>, not_set: &str) -> Label {
+    let label = Label::new(Some(value.unwrap_or(not_set)));
+    label.set_halign(Align::Start);
+    label.set_xalign(0.0);
+    label.set_wrap(true);
+    label.set_selectable(true);
+    label.set_hexpand(true);
+    if value.is_some() {
+        label.add_css_class("monospace");
+    } else {
+        label.add_css_class("dim-label");
+    }
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ops(old: &str, new: &str) -> Vec<(Op, String)> {
+        let old: Vec<&str> = old.lines().collect();
+        let new: Vec<&str> = new.lines().collect();
+        diff_ops(&old, &new)
+            .into_iter()
+            .map(|(op, line)| (op, line.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn shared_start_and_end_stay_equal() {
+        assert_eq!(
+            ops("a\nb\nc\nd", "a\nx\nc\nd"),
+            vec![
+                (Op::Equal, "a".to_string()),
+                (Op::Remove, "b".to_string()),
+                (Op::Add, "x".to_string()),
+                (Op::Equal, "c".to_string()),
+                (Op::Equal, "d".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn repeated_lines_are_not_matched_twice() {
+        assert_eq!(
+            ops("a\na", "a"),
+            vec![(Op::Equal, "a".to_string()), (Op::Remove, "a".to_string())]
+        );
+        assert_eq!(
+            ops("a", "a\na"),
+            vec![(Op::Equal, "a".to_string()), (Op::Add, "a".to_string())]
+        );
+    }
+
+    #[test]
+    fn identical_texts_have_no_diff() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "old", "new"), "");
+    }
+}
@@ -0,0 +1,267 @@
+use crate::{
+    diff::{add_diff_tags, append_diff_line},
+    gui::format_change_time,
+    utils::{Draft, DraftReason, MARGIN_NORMAL, delete_draft, load_drafts, save_draft},
+};
+use gtk::{
+    Align, Box, Button, Entry, Label, ListBox, Orientation, ScrolledWindow, SelectionMode,
+    TextBuffer, TextView, Window, WrapMode, prelude::*,
+};
+use rust_i18n::t;
+
+pub fn draft_title(draft: &Draft) -> String {
+    if draft.name.trim().is_empty() {
+        t!(
+            "drafts.unnamed_draft_",
+            time = format_change_time(draft.created)
+        )
+        .to_string()
+    } else {
+        draft.name.clone()
+    }
+}
+
+/// Fills `buffer` with one block per pending change, comparing the value currently on disk
+/// (`-`) with the value from the draft (`+`).
+fn fill_diff(
+    buffer: &TextBuffer,
+    draft: &Draft,
+    disk_value: &dyn Fn(&str, &str) -> Option<String>,
+) {
+    add_diff_tags(buffer);
+
+    let mut changes: Vec<_> = draft.changes.iter().collect();
+    changes.sort();
+
+    for ((category, key), value) in changes {
+        append_diff_line(buffer, Some("header"), &format!("@@ {category}:{key}"));
+        match disk_value(category, key) {
+            Some(disk) if disk == *value => {
+                append_diff_line(buffer, None, &format!("  {value}"));
+            }
+            Some(disk) => {
+                append_diff_line(buffer, Some("removed"), &format!("- {disk}"));
+                append_diff_line(buffer, Some("added"), &format!("+ {value}"));
+            }
+            None => {
+                append_diff_line(buffer, Some("added"), &format!("+ {value}"));
+            }
+        }
+    }
+}
+
+/// Shows the changes of `draft` against the config on disk and lets the user apply, keep or
+/// discard them. `disk_value` returns the value an option currently has on disk.
+pub fn show_draft_dialog<D, F>(parent: &impl IsA<Window>, draft: Draft, disk_value: D, on_apply: F)
+where
+    D: Fn(&str, &str) -> Option<String>,
+    F: Fn(&Draft) + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(t!("drafts.recover_changes").to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(560)
+        .default_height(600)
+        .build();
+
+    let main_box = Box::new(Orientation::Vertical, 10);
+    main_box.set_margin_top(MARGIN_NORMAL);
+    main_box.set_margin_bottom(MARGIN_NORMAL);
+    main_box.set_margin_start(MARGIN_NORMAL);
+    main_box.set_margin_end(MARGIN_NORMAL);
+
+    let description = match draft.reason {
+        DraftReason::Crash => t!("drafts.crash_description_", profile = draft.profile),
+        DraftReason::Conflict => t!("drafts.conflict_description_", profile = draft.profile),
+    };
+    let description_label = Label::new(Some(&description));
+    description_label.set_halign(Align::Start);
+    description_label.set_wrap(true);
+    description_label.set_xalign(0.0);
+    main_box.append(&description_label);
+
+    let name_box = Box::new(Orientation::Horizontal, 8);
+    let name_label = Label::new(Some(&t!("drafts.name")));
+    let name_entry = Entry::new();
+    name_entry.set_hexpand(true);
+    name_entry.set_text(&draft.name);
+    name_entry.set_placeholder_text(Some(&draft_title(&Draft {
+        name: String::new(),
+        ..draft.clone()
+    })));
+    name_box.append(&name_label);
+    name_box.append(&name_entry);
+    main_box.append(&name_box);
+
+    let diff_label = Label::new(Some(&t!(
+        "drafts.changes_against_disk_",
+        count = draft.changes.len()
+    )));
+    diff_label.set_halign(Align::Start);
+    diff_label.add_css_class("heading");
+    main_box.append(&diff_label);
+
+    let text_view = TextView::new();
+    text_view.set_editable(false);
+    text_view.set_cursor_visible(false);
+    text_view.set_monospace(true);
+    text_view.set_wrap_mode(WrapMode::WordChar);
+    fill_diff(&text_view.buffer(), &draft, &disk_value);
+
+    let scrolled_window = ScrolledWindow::new();
+    scrolled_window.set_vexpand(true);
+    scrolled_window.set_min_content_height(300);
+    scrolled_window.set_child(Some(&text_view));
+    main_box.append(&scrolled_window);
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+
+    let discard_button = Button::with_label(&t!("drafts.discard"));
+    discard_button.add_css_class("destructive-action");
+    let keep_button = Button::with_label(&t!("drafts.keep_as_draft"));
+    let apply_button = Button::with_label(&t!("drafts.apply"));
+    apply_button.add_css_class("suggested-action");
+
+    buttons_box.append(&discard_button);
+    buttons_box.append(&keep_button);
+    buttons_box.append(&apply_button);
+    main_box.append(&buttons_box);
+
+    dialog_window.set_child(Some(&main_box));
+
+    let named_draft = {
+        let name_entry = name_entry.clone();
+        let draft = draft.clone();
+        move || Draft {
+            name: name_entry.text().trim().to_string(),
+            ..draft.clone()
+        }
+    };
+
+    let dialog_window_clone = dialog_window.clone();
+    let draft_clone = draft.clone();
+    discard_button.connect_clicked(move |_| {
+        if let Err(e) = delete_draft(&draft_clone) {
+            eprintln!("[Drafts] Failed to delete draft: {}", e);
+        }
+        dialog_window_clone.close();
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    let named_draft_clone = named_draft.clone();
+    keep_button.connect_clicked(move |_| {
+        if let Err(e) = save_draft(&named_draft_clone()) {
+            eprintln!("[Drafts] Failed to save draft: {}", e);
+        }
+        dialog_window_clone.close();
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    apply_button.connect_clicked(move |_| {
+        let draft = named_draft();
+        on_apply(&draft);
+        if let Err(e) = delete_draft(&draft) {
+            eprintln!("[Drafts] Failed to delete draft: {}", e);
+        }
+        dialog_window_clone.close();
+    });
+
+    dialog_window.present();
+}
+
+/// Lists the drafts saved for `profile`; `on_review` is called with the draft to review.
+pub fn show_drafts_list<F>(parent: &impl IsA<Window>, profile: &str, on_review: F)
+where
+    F: Fn(Draft) + Clone + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(t!("drafts.drafts_of_", profile = profile).to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(420)
+        .default_height(400)
+        .build();
+
+    let main_box = Box::new(Orientation::Vertical, 10);
+    main_box.set_margin_top(MARGIN_NORMAL);
+    main_box.set_margin_bottom(MARGIN_NORMAL);
+    main_box.set_margin_start(MARGIN_NORMAL);
+    main_box.set_margin_end(MARGIN_NORMAL);
+
+    let drafts = load_drafts(profile);
+
+    if drafts.is_empty() {
+        let empty_label = Label::new(Some(&t!("drafts.no_drafts")));
+        empty_label.add_css_class("dim-label");
+        empty_label.set_vexpand(true);
+        main_box.append(&empty_label);
+    } else {
+        let list_box = ListBox::new();
+        list_box.set_selection_mode(SelectionMode::None);
+        list_box.add_css_class("boxed-list");
+
+        for draft in drafts {
+            let row_box = Box::new(Orientation::Horizontal, 8);
+            row_box.set_margin_start(MARGIN_NORMAL / 2);
+            row_box.set_margin_end(MARGIN_NORMAL / 2);
+            row_box.set_margin_top(MARGIN_NORMAL / 2);
+            row_box.set_margin_bottom(MARGIN_NORMAL / 2);
+
+            let text_box = Box::new(Orientation::Vertical, 2);
+            text_box.set_hexpand(true);
+
+            let title_label = Label::new(Some(&draft_title(&draft)));
+            title_label.set_halign(Align::Start);
+            title_label.add_css_class("heading");
+
+            let details_label = Label::new(Some(&t!(
+                "drafts.draft_details_",
+                count = draft.changes.len(),
+                time = format_change_time(draft.created)
+            )));
+            details_label.set_halign(Align::Start);
+            details_label.add_css_class("dim-label");
+
+            text_box.append(&title_label);
+            text_box.append(&details_label);
+
+            let review_button = Button::with_label(&t!("drafts.review"));
+            review_button.set_valign(Align::Center);
+
+            let dialog_window_clone = dialog_window.clone();
+            let on_review = on_review.clone();
+            review_button.connect_clicked(move |_| {
+                dialog_window_clone.close();
+                on_review(draft.clone());
+            });
+
+            row_box.append(&text_box);
+            row_box.append(&review_button);
+            list_box.append(&row_box);
+        }
+
+        let scrolled_window = ScrolledWindow::new();
+        scrolled_window.set_vexpand(true);
+        scrolled_window.set_child(Some(&list_box));
+        main_box.append(&scrolled_window);
+    }
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+    let close_button = Button::with_label(&t!("drafts.close"));
+    buttons_box.append(&close_button);
+    main_box.append(&buttons_box);
+
+    dialog_window.set_child(Some(&main_box));
+
+    let dialog_window_clone = dialog_window.clone();
+    close_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    dialog_window.present();
+}
@@ -0,0 +1,511 @@
+use crate::{
+    diff::value_label,
+    profile_diff::{DiffCopy, is_option_category, row_category, summarize},
+    utils::{MARGIN_NORMAL, unix_timestamp},
+};
+use gtk::{
+    Align, Box, Button, CheckButton, Grid, Label, Orientation, ScrolledWindow, Window, prelude::*,
+};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    rc::Rc,
+};
+
+/// Schema version written by [`HyprvizExport::to_json`]. Files without a `version` are the flat
+/// `category:name` maps older releases wrote, which only carry options.
+pub const EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportMeta {
+    /// hyprviz version that wrote the file.
+    #[serde(default)]
+    pub app_version: String,
+    #[serde(default)]
+    pub profile: String,
+    #[serde(default)]
+    pub exported_at: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportRow {
+    pub name: String,
+    pub value: String,
+}
+
+/// A profile as hyprviz exports it: option values and top-level rows, by category.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HyprvizExport {
+    pub version: u32,
+    #[serde(default)]
+    pub meta: ExportMeta,
+    #[serde(default)]
+    pub options: BTreeMap<String, BTreeMap<String, String>>,
+    /// Rows in the order they appear in the config.
+    #[serde(default)]
+    pub rows: BTreeMap<String, Vec<ExportRow>>,
+}
+
+impl HyprvizExport {
+    /// Exports the options and rows of the expanded config of `profile`.
+    pub fn from_config(profile: &str, config: &str) -> Self {
+        let (options, rows) = summarize(config);
+        let mut export = HyprvizExport {
+            version: EXPORT_VERSION,
+            meta: ExportMeta {
+                app_version: env!("CARGO_PKG_VERSION").to_string(),
+                profile: profile.to_string(),
+                exported_at: unix_timestamp(),
+            },
+            ..Default::default()
+        };
+
+        for ((category, key), value) in options {
+            export
+                .options
+                .entry(category)
+                .or_default()
+                .insert(key, value);
+        }
+        for (name, value) in rows {
+            export
+                .rows
+                .entry(row_category(&name).to_string())
+                .or_default()
+                .push(ExportRow { name, value });
+        }
+
+        export
+    }
+
+    /// Reads an export of any version up to [`EXPORT_VERSION`].
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let json: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        match json.get("version").and_then(Value::as_u64) {
+            Some(version) if version > EXPORT_VERSION as u64 => {
+                Err(t!("export.unsupported_version_", version = version).to_string())
+            }
+            Some(_) => serde_json::from_value(json).map_err(|e| e.to_string()),
+            None => {
+                let flat: HashMap<String, String> =
+                    serde_json::from_value(json).map_err(|e| e.to_string())?;
+                let mut export = HyprvizExport::default();
+                for (key, value) in flat {
+                    if let Some((category, name)) = key.split_once(':')
+                        && is_option_category(category)
+                    {
+                        export
+                            .options
+                            .entry(category.to_string())
+                            .or_default()
+                            .insert(name.to_string(), value);
+                    }
+                }
+                Ok(export)
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// How an imported entry relates to the current profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    Addition,
+    Removal,
+    /// The option is set to a different value in the current profile.
+    Conflict,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportEntry {
+    pub kind: ImportKind,
+    /// Value in the current profile, for conflicts.
+    pub current: Option<String>,
+    pub change: DiffCopy,
+}
+
+impl ImportEntry {
+    fn category(&self) -> &str {
+        match &self.change {
+            DiffCopy::Option { category, .. }
+            | DiffCopy::AddRow { category, .. }
+            | DiffCopy::RemoveRow { category, .. } => category,
+        }
+    }
+}
+
+/// What importing `imported` over `current` changes. Options the import does not set are kept;
+/// rows it does not have are removed only if `remove_missing_rows` is set.
+pub fn import_entries(
+    current: &HyprvizExport,
+    imported: &HyprvizExport,
+    remove_missing_rows: bool,
+) -> Vec<ImportEntry> {
+    let mut entries = Vec::new();
+
+    for (category, options) in &imported.options {
+        let current_options = current.options.get(category);
+        for (key, value) in options {
+            let current_value = current_options.and_then(|options| options.get(key));
+            let kind = match current_value {
+                Some(current_value) if current_value == value => continue,
+                Some(_) => ImportKind::Conflict,
+                None => ImportKind::Addition,
+            };
+            entries.push(ImportEntry {
+                kind,
+                current: current_value.cloned(),
+                change: DiffCopy::Option {
+                    category: category.clone(),
+                    key: key.clone(),
+                    value: value.clone(),
+                },
+            });
+        }
+    }
+
+    let mut categories: Vec<&String> = current.rows.keys().chain(imported.rows.keys()).collect();
+    categories.sort();
+    categories.dedup();
+
+    for category in categories {
+        // Rows are matched as a multiset, so a repeated line only matches once.
+        let mut current_rows = current.rows.get(category).cloned().unwrap_or_default();
+        let mut added = Vec::new();
+        for row in imported.rows.get(category).into_iter().flatten() {
+            match current_rows.iter().position(|r| r == row) {
+                Some(index) => {
+                    current_rows.remove(index);
+                }
+                None => added.push(row.clone()),
+            }
+        }
+
+        if !remove_missing_rows {
+            current_rows.clear();
+        }
+
+        entries.extend(current_rows.into_iter().map(|row| ImportEntry {
+            kind: ImportKind::Removal,
+            current: None,
+            change: DiffCopy::RemoveRow {
+                category: category.clone(),
+                name: row.name,
+                value: row.value,
+            },
+        }));
+        entries.extend(added.into_iter().map(|row| ImportEntry {
+            kind: ImportKind::Addition,
+            current: None,
+            change: DiffCopy::AddRow {
+                category: category.clone(),
+                name: row.name,
+                value: row.value,
+            },
+        }));
+    }
+
+    entries
+}
+
+/// Lists what an import changes, grouped by category, with `notes` about its source below the
+/// summary. Every entry can be left out; `on_apply` receives the changes that stay checked.
+pub fn show_import_preview<F>(
+    parent: &impl IsA<Window>,
+    title: &str,
+    notes: &[String],
+    entries: Vec<ImportEntry>,
+    categories: Vec<(String, &'static str)>,
+    on_apply: F,
+) where
+    F: Fn(Vec<DiffCopy>) + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(title)
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(760)
+        .default_height(560)
+        .build();
+
+    let main_box = Box::new(Orientation::Vertical, 10);
+    main_box.set_margin_top(MARGIN_NORMAL);
+    main_box.set_margin_bottom(MARGIN_NORMAL);
+    main_box.set_margin_start(MARGIN_NORMAL);
+    main_box.set_margin_end(MARGIN_NORMAL);
+
+    let count = |kind: ImportKind| entries.iter().filter(|e| e.kind == kind).count();
+    let summary_label = Label::new(Some(&if entries.is_empty() {
+        t!("export.nothing_to_import").to_string()
+    } else {
+        t!(
+            "export.summary_",
+            additions = count(ImportKind::Addition),
+            removals = count(ImportKind::Removal),
+            conflicts = count(ImportKind::Conflict)
+        )
+        .to_string()
+    }));
+    summary_label.set_halign(Align::Start);
+    summary_label.set_wrap(true);
+    summary_label.add_css_class("heading");
+    main_box.append(&summary_label);
+
+    for note in notes {
+        let note_label = Label::new(Some(note));
+        note_label.set_halign(Align::Start);
+        note_label.set_xalign(0.0);
+        note_label.set_wrap(true);
+        note_label.add_css_class("dim-label");
+        main_box.append(&note_label);
+    }
+
+    let content = Box::new(Orientation::Vertical, 6);
+    let scrolled_window = ScrolledWindow::new();
+    scrolled_window.set_vexpand(true);
+    scrolled_window.set_child(Some(&content));
+    main_box.append(&scrolled_window);
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+    let cancel_button = Button::with_label(&t!("export.cancel"));
+    let apply_button = Button::with_label(&t!("export.apply"));
+    apply_button.add_css_class("suggested-action");
+    apply_button.set_sensitive(!entries.is_empty());
+    buttons_box.append(&cancel_button);
+    buttons_box.append(&apply_button);
+    main_box.append(&buttons_box);
+
+    dialog_window.set_child(Some(&main_box));
+
+    let mut checks: Vec<(CheckButton, DiffCopy)> = Vec::new();
+    for (display_name, category) in &categories {
+        let category_entries: Vec<&ImportEntry> = entries
+            .iter()
+            .filter(|entry| entry.category() == *category)
+            .collect();
+        if category_entries.is_empty() {
+            continue;
+        }
+
+        let title_label = Label::new(Some(display_name.as_str()));
+        title_label.set_halign(Align::Start);
+        title_label.set_margin_top(MARGIN_NORMAL / 2);
+        title_label.add_css_class("heading");
+        content.append(&title_label);
+
+        let grid = Grid::new();
+        grid.set_column_spacing(12);
+        grid.set_row_spacing(4);
+
+        for (row, entry) in category_entries.into_iter().enumerate() {
+            let row = row as i32;
+            let (name, value) = match &entry.change {
+                DiffCopy::Option { key, value, .. } => (key, value),
+                DiffCopy::AddRow { name, value, .. } | DiffCopy::RemoveRow { name, value, .. } => {
+                    (name, value)
+                }
+            };
+
+            let check = CheckButton::new();
+            check.set_active(true);
+            check.set_valign(Align::Start);
+            check.set_tooltip_text(Some(&match entry.kind {
+                ImportKind::Addition => t!("export.addition"),
+                ImportKind::Removal => t!("export.removal"),
+                ImportKind::Conflict => t!("export.conflict"),
+            }));
+            grid.attach(&check, 0, row, 1, 1);
+
+            let name_label = Label::new(Some(&format!(
+                "{} {}",
+                match entry.kind {
+                    ImportKind::Addition => "+",
+                    ImportKind::Removal => "−",
+                    ImportKind::Conflict => "≠",
+                },
+                name
+            )));
+            name_label.set_halign(Align::Start);
+            name_label.set_valign(Align::Start);
+            name_label.add_css_class("dim-label");
+            grid.attach(&name_label, 1, row, 1, 1);
+
+            match (&entry.kind, &entry.current) {
+                (ImportKind::Conflict, Some(current)) => {
+                    grid.attach(&value_label(Some(current), ""), 2, row, 1, 1);
+                    grid.attach(&Label::new(Some("→")), 3, row, 1, 1);
+                    grid.attach(&value_label(Some(value), ""), 4, row, 1, 1);
+                }
+                _ => grid.attach(&value_label(Some(value), ""), 2, row, 3, 1),
+            }
+
+            checks.push((check, entry.change.clone()));
+        }
+
+        content.append(&grid);
+    }
+
+    let checks = Rc::new(checks);
+    for (check, _) in checks.iter() {
+        let checks = checks.clone();
+        let apply_button = apply_button.clone();
+        check.connect_toggled(move |_| {
+            apply_button.set_sensitive(checks.iter().any(|(check, _)| check.is_active()));
+        });
+    }
+
+    let dialog_window_clone = dialog_window.clone();
+    cancel_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    apply_button.connect_clicked(move |_| {
+        let changes = checks
+            .iter()
+            .filter(|(check, _)| check.is_active())
+            .map(|(_, change)| change.clone())
+            .collect();
+        dialog_window_clone.close();
+        on_apply(changes);
+    });
+
+    dialog_window.present();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export(options: &[(&str, &str, &str)], rows: &[(&str, &str, &str)]) -> HyprvizExport {
+        let mut export = HyprvizExport {
+            version: EXPORT_VERSION,
+            ..Default::default()
+        };
+        for (category, key, value) in options {
+            export
+                .options
+                .entry(category.to_string())
+                .or_default()
+                .insert(key.to_string(), value.to_string());
+        }
+        for (category, name, value) in rows {
+            export
+                .rows
+                .entry(category.to_string())
+                .or_default()
+                .push(ExportRow {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                });
+        }
+        export
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let newer = format!(r#"{{"version": {}, "options": {{}}}}"#, EXPORT_VERSION + 1);
+        assert!(HyprvizExport::parse(&newer).is_err());
+
+        let current = format!(r#"{{"version": {EXPORT_VERSION}, "options": {{}}}}"#);
+        assert!(HyprvizExport::parse(&current).is_ok());
+    }
+
+    #[test]
+    fn unversioned_files_are_read_as_flat_options() {
+        let export =
+            HyprvizExport::parse(r#"{"general:gaps_in": "5", "unknown:key": "1"}"#).unwrap();
+        assert_eq!(export.options.len(), 1);
+        assert_eq!(export.options["general"]["gaps_in"], "5");
+        assert!(export.rows.is_empty());
+    }
+
+    #[test]
+    fn options_are_added_or_conflict() {
+        let current = export(
+            &[("general", "gaps_in", "5"), ("general", "gaps_out", "20")],
+            &[],
+        );
+        let imported = export(
+            &[
+                ("general", "gaps_in", "5"),
+                ("general", "gaps_out", "10"),
+                ("general", "border_size", "2"),
+            ],
+            &[],
+        );
+
+        let entries = import_entries(&current, &imported, false);
+        let kinds: Vec<(ImportKind, Option<&str>, &DiffCopy)> = entries
+            .iter()
+            .map(|entry| (entry.kind, entry.current.as_deref(), &entry.change))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (
+                    ImportKind::Addition,
+                    None,
+                    &DiffCopy::Option {
+                        category: "general".to_string(),
+                        key: "border_size".to_string(),
+                        value: "2".to_string(),
+                    }
+                ),
+                (
+                    ImportKind::Conflict,
+                    Some("20"),
+                    &DiffCopy::Option {
+                        category: "general".to_string(),
+                        key: "gaps_out".to_string(),
+                        value: "10".to_string(),
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_rows_are_removed_only_when_asked() {
+        let current = export(
+            &[],
+            &[
+                ("exec", "exec-once", "waybar"),
+                ("exec", "exec-once", "waybar"),
+            ],
+        );
+        let imported = export(
+            &[],
+            &[
+                ("exec", "exec-once", "waybar"),
+                ("env", "env", "XCURSOR_SIZE,24"),
+            ],
+        );
+
+        let kinds = |remove_missing_rows| {
+            import_entries(&current, &imported, remove_missing_rows)
+                .iter()
+                .map(|entry| (entry.kind, entry.category().to_string()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            kinds(false),
+            vec![(ImportKind::Addition, "env".to_string())]
+        );
+        assert_eq!(
+            kinds(true),
+            vec![
+                (ImportKind::Addition, "env".to_string()),
+                (ImportKind::Removal, "exec".to_string()),
+            ]
+        );
+    }
+}
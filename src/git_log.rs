@@ -0,0 +1,295 @@
+use crate::{
+    diff::{add_diff_tags, set_unified_diff_text, unified_diff},
+    gui::format_change_time,
+    utils::{
+        GitRevision, GitSettings, MARGIN_NORMAL, display_path, get_config_path, git_file_at,
+        git_file_log, git_init, git_repository, load_git_settings, save_git_settings,
+    },
+};
+use gtk::{
+    AlertDialog, Align, Box, Button, DropDown, Label, ListBox, Orientation, Paned, ScrolledWindow,
+    SelectionMode, Switch, TextView, Window, gio, glib, prelude::*,
+};
+use rust_i18n::t;
+use std::{cell::RefCell, fs, rc::Rc};
+
+fn revision_row(revision: &GitRevision) -> Box {
+    let row_box = Box::new(Orientation::Vertical, 2);
+    row_box.set_margin_start(MARGIN_NORMAL / 2);
+    row_box.set_margin_end(MARGIN_NORMAL / 2);
+    row_box.set_margin_top(MARGIN_NORMAL / 3);
+    row_box.set_margin_bottom(MARGIN_NORMAL / 3);
+
+    let summary_label = Label::new(Some(&revision.summary));
+    summary_label.set_halign(Align::Start);
+    summary_label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+    row_box.append(&summary_label);
+
+    let details_label = Label::new(Some(&format!(
+        "{} · {}",
+        revision.short_hash(),
+        format_change_time(revision.time)
+    )));
+    details_label.set_halign(Align::Start);
+    details_label.add_css_class("dim-label");
+    details_label.add_css_class("caption");
+    row_box.append(&details_label);
+
+    row_box
+}
+
+fn repository_text() -> String {
+    match git_repository() {
+        Some(repository) => t!("git_log.repository_", path = display_path(&repository)).to_string(),
+        None => t!("git_log.no_repository").to_string(),
+    }
+}
+
+/// Shows the commits of a profile file, with the diff of each revision against the file as it
+/// is now. `on_checkout` is called with the profile and the revision to restore once confirmed.
+pub fn show_git_log<F>(
+    parent: &impl IsA<Window>,
+    profiles: Vec<String>,
+    active: &str,
+    on_checkout: F,
+) where
+    F: Fn(&str, &GitRevision) + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(t!("git_log.title").to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(860)
+        .default_height(600)
+        .build();
+
+    let main_box = Box::new(Orientation::Vertical, 10);
+    main_box.set_margin_top(MARGIN_NORMAL);
+    main_box.set_margin_bottom(MARGIN_NORMAL);
+    main_box.set_margin_start(MARGIN_NORMAL);
+    main_box.set_margin_end(MARGIN_NORMAL);
+
+    let settings_box = Box::new(Orientation::Horizontal, 6);
+    let enabled_switch = Switch::new();
+    enabled_switch.set_active(load_git_settings().enabled);
+    enabled_switch.set_valign(Align::Center);
+    let enabled_label = Label::new(Some(&t!("git_log.commit_after_save")));
+    settings_box.append(&enabled_switch);
+    settings_box.append(&enabled_label);
+    main_box.append(&settings_box);
+
+    let repository_box = Box::new(Orientation::Horizontal, 6);
+    let repository_label = Label::new(Some(&repository_text()));
+    repository_label.set_halign(Align::Start);
+    repository_label.set_hexpand(true);
+    repository_label.set_wrap(true);
+    repository_label.set_xalign(0.0);
+    repository_label.add_css_class("dim-label");
+    let init_button = Button::with_label(&t!("git_log.create_repository"));
+    init_button.set_visible(git_repository().is_none());
+    repository_box.append(&repository_label);
+    repository_box.append(&init_button);
+    main_box.append(&repository_box);
+
+    let profile_names: Vec<&str> = profiles.iter().map(String::as_str).collect();
+    let profile_dropdown = DropDown::from_strings(&profile_names);
+    if let Some(index) = profiles.iter().position(|p| p == active) {
+        profile_dropdown.set_selected(index as u32);
+    }
+    profile_dropdown.set_halign(Align::Start);
+    main_box.append(&profile_dropdown);
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::Single);
+    list_box.add_css_class("boxed-list");
+
+    let placeholder_label = Label::new(None);
+    placeholder_label.set_wrap(true);
+    placeholder_label.set_margin_start(MARGIN_NORMAL);
+    placeholder_label.set_margin_end(MARGIN_NORMAL);
+    placeholder_label.add_css_class("dim-label");
+    list_box.set_placeholder(Some(&placeholder_label));
+
+    let list_scrolled_window = ScrolledWindow::new();
+    list_scrolled_window.set_min_content_width(260);
+    list_scrolled_window.set_child(Some(&list_box));
+
+    let text_view = TextView::new();
+    text_view.set_editable(false);
+    text_view.set_cursor_visible(false);
+    text_view.set_monospace(true);
+    add_diff_tags(&text_view.buffer());
+
+    let diff_scrolled_window = ScrolledWindow::new();
+    diff_scrolled_window.set_child(Some(&text_view));
+
+    let paned = Paned::new(Orientation::Horizontal);
+    paned.set_start_child(Some(&list_scrolled_window));
+    paned.set_end_child(Some(&diff_scrolled_window));
+    paned.set_position(320);
+    paned.set_vexpand(true);
+    main_box.append(&paned);
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+
+    let close_button = Button::with_label(&t!("git_log.close"));
+    let checkout_button = Button::with_label(&t!("git_log.check_out"));
+    checkout_button.add_css_class("suggested-action");
+    checkout_button.set_sensitive(false);
+    buttons_box.append(&close_button);
+    buttons_box.append(&checkout_button);
+    main_box.append(&buttons_box);
+
+    dialog_window.set_child(Some(&main_box));
+
+    let profiles = Rc::new(profiles);
+    let revisions: Rc<RefCell<Vec<GitRevision>>> = Rc::new(RefCell::new(Vec::new()));
+
+    let selected_profile = {
+        let profiles = profiles.clone();
+        let profile_dropdown = profile_dropdown.clone();
+        move || profiles.get(profile_dropdown.selected() as usize).cloned()
+    };
+
+    let fill_revisions = {
+        let list_box = list_box.clone();
+        let revisions = revisions.clone();
+        let selected_profile = selected_profile.clone();
+        Rc::new(move || {
+            while let Some(child) = list_box.first_child() {
+                list_box.remove(&child);
+            }
+            revisions.borrow_mut().clear();
+
+            let Some(profile) = selected_profile() else {
+                return;
+            };
+            match git_file_log(&get_config_path(true, &profile)) {
+                Ok(log) if log.is_empty() => {
+                    placeholder_label.set_label(&t!("git_log.no_commits"));
+                }
+                Ok(log) => {
+                    for revision in &log {
+                        list_box.append(&revision_row(revision));
+                    }
+                    *revisions.borrow_mut() = log;
+                }
+                Err(e) => placeholder_label.set_label(&e),
+            }
+
+            if let Some(first_row) = list_box.row_at_index(0) {
+                list_box.select_row(Some(&first_row));
+            }
+        })
+    };
+    let fill_revisions_clone = fill_revisions.clone();
+    profile_dropdown.connect_selected_notify(move |_| fill_revisions_clone());
+
+    let revisions_clone = revisions.clone();
+    let selected_profile_clone = selected_profile.clone();
+    let checkout_button_clone = checkout_button.clone();
+    list_box.connect_row_selected(move |_, row| {
+        let revision =
+            row.and_then(|row| revisions_clone.borrow().get(row.index() as usize).cloned());
+        checkout_button_clone.set_sensitive(revision.is_some());
+        let (Some(revision), Some(profile)) = (revision, selected_profile_clone()) else {
+            text_view.buffer().set_text("");
+            return;
+        };
+
+        let path = get_config_path(true, &profile);
+        let old = match git_file_at(&path, &revision) {
+            Ok(old) => old,
+            Err(e) => {
+                text_view.buffer().set_text(&e);
+                return;
+            }
+        };
+        let current = fs::read_to_string(&path).unwrap_or_default();
+        let label = display_path(&path);
+        let diff = unified_diff(
+            &old,
+            &current,
+            &format!("{} ({})", label, revision.short_hash()),
+            &label,
+        );
+
+        if diff.is_empty() {
+            text_view.buffer().set_text(&t!("git_log.same_as_current"));
+        } else {
+            set_unified_diff_text(&text_view.buffer(), &diff);
+        }
+    });
+
+    fill_revisions();
+
+    let init_button_clone = init_button.clone();
+    enabled_switch.connect_active_notify(move |switch| {
+        let settings = GitSettings {
+            enabled: switch.is_active(),
+        };
+        if let Err(e) = save_git_settings(&settings) {
+            eprintln!("Failed to save git settings: {}", e);
+        }
+        init_button_clone.set_visible(git_repository().is_none());
+    });
+
+    init_button.connect_clicked(move |button| {
+        match git_init() {
+            Ok(_) => button.set_visible(false),
+            Err(e) => eprintln!("Failed to create git repository: {}", e),
+        }
+        repository_label.set_label(&repository_text());
+        fill_revisions();
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    close_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    let on_checkout = Rc::new(on_checkout);
+    let dialog_window_clone = dialog_window.clone();
+    checkout_button.connect_clicked(move |_| {
+        let (Some(revision), Some(profile)) = (
+            list_box
+                .selected_row()
+                .and_then(|row| revisions.borrow().get(row.index() as usize).cloned()),
+            selected_profile(),
+        ) else {
+            return;
+        };
+
+        let confirmation = AlertDialog::builder()
+            .message(t!("git_log.check_out_revision").to_string())
+            .detail(
+                t!(
+                    "git_log.check_out_revision_text_",
+                    profile = profile,
+                    revision = revision.short_hash(),
+                    time = format_change_time(revision.time)
+                )
+                .to_string(),
+            )
+            .buttons([&*t!("git_log.cancel"), &*t!("git_log.check_out")])
+            .modal(true)
+            .build();
+
+        let dialog_window = dialog_window_clone.clone();
+        let on_checkout = on_checkout.clone();
+        confirmation.choose(
+            Some(&dialog_window_clone),
+            None::<&gio::Cancellable>,
+            move |res: Result<i32, glib::Error>| {
+                if res.ok() == Some(1) {
+                    dialog_window.close();
+                    on_checkout(&profile, &revision);
+                }
+            },
+        );
+    });
+
+    dialog_window.present();
+}
@@ -1,26 +1,51 @@
 use crate::{
+    backups::show_backup_browser,
+    categories::category_list,
+    command_palette::{CommandAction, PaletteCommand, show_command_palette},
+    config_document::{ConfigDocument, option_path},
+    drafts::{draft_title, show_draft_dialog, show_drafts_list},
+    export::{HyprvizExport, import_entries, show_import_preview},
+    git_log::show_git_log,
+    guides::guide_source,
+    include_graph::show_include_graph,
+    merge::{MergeEntry, show_merge_dialog},
+    palette::show_palette_dialog,
+    profile_diff::{DiffCopy, show_profile_diff},
+    profile_rules::show_profile_rules_dialog,
+    profiles::{show_profile_name_dialog, show_profile_properties},
+    save_review::{group_pending_changes, show_save_review},
+    search::{FieldKind, SearchItem, rank},
+    snippet::{parse_snippet, show_copy_hyprlang, show_paste_snippet},
     utils::{
-        ConfigChange, HistoryManager, MARGIN_NORMAL, atomic_write, expand_base_config,
-        expand_source, find_all_profiles, get_config_path, is_development_mode, mute_stdout,
-        reload_hyprland,
+        ConfigChange, Draft, HistoryManager, IncludeNode, MARGIN_NORMAL, ViewFilter, atomic_write,
+        atomic_write_all, build_include_graph, can_extend, create_inheriting_profile,
+        delete_profile, display_path, duplicate_profile, expand_base_config, expand_source,
+        expand_source_str, extract_value, find_all_profiles, get_available_monitors,
+        get_config_path, get_current_profile, git_commit, git_commit_message, git_file_at,
+        is_development_mode, load_git_settings, load_profile_meta, load_profile_rules,
+        load_ui_settings, mute_stdout, option_origins, origin_raw, profile_children,
+        reload_hyprland, rename_profile, restore_backup, save_profile_meta, save_profile_rules,
+        save_ui_settings, set_profile_parent, split_origin_raw, touch_profile_meta,
+        transform_config, update_source_line,
     },
-    widget::{ConfigWidget, DynamicTopLevelRow},
+    widget::{ConfigWidget, DynamicTopLevelRow, OptionSource, SECTION_CSS_CLASS},
 };
 use gio::glib::SourceId;
 use gtk::{
     AlertDialog, Application, ApplicationWindow, Box, Button, ColorDialogButton, DropDown, Entry,
-    FileDialog, HeaderBar, Label, Orientation, Popover, ScrolledWindow, SearchEntry, SpinButton,
-    Stack, StackSidebar, StringList, StringObject, Switch, TextView, Widget, Window, WrapMode, gdk,
-    glib, prelude::*,
+    FileDialog, HeaderBar, Image, Label, ListBox, ListBoxRow, Orientation, Popover, ScrolledWindow,
+    SearchEntry, SelectionMode, SpinButton, Stack, StackSidebar, StringList, StringObject, Switch,
+    Widget, Window, gdk, glib, prelude::*,
 };
 use hyprparser::{HyprlandConfig, parse_config};
 use rust_i18n::{available_locales, locale, set_locale, t};
 use std::{
-    cell::RefCell,
-    collections::HashMap,
-    fs,
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs, io,
     path::{Path, PathBuf},
     rc::Rc,
+    time::Duration,
 };
 
 thread_local! {
@@ -96,21 +121,234 @@ pub fn set_widget_value(widget: &Widget, value: &str) {
     }
 }
 
+/// A row of the history timeline: the undo stack length it jumps to and the categories its
+/// changes touch, empty for the initial state.
+struct TimelineEntry {
+    position: usize,
+    categories: Vec<String>,
+}
+
+pub fn format_change_time(timestamp: u64) -> String {
+    if timestamp == 0 {
+        return "—".to_string();
+    }
+
+    glib::DateTime::from_unix_local(timestamp as i64)
+        .and_then(|time| time.format("%Y-%m-%d %H:%M:%S"))
+        .map(|time| time.to_string())
+        .unwrap_or_else(|_| "—".to_string())
+}
+
+/// Returns every profile, starting with `Default`.
+fn profile_list() -> Vec<String> {
+    let mut profiles = find_all_profiles().unwrap_or_default();
+    if !profiles.contains(&"Default".to_string()) {
+        profiles.insert(0, "Default".to_string());
+    }
+    profiles
+}
+
+fn view_filter_labels() -> Vec<String> {
+    ViewFilter::ALL
+        .iter()
+        .map(|filter| match filter {
+            ViewFilter::All => t!("gui.all_options"),
+            ViewFilter::Modified => t!("gui.modified_since_load"),
+            ViewFilter::SetInProfile => t!("gui.set_in_this_profile"),
+            ViewFilter::Overridden => t!("gui.overridden_from_base_config"),
+            ViewFilter::DiffersFromDefault => t!("gui.differs_from_default"),
+        })
+        .map(|label| label.to_string())
+        .collect()
+}
+
+/// Whether two option values mean the same, so `1` matches `1.0` and `true` matches `1`.
+fn same_option_value(a: &str, b: &str) -> bool {
+    let as_number = |value: &str| match value.trim() {
+        "true" | "on" | "yes" => Some(1.0),
+        "false" | "off" | "no" => Some(0.0),
+        value => value.parse::<f64>().ok(),
+    };
+    a.trim() == b.trim() || matches!((as_number(a), as_number(b)), (Some(a), Some(b)) if a == b)
+}
+
+/// Whether the option `key` of `category` passes `filter`.
+fn matches_view_filter(
+    filter: ViewFilter,
+    history: &HistoryManager,
+    category: &str,
+    key: &str,
+    default: &str,
+    source: &OptionSource,
+) -> bool {
+    let initial = history.get_initial_value(category, key);
+    let pending = history
+        .get_current_state()
+        .get(&(category.to_string(), key.to_string()));
+
+    match filter {
+        ViewFilter::All => true,
+        ViewFilter::Modified => pending.is_some_and(|value| Some(value) != initial.as_ref()),
+        ViewFilter::SetInProfile => source.in_profile,
+        ViewFilter::Overridden => source.in_profile && source.base_value.is_some(),
+        ViewFilter::DiffersFromDefault => {
+            let value = pending
+                .cloned()
+                .or(initial)
+                .unwrap_or_else(|| default.to_string());
+            !same_option_value(&value, default)
+        }
+    }
+}
+
+fn selected_view_filter(dropdown: &DropDown) -> ViewFilter {
+    ViewFilter::ALL
+        .get(dropdown.selected() as usize)
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Shows only the options `filter` picks on the page of `widget`, hiding sections left empty,
+/// and counts them next to the category in the sidebar. The widgets it hides are added to
+/// `filtered_out`. Pages that are not rendered yet keep their plain title.
+fn filter_page(
+    stack: &Stack,
+    widget: &ConfigWidget,
+    filter: ViewFilter,
+    history: &HistoryManager,
+    filtered_out: &mut Vec<Widget>,
+) {
+    let page = stack.page(&widget.scrolled_window);
+    if filter == ViewFilter::All || !widget.is_rendered {
+        page.set_title(&widget.display_name);
+        return;
+    }
+
+    let category = widget.category.as_str();
+    let sources = widget.option_sources.borrow();
+    let mut rows: HashMap<Widget, bool> = HashMap::new();
+    for (key, data) in &widget.options {
+        // Read-only blocks have no source.
+        let Some(source) = sources.get(key) else {
+            continue;
+        };
+        let Some(row) = data
+            .visual_widget
+            .clone()
+            .unwrap_or_else(|| data.widget.clone())
+            .parent()
+        else {
+            continue;
+        };
+        let matches = matches_view_filter(filter, history, category, key, &data.default, source);
+        *rows.entry(row).or_default() |= matches;
+    }
+
+    // The first section is the page title and stays; other sections are hidden when none of
+    // their options show, and everything else on the page is hidden.
+    let mut hidden = Vec::new();
+    let mut is_title = true;
+    let mut section: Option<(Widget, bool)> = None;
+    let mut child = widget.container.first_child();
+    while let Some(current) = child {
+        child = current.next_sibling();
+
+        if let Some(&matches) = rows.get(&current) {
+            match &mut section {
+                Some((_, shown)) if matches => *shown = true,
+                _ if !matches => hidden.push(current),
+                _ => {}
+            }
+        } else if current.has_css_class(SECTION_CSS_CLASS) {
+            if is_title {
+                is_title = false;
+                continue;
+            }
+            if let Some((previous, false)) = section.take() {
+                hidden.push(previous);
+            }
+            section = Some((current, false));
+        } else if widget.preview.as_ref() != Some(&current) {
+            hidden.push(current);
+        }
+    }
+    if let Some((previous, false)) = section {
+        hidden.push(previous);
+    }
+
+    for current in hidden {
+        if current.is_visible() {
+            current.set_visible(false);
+            filtered_out.push(current);
+        }
+    }
+
+    let count = rows.values().filter(|matches| **matches).count();
+    page.set_title(&t!(
+        "gui.category_count_",
+        name = widget.display_name,
+        count = count
+    ));
+}
+
+/// A file as it would be written by saving, see [`ConfigGUI::render_files`].
+struct RenderedFile {
+    path: PathBuf,
+    original: String,
+    document: ConfigDocument,
+    /// `(category, old raw, new raw)` of rows whose line changed.
+    renamed: Vec<(String, String, String)>,
+}
+
 pub struct ConfigGUI {
     pub window: ApplicationWindow,
     config_widgets: Rc<RefCell<HashMap<String, ConfigWidget>>>,
     top_level_rows: Rc<RefCell<HashMap<(String, String), DynamicTopLevelRow>>>,
+    documents: RefCell<HashMap<PathBuf, ConfigDocument>>,
+    file_monitors: RefCell<Vec<gio::FileMonitor>>,
+    /// The expanded config as hyprviz last loaded or saved it, used as the merge base when
+    /// the files change on disk.
+    config_snapshot: RefCell<String>,
+    changed_files: RefCell<BTreeSet<PathBuf>>,
+    external_check_scheduled: Cell<bool>,
+    external_dialog_open: Cell<bool>,
     title_label: Label,
     save_button: Button,
     pub profile_dropdown: DropDown,
+    /// Set while the profile list is changed by hyprviz, so no profile switch is offered.
+    pub is_programmatic_switch: Rc<Cell<bool>>,
+    pub last_confirmed_index: Rc<Cell<u32>>,
     current_profile_label: Label,
+    profile_icon: Image,
     create_profile_button: Button,
+    rename_profile_button: Button,
+    duplicate_profile_button: Button,
+    profile_properties_button: Button,
+    compare_profiles_button: Button,
+    profile_rules_button: Button,
     delete_profile_button: Button,
     history_button: Button,
+    palette_button: Button,
+    include_graph_button: Button,
+    backups_button: Button,
+    git_log_button: Button,
     save_config_button: Button,
     load_config_button: Button,
+    paste_snippet_button: Button,
+    copy_hyprlang_button: Button,
     copy_button: Button,
     search_entry: SearchEntry,
+    search_popover: Popover,
+    search_results: ListBox,
+    /// Category and widget of every row in `search_results`.
+    search_targets: RefCell<Vec<(String, Widget)>>,
+    /// Search items of the rendered pages, built on first use after each load.
+    search_index: RefCell<Option<Rc<Vec<SearchItem>>>>,
+    view_filter_dropdown: DropDown,
+    /// Set while the filter list is relabelled, so the filter is not applied mid-reload.
+    is_programmatic_filter: Rc<Cell<bool>>,
+    /// Widgets the view filter hid, shown again before it is applied anew.
+    filtered_out: Rc<RefCell<Vec<Widget>>>,
     locale_dropdown: DropDown,
     pub history: Rc<RefCell<HistoryManager>>,
     content_box: Box,
@@ -149,21 +387,56 @@ impl ConfigGUI {
         let search_entry = SearchEntry::new();
         search_entry.set_width_chars(25);
 
+        let search_results = ListBox::new();
+        search_results.set_selection_mode(SelectionMode::Browse);
+        let search_results_window = ScrolledWindow::new();
+        search_results_window.set_min_content_width(360);
+        search_results_window.set_max_content_height(420);
+        search_results_window.set_propagate_natural_height(true);
+        search_results_window.set_child(Some(&search_results));
+        search_results_window.set_visible(false);
+
+        let search_box = Box::new(Orientation::Vertical, 6);
+        search_box.append(&search_entry);
+        search_box.append(&search_results_window);
+
         let popover = gtk::Popover::new();
-        popover.set_child(Some(&search_entry));
+        popover.set_child(Some(&search_box));
         popover.set_position(gtk::PositionType::Bottom);
         popover.set_parent(&search_button);
 
         let create_profile_button = Button::with_label(&t!("gui.create_profile"));
+        let rename_profile_button = Button::with_label(&t!("gui.rename_profile"));
+        let duplicate_profile_button = Button::with_label(&t!("gui.duplicate_profile"));
+        let profile_properties_button = Button::with_label(&t!("gui.profile_properties"));
+        let compare_profiles_button = Button::with_label(&t!("gui.compare_profiles"));
+        let profile_rules_button = Button::with_label(&t!("gui.automatic_switching"));
         let delete_profile_button = Button::with_label(&t!("gui.delete_profile"));
         let history_button = Button::with_label(&t!("gui.history"));
+        let palette_button = Button::with_label(&t!("gui.wallpaper_palette"));
+        let include_graph_button = Button::with_label(&t!("gui.included_files"));
+        let backups_button = Button::with_label(&t!("gui.backups"));
+        let git_log_button = Button::with_label(&t!("gui.version_history"));
+        let paste_snippet_button = Button::with_label(&t!("gui.paste_snippet"));
+        let copy_hyprlang_button = Button::with_label(&t!("gui.copy_as_hyprlang"));
         let load_config_button = Button::with_label(&t!("gui.load_hyprviz_config"));
         let save_config_button = Button::with_label(&t!("gui.save_hyprviz_config"));
         let copy_button = Button::with_label(&t!("gui.copyright"));
 
         gear_menu_box.append(&create_profile_button);
+        gear_menu_box.append(&rename_profile_button);
+        gear_menu_box.append(&duplicate_profile_button);
+        gear_menu_box.append(&profile_properties_button);
+        gear_menu_box.append(&compare_profiles_button);
+        gear_menu_box.append(&profile_rules_button);
         gear_menu_box.append(&delete_profile_button);
         gear_menu_box.append(&history_button);
+        gear_menu_box.append(&palette_button);
+        gear_menu_box.append(&include_graph_button);
+        gear_menu_box.append(&backups_button);
+        gear_menu_box.append(&git_log_button);
+        gear_menu_box.append(&paste_snippet_button);
+        gear_menu_box.append(&copy_hyprlang_button);
         gear_menu_box.append(&load_config_button);
         gear_menu_box.append(&save_config_button);
         gear_menu_box.append(&copy_button);
@@ -184,9 +457,16 @@ impl ConfigGUI {
             }
         });
 
-        let popover_clone = popover.clone();
+        let search_results_clone = search_results.clone();
         search_entry.connect_activate(move |_| {
-            popover_clone.popdown();
+            if let Some(row) = search_results_clone.row_at_index(0) {
+                row.activate();
+            }
+        });
+
+        let search_results_window_clone = search_results_window.clone();
+        search_results.connect_row_added(move |_, _| {
+            search_results_window_clone.set_visible(true);
         });
 
         let popover_clone = popover.clone();
@@ -203,6 +483,18 @@ impl ConfigGUI {
 
         header_bar.pack_start(&search_button);
 
+        let view_filter_labels = view_filter_labels();
+        let view_filter_labels: Vec<&str> = view_filter_labels.iter().map(String::as_str).collect();
+        let view_filter_dropdown = DropDown::from_strings(&view_filter_labels);
+        view_filter_dropdown.set_tooltip_text(Some(&t!("gui.view_filter")));
+        if let Some(index) = ViewFilter::ALL
+            .iter()
+            .position(|filter| *filter == load_ui_settings().view_filter)
+        {
+            view_filter_dropdown.set_selected(index as u32);
+        }
+        header_bar.pack_start(&view_filter_dropdown);
+
         let locales_cow = available_locales!();
         let locales: Vec<&str> = locales_cow.iter().map(|s| s.as_ref()).collect();
         let locales_string_list = StringList::new(&locales);
@@ -220,16 +512,7 @@ impl ConfigGUI {
         let save_button = Button::with_label(&t!("gui.save"));
         save_button.add_css_class("suggested-action");
 
-        let profiles = if let Some(mut profiles) = find_all_profiles() {
-            if profiles.contains(&"Default".to_string()) {
-                profiles
-            } else {
-                profiles.insert(0, "Default".to_string());
-                profiles
-            }
-        } else {
-            vec!["Default".to_string()]
-        };
+        let profiles = profile_list();
         let profiles_str_vec: Vec<&str> = profiles.iter().map(|s| s.as_str()).collect();
         let profiles_str: &[&str] = profiles_str_vec.as_slice();
         let string_list = StringList::new(profiles_str);
@@ -237,10 +520,13 @@ impl ConfigGUI {
         profile_dropdown.set_halign(gtk::Align::End);
         profile_dropdown.set_width_request(100);
         let current_profile_label = Label::new(Some(&t!("gui.profile")));
+        let profile_icon = Image::new();
+        profile_icon.set_visible(false);
 
         header_bar.pack_end(&save_button);
         header_bar.pack_end(&profile_dropdown);
         header_bar.pack_end(&current_profile_label);
+        header_bar.pack_end(&profile_icon);
 
         window.set_titlebar(Some(&header_bar));
 
@@ -263,17 +549,44 @@ impl ConfigGUI {
             window,
             config_widgets,
             top_level_rows: Rc::new(RefCell::new(HashMap::new())),
+            documents: RefCell::new(HashMap::new()),
+            file_monitors: RefCell::new(Vec::new()),
+            config_snapshot: RefCell::new(String::new()),
+            changed_files: RefCell::new(BTreeSet::new()),
+            external_check_scheduled: Cell::new(false),
+            external_dialog_open: Cell::new(false),
             title_label,
             save_button,
             profile_dropdown,
+            is_programmatic_switch: Rc::new(Cell::new(false)),
+            last_confirmed_index: Rc::new(Cell::new(0)),
             current_profile_label,
+            profile_icon,
             history_button,
+            palette_button,
+            include_graph_button,
+            backups_button,
+            git_log_button,
             create_profile_button,
+            rename_profile_button,
+            duplicate_profile_button,
+            profile_properties_button,
+            compare_profiles_button,
+            profile_rules_button,
             delete_profile_button,
             save_config_button,
             load_config_button,
+            paste_snippet_button,
+            copy_hyprlang_button,
             copy_button,
             search_entry,
+            search_popover: popover,
+            search_results,
+            search_targets: RefCell::new(Vec::new()),
+            search_index: RefCell::new(None),
+            view_filter_dropdown,
+            is_programmatic_filter: Rc::new(Cell::new(false)),
+            filtered_out: Rc::new(RefCell::new(Vec::new())),
             locale_dropdown,
             content_box,
             history: Rc::new(RefCell::new(HistoryManager::new(u16::MAX as usize, 1 << 9))),
@@ -290,20 +603,31 @@ impl ConfigGUI {
         key_controller.connect_key_pressed(move |_, keyval, _keycode, state| {
             let ctrl = state.contains(gdk::ModifierType::CONTROL_MASK);
             let shift = state.contains(gdk::ModifierType::SHIFT_MASK);
+            // Ctrl+K - Command palette
+            if ctrl && !shift && keyval == gdk::Key::k {
+                Self::open_command_palette(&gui_clone);
+                return glib::Propagation::Stop;
+            }
             let mut history = history_clone.borrow_mut();
             match (keyval, ctrl, shift) {
                 // Ctrl+Z - Undo
                 (gdk::Key::z, true, false) => {
-                    if let Some(change) = history.undo() {
-                        gui_clone.borrow().apply_undo_to_ui(&change);
-                    }
+                    let steps: Vec<(ConfigChange, bool)> = history
+                        .undo()
+                        .into_iter()
+                        .map(|change| (change, true))
+                        .collect();
+                    gui_clone.borrow().apply_history_steps_to_ui(&steps);
                     glib::Propagation::Stop
                 }
                 // Ctrl+Y or Ctrl+Shift+Z - Redo
                 (gdk::Key::y, true, false) | (gdk::Key::z, true, true) => {
-                    if let Some(change) = history.redo() {
-                        gui_clone.borrow().apply_redo_to_ui(&change);
-                    }
+                    let steps: Vec<(ConfigChange, bool)> = history
+                        .redo()
+                        .into_iter()
+                        .map(|change| (change, false))
+                        .collect();
+                    gui_clone.borrow().apply_history_steps_to_ui(&steps);
                     glib::Propagation::Stop
                 }
                 _ => glib::Propagation::Proceed,
@@ -311,6 +635,12 @@ impl ConfigGUI {
         });
         gui.borrow().window.add_controller(key_controller);
 
+        let history_clone = Rc::clone(&gui.borrow().history);
+        gui.borrow().window.connect_close_request(move |_| {
+            history_clone.borrow_mut().end_session();
+            glib::Propagation::Proceed
+        });
+
         let gui_clone = Rc::clone(&gui);
         gui.borrow()
             .create_profile_button
@@ -339,6 +669,17 @@ impl ConfigGUI {
                 dialog_box.append(&label);
                 dialog_box.append(&entry);
 
+                let parents = profile_list();
+                let mut parent_names = vec![t!("gui.copy_default_profile").to_string()];
+                parent_names.extend(
+                    parents
+                        .iter()
+                        .map(|parent| t!("gui.inherit_from_", name = parent).to_string()),
+                );
+                let parent_names: Vec<&str> = parent_names.iter().map(String::as_str).collect();
+                let parent_dropdown = DropDown::from_strings(&parent_names);
+                dialog_box.append(&parent_dropdown);
+
                 let buttons_box = Box::new(Orientation::Horizontal, 5);
                 buttons_box.set_halign(gtk::Align::End);
 
@@ -362,21 +703,25 @@ impl ConfigGUI {
                 let entry_clone = entry.clone();
                 create_button.connect_clicked(move |_| {
                     let profile_name = entry_clone.text().to_string();
-                    if profile_name.is_empty() || profile_name == "Default" {
-                        gui_clone.borrow().custom_error_popup(
-                            &t!("gui.invalid_profile_name"),
-                            &t!("gui.profile_name_cannot_be_empty_or_default"),
-                        );
+                    if !gui_clone.borrow().validate_new_profile_name(&profile_name) {
                         return;
                     }
 
                     let hyprviz_path = get_config_path(true, &profile_name);
 
-                    if hyprviz_path.exists() {
-                        gui_clone.borrow().custom_error_popup(
-                            &t!("gui.profile_exists"),
-                            &t!("gui.profile__already_exists", name = profile_name),
-                        );
+                    if let Some(parent) = (parent_dropdown.selected() as usize)
+                        .checked_sub(1)
+                        .and_then(|index| parents.get(index))
+                    {
+                        if let Err(e) = create_inheriting_profile(&profile_name, parent) {
+                            gui_clone.borrow().custom_error_popup(
+                                &t!("gui.failed_to_create_profile"),
+                                &t!("gui.failed_to_create_profile_file_", error = e),
+                            );
+                            return;
+                        }
+                        dialog_window_clone.close();
+                        gui_clone.borrow().select_profile(&profile_name);
                         return;
                     }
 
@@ -402,25 +747,12 @@ impl ConfigGUI {
                         );
                         return;
                     }
+                    if let Err(e) = touch_profile_meta(&profile_name) {
+                        eprintln!("Failed to write profile metadata: {}", e);
+                    }
 
                     dialog_window_clone.close();
-
-                    if let Some(mut profiles) = find_all_profiles() {
-                        if !profiles.contains(&"Default".to_string()) {
-                            profiles.insert(0, "Default".to_string());
-                        }
-                        let profiles_str_vec: Vec<&str> =
-                            profiles.iter().map(|s| s.as_str()).collect();
-                        let string_list = StringList::new(&profiles_str_vec);
-                        gui_clone
-                            .borrow()
-                            .profile_dropdown
-                            .set_model(Some(&string_list));
-
-                        if let Some(pos) = profiles.iter().position(|p| *p == profile_name) {
-                            gui_clone.borrow().profile_dropdown.set_selected(pos as u32);
-                        }
-                    }
+                    gui_clone.borrow().select_profile(&profile_name);
                 });
 
                 dialog_window.present();
@@ -452,6 +784,27 @@ impl ConfigGUI {
                     return;
                 }
 
+                let children = profile_children(&profile_name);
+                if !children.is_empty() {
+                    gui.borrow().custom_error_popup(
+                        &t!("gui.cannot_delete_profile"),
+                        &t!(
+                            "gui.profile_is_inherited_by_",
+                            name = profile_name,
+                            children = children.join(", ")
+                        ),
+                    );
+                    return;
+                }
+
+                if load_profile_rules().uses_profile(&profile_name) {
+                    gui.borrow().custom_error_popup(
+                        &t!("gui.cannot_delete_profile"),
+                        &t!("gui.profile_is_used_by_rules_", name = profile_name),
+                    );
+                    return;
+                }
+
                 let dialog_window = Window::builder()
                     .title(t!("gui.delete_profile"))
                     .modal(true)
@@ -499,48 +852,315 @@ impl ConfigGUI {
                 let dialog_window_clone = dialog_window.clone();
                 let gui_clone = Rc::clone(&gui);
                 let profile_name_clone = profile_name.clone();
-                delete_button.connect_clicked(move |_| {
-                    let hyprviz_path = get_config_path(true, &profile_name_clone);
-
-                    match fs::remove_file(&hyprviz_path) {
-                        Ok(_) => {
-                            if let Some(mut profiles) = find_all_profiles() {
-                                if !profiles.contains(&"Default".to_string()) {
-                                    profiles.insert(0, "Default".to_string());
-                                }
-
-                                let profiles_str_vec: Vec<&str> =
-                                    profiles.iter().map(|s| s.as_str()).collect();
-                                let string_list = StringList::new(&profiles_str_vec);
-                                gui_clone
-                                    .borrow()
-                                    .profile_dropdown
-                                    .set_model(Some(&string_list));
-
-                                if let Some(pos) = profiles.iter().position(|p| *p == "Default") {
-                                    gui_clone.borrow().profile_dropdown.set_selected(pos as u32);
-                                }
-                            }
-
-                            dialog_window_clone.close();
-                        }
-                        Err(e) => {
-                            gui_clone.borrow().custom_error_popup(
-                                &t!("gui.failed_to_delete_profile"),
-                                &t!("gui.failed_to_delete_profile_file_", error = e),
-                            );
-                        }
+                delete_button.connect_clicked(move |_| match delete_profile(&profile_name_clone) {
+                    Ok(_) => {
+                        gui_clone.borrow().select_profile("Default");
+                        dialog_window_clone.close();
+                    }
+                    Err(e) => {
+                        gui_clone.borrow().custom_error_popup(
+                            &t!("gui.failed_to_delete_profile"),
+                            &t!("gui.failed_to_delete_profile_file_", error = e),
+                        );
                     }
                 });
 
                 dialog_window.present();
             });
 
+        let gui_clone = Rc::clone(&gui);
+        gui.borrow()
+            .rename_profile_button
+            .connect_clicked(move |_| {
+                let profile = gui_clone.borrow().selected_profile();
+                if profile == "Default" {
+                    gui_clone.borrow().custom_error_popup(
+                        &t!("gui.cannot_rename_profile"),
+                        &t!("gui.the_default_profile_cannot_be_renamed"),
+                    );
+                    return;
+                }
+
+                let window = gui_clone.borrow().window.clone();
+                let gui = Rc::clone(&gui_clone);
+                show_profile_name_dialog(
+                    &window,
+                    &t!("gui.rename_profile"),
+                    &t!("gui.rename"),
+                    &profile.clone(),
+                    move |new_name| {
+                        if new_name == profile {
+                            return true;
+                        }
+                        if !gui.borrow().validate_new_profile_name(&new_name) {
+                            return false;
+                        }
+
+                        if let Err(e) = rename_profile(&profile, &new_name) {
+                            gui.borrow().custom_error_popup(
+                                &t!("gui.failed_to_rename_profile"),
+                                &t!("gui.failed_to_rename_profile_", error = e),
+                            );
+                            return false;
+                        }
+
+                        // The renamed profile is the active one, so the main config has to
+                        // source its new file.
+                        let config_path = get_config_path(false, "Default");
+                        if let Err(e) = update_source_line(&config_path, &new_name) {
+                            gui.borrow().custom_error_popup(
+                                &t!("gui.failed_to_rename_profile"),
+                                &t!("gui.failed_to_rename_profile_", error = e),
+                            );
+                        }
+                        gui.borrow().history.borrow_mut().set_profile(&new_name);
+                        gui.borrow().refresh_profile_list(&new_name);
+                        Self::reload_from_disk(&gui);
+                        reload_hyprland();
+                        true
+                    },
+                );
+            });
+
+        let gui_clone = Rc::clone(&gui);
+        gui.borrow()
+            .duplicate_profile_button
+            .connect_clicked(move |_| {
+                let profile = gui_clone.borrow().selected_profile();
+                let window = gui_clone.borrow().window.clone();
+                let gui = Rc::clone(&gui_clone);
+                show_profile_name_dialog(
+                    &window,
+                    &t!("gui.duplicate_profile"),
+                    &t!("gui.duplicate"),
+                    &t!("gui.copy_of_", name = profile),
+                    move |new_name| {
+                        if !gui.borrow().validate_new_profile_name(&new_name) {
+                            return false;
+                        }
+
+                        if let Err(e) = duplicate_profile(&profile, &new_name) {
+                            gui.borrow().custom_error_popup(
+                                &t!("gui.failed_to_create_profile"),
+                                &t!("gui.failed_to_create_profile_file_", error = e),
+                            );
+                            return false;
+                        }
+
+                        gui.borrow().select_profile(&new_name);
+                        true
+                    },
+                );
+            });
+
+        let gui_clone = Rc::clone(&gui);
+        gui.borrow()
+            .profile_properties_button
+            .connect_clicked(move |_| {
+                let profile = gui_clone.borrow().selected_profile();
+                let parents = profile_list()
+                    .into_iter()
+                    .filter(|parent| can_extend(&profile, parent))
+                    .collect();
+                let window = gui_clone.borrow().window.clone();
+                let gui = Rc::clone(&gui_clone);
+                show_profile_properties(
+                    &window,
+                    &profile.clone(),
+                    load_profile_meta(&profile),
+                    parents,
+                    move |meta| {
+                        let parent_changed = load_profile_meta(&profile).extends != meta.extends;
+                        if parent_changed
+                            && let Err(e) = set_profile_parent(&profile, meta.extends.as_deref())
+                        {
+                            gui.borrow().custom_error_popup(
+                                &t!("gui.failed_to_save_profile_properties"),
+                                &t!("gui.failed_to_save_profile_properties_", error = e),
+                            );
+                            return;
+                        }
+
+                        let mut saved = load_profile_meta(&profile);
+                        saved.description = meta.description;
+                        saved.icon = meta.icon;
+                        if let Err(e) = save_profile_meta(&profile, &saved) {
+                            gui.borrow().custom_error_popup(
+                                &t!("gui.failed_to_save_profile_properties"),
+                                &t!("gui.failed_to_save_profile_properties_", error = e),
+                            );
+                        }
+
+                        if parent_changed {
+                            Self::reload_from_disk(&gui);
+                            reload_hyprland();
+                        } else {
+                            gui.borrow().update_profile_header();
+                        }
+                    },
+                );
+            });
+
+        let gui_clone = Rc::clone(&gui);
+        gui.borrow()
+            .compare_profiles_button
+            .connect_clicked(move |_| {
+                let window = gui_clone.borrow().window.clone();
+                let active = gui_clone.borrow().history.borrow().profile().to_string();
+                let gui = Rc::clone(&gui_clone);
+                show_profile_diff(
+                    &window,
+                    profile_list(),
+                    &active,
+                    category_list(),
+                    move |source, copy| gui.borrow().copy_from_profile(source, copy),
+                );
+            });
+
+        let gui_clone = Rc::clone(&gui);
+        gui.borrow().profile_rules_button.connect_clicked(move |_| {
+            let window = gui_clone.borrow().window.clone();
+            let gui = Rc::clone(&gui_clone);
+            show_profile_rules_dialog(
+                &window,
+                load_profile_rules(),
+                profile_list(),
+                move |rules| {
+                    if let Err(e) = save_profile_rules(&rules) {
+                        gui.borrow().custom_error_popup(
+                            &t!("gui.saving_failed"),
+                            &t!("gui.failed_to_save_profile_rules_", error = e),
+                        );
+                        return;
+                    }
+
+                    // Offer the profile the new rules pick right away.
+                    let active = gui.borrow().history.borrow().profile().to_string();
+                    if let Some(target) = rules.profile_for(&get_available_monitors(false))
+                        && target != active
+                    {
+                        gui.borrow().select_profile(target);
+                    }
+                },
+            );
+        });
+
         let gui_clone = Rc::clone(&gui);
         gui.borrow().history_button.connect_clicked(move |_| {
             Self::show_history_manager(Rc::clone(&gui_clone));
         });
 
+        let gui_clone = Rc::clone(&gui);
+        gui.borrow().include_graph_button.connect_clicked(move |_| {
+            let gui = gui_clone.borrow();
+            let graph = build_include_graph(&get_config_path(false, "Default"));
+            let profile_path = get_config_path(true, &gui.selected_profile());
+            let profile_path = profile_path.canonicalize().unwrap_or(profile_path);
+            show_include_graph(&gui.window, &graph, &profile_path);
+        });
+
+        let gui_clone = Rc::clone(&gui);
+        gui.borrow().backups_button.connect_clicked(move |_| {
+            let window = gui_clone.borrow().window.clone();
+            let gui = Rc::clone(&gui_clone);
+            show_backup_browser(&window, move |backup| {
+                if let Err(e) = restore_backup(backup) {
+                    gui.borrow().custom_error_popup(
+                        &t!("gui.restore_failed"),
+                        &t!(
+                            "gui.failed_to_restore_backup_",
+                            file = display_path(&backup.original),
+                            error = e
+                        ),
+                    );
+                    return;
+                }
+
+                Self::reload_from_disk(&gui);
+                reload_hyprland();
+            });
+        });
+
+        let gui_clone = Rc::clone(&gui);
+        gui.borrow().git_log_button.connect_clicked(move |_| {
+            let window = gui_clone.borrow().window.clone();
+            let active = gui_clone.borrow().selected_profile();
+            let gui = Rc::clone(&gui_clone);
+            show_git_log(
+                &window,
+                profile_list(),
+                &active,
+                move |profile, revision| {
+                    let path = get_config_path(true, profile);
+                    let result = git_file_at(&path, revision).and_then(|content| {
+                        atomic_write(&path, &content).map_err(|e| e.to_string())
+                    });
+                    if let Err(e) = result {
+                        gui.borrow().custom_error_popup(
+                            &t!("gui.check_out_failed"),
+                            &t!(
+                                "gui.failed_to_check_out_revision_",
+                                revision = revision.short_hash(),
+                                error = e
+                            ),
+                        );
+                        return;
+                    }
+
+                    if load_git_settings().enabled {
+                        let message = format!("{}: check out {}", profile, revision.short_hash());
+                        if let Err(e) = git_commit(&[path], &message) {
+                            gui.borrow().custom_error_popup(
+                                &t!("gui.git_commit_failed"),
+                                &t!("gui.failed_to_commit_the_saved_files_", error = e),
+                            );
+                        }
+                    }
+
+                    if gui.borrow().selected_profile() == profile {
+                        Self::reload_from_disk(&gui);
+                        reload_hyprland();
+                    }
+                },
+            );
+        });
+
+        let gui_clone = Rc::clone(&gui);
+        gui.borrow().palette_button.connect_clicked(move |_| {
+            let gui = Rc::clone(&gui_clone);
+            let window = gui.borrow().window.clone();
+
+            show_palette_dialog(&window, move |changes| {
+                let history = Rc::clone(&gui.borrow().history);
+                history
+                    .borrow_mut()
+                    .begin_transaction(t!("gui.apply_wallpaper_palette"));
+                for (category, key, value) in changes {
+                    gui.borrow().apply_value_to_ui(&category, &key, &value);
+                }
+                history.borrow_mut().commit_transaction();
+            });
+        });
+
+        let gui_clone = Rc::clone(&gui);
+        gui.borrow().paste_snippet_button.connect_clicked(move |_| {
+            let window = gui_clone.borrow().window.clone();
+            let gui = Rc::clone(&gui_clone);
+            show_paste_snippet(&window, move |text| Self::preview_snippet(&gui, text));
+        });
+
+        let gui_clone = Rc::clone(&gui);
+        gui.borrow().copy_hyprlang_button.connect_clicked(move |_| {
+            let gui = gui_clone.borrow();
+            match gui.current_export() {
+                Ok(export) => show_copy_hyprlang(&gui.window, &export, category_list()),
+                Err(e) => gui.custom_error_popup(
+                    &t!("gui.reading_failed"),
+                    &t!("gui.failed_to_read_the_configuration_file_", error = e),
+                ),
+            }
+        });
+
         let gui_clone = Rc::clone(&gui);
         gui.borrow().load_config_button.connect_clicked(move |_| {
             let gui = Rc::clone(&gui_clone);
@@ -555,7 +1175,7 @@ impl ConfigGUI {
                 if let Ok(file) = dialog.open_future(Some(&window)).await
                     && let Some(path) = file.path()
                 {
-                    gui.borrow().load_hyprviz_config(&path);
+                    Self::load_hyprviz_config(&gui, &path);
                 }
             });
         });
@@ -598,6 +1218,27 @@ along with this program; if not, see
             );
         });
 
+        let is_programmatic_filter = Rc::clone(&gui.borrow().is_programmatic_filter);
+        let gui_clone = Rc::clone(&gui);
+        gui.borrow()
+            .view_filter_dropdown
+            .connect_selected_notify(move |dd| {
+                if is_programmatic_filter.get() {
+                    return;
+                }
+
+                let mut settings = load_ui_settings();
+                settings.view_filter = ViewFilter::ALL
+                    .get(dd.selected() as usize)
+                    .copied()
+                    .unwrap_or_default();
+                if let Err(e) = save_ui_settings(&settings) {
+                    eprintln!("Failed to save UI settings: {}", e);
+                }
+
+                gui_clone.borrow().apply_view_filter(true);
+            });
+
         let gui_clone = Rc::clone(&gui);
         gui.borrow()
             .locale_dropdown
@@ -624,12 +1265,19 @@ along with this program; if not, see
         let gui_clone = Rc::clone(&gui);
         gui.borrow()
             .search_entry
-            .connect_changed(move |entry| gui_clone.borrow().filter_options(entry.text()));
+            .connect_changed(move |entry| gui_clone.borrow().update_search_results(&entry.text()));
+
+        let gui_clone = Rc::clone(&gui);
+        gui.borrow()
+            .search_results
+            .connect_row_activated(move |_, row| {
+                gui_clone.borrow().open_search_result(row.index() as usize)
+            });
 
         let gui_clone = Rc::clone(&gui);
         gui.borrow()
             .save_button
-            .connect_clicked(move |_| gui_clone.borrow().save_config_file());
+            .connect_clicked(move |_| Self::review_and_save(Rc::clone(&gui_clone)));
     }
 
     fn find_target_widget(&self, change: &ConfigChange) -> Option<Widget> {
@@ -769,163 +1417,284 @@ along with this program; if not, see
         });
     }
 
-    fn apply_undo_to_ui(&self, change: &ConfigChange) {
-        if let Some(raw) = change.key.strip_suffix("_name")
+    /// Applies undone (`true`) or redone (`false`) changes to the widgets.
+    fn apply_history_steps_to_ui(&self, steps: &[(ConfigChange, bool)]) {
+        for (i, (change, undone)) in steps.iter().enumerate() {
+            let value = if *undone {
+                change.old_value.as_deref()
+            } else {
+                change.new_value.as_deref()
+            };
+
+            // Only the last step is brought into view, so undoing a transaction or jumping
+            // through the timeline does not scroll through every intermediate widget.
+            if i + 1 == steps.len() {
+                self.apply_history_value_to_ui(change, value);
+            } else {
+                self.set_value_in_ui(&change.category, &change.key, value);
+            }
+        }
+    }
+
+    fn apply_history_value_to_ui(&self, change: &ConfigChange, value: Option<&str>) {
+        self.set_value_in_ui(&change.category, &change.key, value);
+
+        if let Some(target_widget) = self.find_target_widget(change) {
+            self.switch_to_category_tab(&change.category);
+            self.focus_and_scroll_to_widget(&target_widget);
+            animate_change(&target_widget);
+        }
+    }
+
+    fn set_value_in_ui(&self, category: &str, key: &str, value: Option<&str>) {
+        if let Some(raw) = key.strip_suffix("_name")
             && let Some(row) = self
                 .top_level_rows
                 .borrow()
-                .get(&(change.category.clone(), raw.to_string()))
-            && let Some(val) = &change.old_value
+                .get(&(category.to_string(), raw.to_string()))
+            && let Some(val) = value
         {
             row.is_programmatic_update.set(true);
             row.name_entry.set_text(val);
             row.is_programmatic_update.set(false);
-        } else if let Some(raw) = change.key.strip_suffix("_value")
+        } else if let Some(raw) = key.strip_suffix("_value")
             && let Some(row) = self
                 .top_level_rows
                 .borrow()
-                .get(&(change.category.clone(), raw.to_string()))
-            && let Some(val) = &change.old_value
+                .get(&(category.to_string(), raw.to_string()))
+            && let Some(val) = value
         {
             row.is_programmatic_update.set(true);
             row.value_entry.set_text(val);
             row.is_programmatic_update.set(false);
-        } else if let Some(raw) = change.key.strip_suffix("_delete")
-            && change.new_value.as_deref() == Some("DELETE")
+        } else if let Some(raw) = key.strip_suffix("_delete")
             && let Some(row) = self
                 .top_level_rows
                 .borrow()
-                .get(&(change.category.clone(), raw.to_string()))
-            && let Some(cat_widget) = self.config_widgets.borrow().get(&change.category)
-            && let Some(wd) = cat_widget.options.get(&change.category)
+                .get(&(category.to_string(), raw.to_string()))
+            && let Some(cat_widget) = self.config_widgets.borrow().get(category)
+            && let Some(wd) = cat_widget.options.get(category)
             && let Some(gtkbox) = wd.widget.downcast_ref::<gtk::Box>()
         {
+            let is_deleted = value == Some("DELETE");
+
             cat_widget.is_programmatic_update.set(true);
-            gtkbox.append(&row.vbox);
+            if is_deleted && row.vbox.parent().is_some() {
+                gtkbox.remove(&row.vbox);
+            } else if !is_deleted && row.vbox.parent().is_none() {
+                gtkbox.append(&row.vbox);
+            }
             cat_widget.is_programmatic_update.set(false);
-        } else if let Some(category_widget) = self.config_widgets.borrow().get(&change.category)
-            && let Some(widget_data) = category_widget.options.get(&change.key)
+        } else if let Some(category_widget) = self.config_widgets.borrow().get(category)
+            && let Some(widget_data) = category_widget.options.get(key)
         {
             let widget = &widget_data.widget;
-            let value_to_apply = change.old_value.as_ref().unwrap_or(&widget_data.default);
+            let value_to_apply = value.unwrap_or(&widget_data.default);
 
             category_widget.is_programmatic_update.set(true);
             set_widget_value(widget, value_to_apply);
             category_widget.is_programmatic_update.set(false);
         }
+    }
 
-        if let Some(target_widget) = self.find_target_widget(change) {
-            self.switch_to_category_tab(&change.category);
-            self.focus_and_scroll_to_widget(&target_widget);
-            animate_change(&target_widget);
-        }
+    fn apply_value_to_ui(&self, category: &str, key: &str, value: &str) {
+        self.history.borrow_mut().record_change(
+            category.to_string(),
+            key.to_string(),
+            value.to_string(),
+        );
+
+        self.set_value_in_ui(category, key, Some(value));
     }
 
-    fn apply_redo_to_ui(&self, change: &ConfigChange) {
-        if let Some(raw) = change.key.strip_suffix("_name")
-            && let Some(row) = self
-                .top_level_rows
-                .borrow()
-                .get(&(change.category.clone(), raw.to_string()))
-            && let Some(val) = &change.new_value
-        {
-            row.is_programmatic_update.set(true);
-            row.name_entry.set_text(val);
-            row.is_programmatic_update.set(false);
-        } else if let Some(raw) = change.key.strip_suffix("_value")
-            && let Some(row) = self
-                .top_level_rows
-                .borrow()
-                .get(&(change.category.clone(), raw.to_string()))
-            && let Some(val) = &change.new_value
-        {
-            row.is_programmatic_update.set(true);
-            row.value_entry.set_text(val);
-            row.is_programmatic_update.set(false);
-        } else if let Some(raw) = change.key.strip_suffix("_delete")
-            && change.new_value.as_deref() == Some("DELETE")
-            && let Some(row) = self
-                .top_level_rows
-                .borrow()
-                .get(&(change.category.clone(), raw.to_string()))
-            && let Some(cat_widget) = self.config_widgets.borrow().get(&change.category)
-            && let Some(wd) = cat_widget.options.get(&change.category)
-            && let Some(gtkbox) = wd.widget.downcast_ref::<gtk::Box>()
-        {
-            cat_widget.is_programmatic_update.set(true);
-            gtkbox.remove(&row.vbox);
-            cat_widget.is_programmatic_update.set(false);
-        } else if let Some(category_widget) = self.config_widgets.borrow().get(&change.category)
-            && let Some(widget_data) = category_widget.options.get(&change.key)
-        {
-            let widget = &widget_data.widget;
-            let value_to_apply = change.new_value.as_ref().unwrap_or(&widget_data.default);
+    /// Previews importing the export at `path` into the active profile, then applies the
+    /// checked entries as one undo step.
+    fn load_hyprviz_config(gui: &Rc<RefCell<ConfigGUI>>, path: &Path) {
+        let this = gui.borrow();
+        let imported = match fs::read_to_string(path) {
+            Ok(content) => match HyprvizExport::parse(&content) {
+                Ok(imported) => imported,
+                Err(e) => {
+                    this.custom_error_popup(
+                        &t!("gui.invalid_config"),
+                        &t!("gui.failed_to_parse_the_configuration_file_", error = e),
+                    );
+                    return;
+                }
+            },
+            Err(e) => {
+                this.custom_error_popup(
+                    &t!("gui.loading_failed"),
+                    &t!("gui.failed_to_read_the_configuration_file_", error = e),
+                );
+                return;
+            }
+        };
 
-            category_widget.is_programmatic_update.set(true);
-            set_widget_value(widget, value_to_apply);
-            category_widget.is_programmatic_update.set(false);
-        }
+        let current = match this.current_export() {
+            Ok(current) => current,
+            Err(e) => {
+                this.custom_error_popup(
+                    &t!("gui.reading_failed"),
+                    &t!("gui.failed_to_read_the_configuration_file_", error = e),
+                );
+                return;
+            }
+        };
 
-        if let Some(target_widget) = self.find_target_widget(change) {
-            self.switch_to_category_tab(&change.category);
-            self.focus_and_scroll_to_widget(&target_widget);
-            animate_change(&target_widget);
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        // Old exports carry no rows, so they must not remove any.
+        let entries = import_entries(&current, &imported, imported.version > 0);
+        let mut notes = Vec::new();
+        if !imported.meta.profile.is_empty() {
+            notes.push(
+                t!(
+                    "gui.exported_from_",
+                    profile = &imported.meta.profile,
+                    version = &imported.meta.app_version
+                )
+                .to_string(),
+            );
         }
-    }
-
-    fn load_hyprviz_config(&self, path: &PathBuf) {
-        match fs::read_to_string(path) {
-            Ok(content) => {
-                if let Ok(config) = serde_json::from_str::<HashMap<String, String>>(&content) {
-                    for (key, value) in config {
-                        let parts: Vec<&str> = key.split(':').collect();
-                        if parts.len() >= 2 {
-                            let category = parts[0].to_string();
-                            let name = parts[1..].join(":");
-                            if let Some(widget) = self.config_widgets.borrow().get(&category)
-                                && let Some(option_widget) = widget.options.get(&name)
-                            {
-                                let actual_widget = &option_widget.widget;
-
-                                set_widget_value(actual_widget, &value);
-                                self.history.borrow_mut().record_change(
-                                    category,
-                                    name,
-                                    value.to_string(),
-                                );
-                            }
-                        }
-                    }
-                    self.custom_info_popup(
-                        &t!("gui.config_loaded"),
-                        &t!("gui.hyprviz_configuration_loaded_successfully"),
+        let window = this.window.clone();
+        drop(this);
+
+        let gui = Rc::clone(gui);
+        let label = file_name.clone();
+        show_import_preview(
+            &window,
+            &t!("gui.import_", file = &file_name),
+            &notes,
+            entries,
+            category_list(),
+            move |changes| {
+                let this = gui.borrow();
+                let skipped =
+                    this.apply_copies(t!("gui.load_hyprviz_config_", file = &label), changes);
+                if skipped > 0 {
+                    this.custom_error_popup(
+                        &t!("gui.config_partially_loaded"),
+                        &t!("gui.rows_not_imported_", count = skipped),
                     );
                 } else {
-                    self.custom_error_popup(
-                        &t!("gui.invalid_config"),
-                        &t!("gui.failed_to_parse_the_configuration_file"),
+                    this.custom_info_popup(
+                        &t!("gui.config_loaded"),
+                        &t!("gui.hyprviz_configuration_loaded_successfully"),
                     );
                 }
-            }
+            },
+        );
+    }
+
+    /// Previews a pasted hyprlang snippet as changes to the active profile, then applies the
+    /// checked ones as one undo step.
+    fn preview_snippet(gui: &Rc<RefCell<ConfigGUI>>, text: &str) {
+        let this = gui.borrow();
+        let current = match this.current_export() {
+            Ok(current) => current,
             Err(e) => {
-                self.custom_error_popup(
-                    &t!("gui.loading_failed"),
+                this.custom_error_popup(
+                    &t!("gui.reading_failed"),
                     &t!("gui.failed_to_read_the_configuration_file_", error = e),
                 );
+                return;
             }
+        };
+
+        let (snippet, mut unknown) = parse_snippet(text);
+        let (entries, unmatched): (Vec<_>, Vec<_>) = import_entries(&current, &snippet, false)
+            .into_iter()
+            .partition(|entry| match &entry.change {
+                DiffCopy::Option { category, key, .. } => this
+                    .rendered_widget(category, |widget| widget.options.contains_key(key))
+                    .unwrap_or(false),
+                _ => true,
+            });
+        unknown.extend(
+            unmatched
+                .into_iter()
+                .filter_map(|entry| match entry.change {
+                    DiffCopy::Option { category, key, .. } => Some(option_path(&category, &key)),
+                    _ => None,
+                }),
+        );
+
+        let mut notes = Vec::new();
+        if !unknown.is_empty() {
+            notes
+                .push(t!("gui.snippet_unknown_options_", options = unknown.join(", ")).to_string());
         }
+        let window = this.window.clone();
+        drop(this);
+
+        let gui = Rc::clone(gui);
+        show_import_preview(
+            &window,
+            &t!("gui.paste_snippet"),
+            &notes,
+            entries,
+            category_list(),
+            move |changes| {
+                let this = gui.borrow();
+                let skipped = this.apply_copies(t!("gui.pasted_snippet"), changes);
+                if skipped > 0 {
+                    this.custom_error_popup(
+                        &t!("gui.snippet_partially_applied"),
+                        &t!("gui.rows_not_imported_", count = skipped),
+                    );
+                }
+            },
+        );
+    }
+
+    /// Applies `changes` as one undo step labelled `label`, returning how many were skipped.
+    fn apply_copies(&self, label: impl Into<String>, changes: Vec<DiffCopy>) -> usize {
+        let history = Rc::clone(&self.history);
+        history.borrow_mut().begin_transaction(label);
+        let skipped = changes
+            .into_iter()
+            .map(|change| self.apply_copy(change))
+            .filter(|applied| !applied)
+            .count();
+        history.borrow_mut().commit_transaction();
+        skipped
+    }
+
+    /// The active profile with its unsaved changes, as it would be exported.
+    fn current_export(&self) -> io::Result<HyprvizExport> {
+        let profile = self.selected_profile();
+        let profile_path = get_config_path(true, &profile);
+        let changes = self.history.borrow().get_current_state().clone();
+
+        let content = match self
+            .render_files(&changes)?
+            .into_iter()
+            .find(|file| file.path == profile_path)
+        {
+            Some(file) => file.document.to_string(),
+            None => fs::read_to_string(&profile_path).unwrap_or_default(),
+        };
+        let config = expand_source_str(&profile_path, &content).unwrap_or(content);
+
+        Ok(HyprvizExport::from_config(&profile, &config))
     }
 
     fn save_hyprviz_config(&self, path: &Path) {
-        let config: HashMap<String, String> = self
-            .history
-            .borrow()
-            .get_current_state()
-            .iter()
-            .map(|((category, name), value)| (format!("{category}:{name}"), value.clone()))
-            .collect();
+        let export = match self.current_export() {
+            Ok(export) => export,
+            Err(e) => {
+                self.custom_error_popup(
+                    &t!("gui.reading_failed"),
+                    &t!("gui.failed_to_read_the_configuration_file_", error = e),
+                );
+                return;
+            }
+        };
 
-        match serde_json::to_string_pretty(&config) {
+        match export.to_json() {
             Ok(json) => match atomic_write(path, &json) {
                 Ok(_) => {
                     self.custom_info_popup(
@@ -949,88 +1718,710 @@ along with this program; if not, see
         }
     }
 
-    fn filter_options(&self, search_text: impl AsRef<str>) {
-        let search_text = search_text.as_ref().to_lowercase();
+    /// Everything search can find: options, guides and category pages from the index built
+    /// after the last load, and the top-level rows as they are now.
+    fn search_index(&self) -> Vec<SearchItem> {
+        let cached = self.search_index.borrow().clone();
+        let pages = cached.unwrap_or_else(|| {
+            let pages = Rc::new(self.build_page_index());
+            *self.search_index.borrow_mut() = Some(pages.clone());
+            pages
+        });
+
+        let mut items = pages.as_ref().clone();
+        items.extend(self.row_search_items());
+        items
+    }
+
+    /// Options with their values at the time, guides and the category pages. Renders every page
+    /// that has not been shown yet.
+    fn build_page_index(&self) -> Vec<SearchItem> {
+        let mut items = Vec::new();
 
-        self.sidebar.set_visible(search_text.is_empty());
+        for (display_name, category) in category_list() {
+            let Some(container) = self.rendered_widget(category, |widget| widget.container.clone())
+            else {
+                continue;
+            };
+            items.push(SearchItem {
+                category: category.to_string(),
+                title: display_name.clone(),
+                subtitle: t!("gui.category").to_string(),
+                fields: vec![
+                    (FieldKind::Name, display_name.clone()),
+                    (FieldKind::Name, category.to_string()),
+                ],
+                widget: container.upcast(),
+            });
 
-        for config_widget in self.config_widgets.borrow().values() {
-            if search_text.is_empty() {
-                config_widget.scrolled_window.set_visible(true);
-                if let Some(scrolled) = config_widget.scrolled_window.child()
-                    && let Some(container) = scrolled.first_child()
-                {
-                    let mut child = container.first_child();
-                    while let Some(widget) = child {
-                        widget.set_visible(true);
-                        child = widget.next_sibling();
+            // Borrowed after rendering, which records the initial values.
+            let history = self.history.borrow();
+            self.rendered_widget(category, |widget| {
+                for (key, data) in &widget.options {
+                    // Pages of rows keep their row list under the category name.
+                    if key == category {
+                        continue;
                     }
+
+                    let value = history
+                        .get_current_state()
+                        .get(&(category.to_string(), key.clone()))
+                        .cloned()
+                        .or_else(|| history.get_initial_value(category, key))
+                        .unwrap_or_else(|| data.default.clone());
+                    let path = option_path(category, key);
+                    items.push(SearchItem {
+                        category: category.to_string(),
+                        title: data.label.clone(),
+                        subtitle: format!("{} › {}", display_name, path),
+                        fields: vec![
+                            (FieldKind::Name, data.label.clone()),
+                            (FieldKind::Name, path),
+                            (FieldKind::Context, value),
+                            (FieldKind::Context, display_name.clone()),
+                            (FieldKind::Text, data.description.clone()),
+                        ],
+                        widget: data
+                            .visual_widget
+                            .clone()
+                            .unwrap_or_else(|| data.widget.clone()),
+                    });
                 }
-            } else {
-                let mut has_matches = false;
 
-                if let Some(scrolled) = config_widget.scrolled_window.child()
-                    && let Some(container) = scrolled.first_child()
-                {
-                    let mut child = container.first_child();
-                    while let Some(widget) = child {
-                        widget.set_visible(false);
-                        if let Some(box_widget) = widget.downcast_ref::<gtk::Box>()
-                            && let Some(label_box) = box_widget.first_child()
-                            && let Some(label) = label_box.first_child()
-                            && let Some(label) = label.downcast_ref::<gtk::Label>()
-                            && label.text().to_lowercase().contains(&search_text)
-                        {
-                            has_matches = true;
-                            widget.set_visible(true);
-                        }
+                for (name, header) in widget.guides.borrow().iter() {
+                    let title = t!("gui.guide_", name = name.replace('-', " ")).to_string();
+                    items.push(SearchItem {
+                        category: category.to_string(),
+                        title: title.clone(),
+                        subtitle: display_name.clone(),
+                        fields: vec![
+                            (FieldKind::Name, title),
+                            (FieldKind::Text, guide_source(name).to_string()),
+                        ],
+                        widget: header.clone(),
+                    });
+                }
+            });
+        }
+
+        items
+    }
+
+    /// Top-level rows shown on their pages. Rows are added and removed while editing, so these
+    /// are not cached.
+    fn row_search_items(&self) -> Vec<SearchItem> {
+        let mut items = Vec::new();
+        let display_names: HashMap<&str, String> = category_list()
+            .into_iter()
+            .map(|(display_name, category)| (category, display_name))
+            .collect();
+        for ((category, _), row) in self.top_level_rows.borrow().iter() {
+            if row.vbox.parent().is_none() {
+                continue;
+            }
+            let name = row.name_entry.text().trim().to_string();
+            let value = row.value_entry.text().trim().to_string();
+            let display_name = display_names
+                .get(category.as_str())
+                .cloned()
+                .unwrap_or_else(|| category.clone());
+            items.push(SearchItem {
+                category: category.clone(),
+                title: format!("{} = {}", name, value),
+                subtitle: display_name.clone(),
+                fields: vec![
+                    (FieldKind::Name, name),
+                    (FieldKind::Context, value),
+                    (FieldKind::Context, display_name),
+                ],
+                widget: row.vbox.clone().upcast(),
+            });
+        }
+
+        items
+    }
+
+    fn update_search_results(&self, query: &str) {
+        while let Some(child) = self.search_results.first_child() {
+            self.search_results.remove(&child);
+        }
+        self.search_targets.borrow_mut().clear();
+        if let Some(results_window) = self.search_results.ancestor(ScrolledWindow::static_type()) {
+            results_window.set_visible(false);
+        }
+        if query.trim().is_empty() {
+            return;
+        }
+
+        let items = self.search_index();
+        let results = rank(&items, query, 50);
+        if results.is_empty() {
+            let empty_label = Label::new(Some(&t!("gui.no_results")));
+            empty_label.add_css_class("dim-label");
+            empty_label.set_margin_top(MARGIN_NORMAL / 2);
+            empty_label.set_margin_bottom(MARGIN_NORMAL / 2);
+            let row = ListBoxRow::new();
+            row.set_child(Some(&empty_label));
+            row.set_activatable(false);
+            row.set_selectable(false);
+            self.search_results.append(&row);
+            return;
+        }
+
+        let mut targets = self.search_targets.borrow_mut();
+        for item in results {
+            let row_box = Box::new(Orientation::Vertical, 2);
+            row_box.set_margin_start(MARGIN_NORMAL / 2);
+            row_box.set_margin_end(MARGIN_NORMAL / 2);
+            row_box.set_margin_top(MARGIN_NORMAL / 3);
+            row_box.set_margin_bottom(MARGIN_NORMAL / 3);
+
+            let title_label = Label::new(Some(&item.title));
+            title_label.set_halign(gtk::Align::Start);
+            title_label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+            row_box.append(&title_label);
+
+            let subtitle_label = Label::new(Some(&item.subtitle));
+            subtitle_label.set_halign(gtk::Align::Start);
+            subtitle_label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+            subtitle_label.add_css_class("dim-label");
+            subtitle_label.add_css_class("caption");
+            row_box.append(&subtitle_label);
+
+            self.search_results.append(&row_box);
+            targets.push((item.category.clone(), item.widget.clone()));
+        }
+    }
+
+    fn open_search_result(&self, index: usize) {
+        let Some((category, widget)) = self.search_targets.borrow().get(index).cloned() else {
+            return;
+        };
+
+        self.search_popover.popdown();
+        self.switch_to_category_tab(&category);
+        self.focus_and_scroll_to_widget(&widget);
+        animate_change(&widget);
+    }
+
+    /// Commands offered by the command palette: everything search finds, resetting options
+    /// that differ from their default, switching profiles and the common actions.
+    fn palette_commands(&self) -> Vec<PaletteCommand> {
+        let action = t!("command_palette.action").to_string();
+        let mut commands = vec![
+            PaletteCommand {
+                id: "save".to_string(),
+                title: t!("command_palette.save").to_string(),
+                subtitle: action.clone(),
+                fields: vec![(FieldKind::Name, t!("command_palette.save").to_string())],
+                action: CommandAction::Click(self.save_button.clone()),
+            },
+            PaletteCommand {
+                id: "history".to_string(),
+                title: t!("command_palette.open_history").to_string(),
+                subtitle: action.clone(),
+                fields: vec![(
+                    FieldKind::Name,
+                    t!("command_palette.open_history").to_string(),
+                )],
+                action: CommandAction::Click(self.history_button.clone()),
+            },
+            PaletteCommand {
+                id: "create_bind".to_string(),
+                title: t!("command_palette.create_bind").to_string(),
+                subtitle: action.clone(),
+                fields: vec![(
+                    FieldKind::Name,
+                    t!("command_palette.create_bind").to_string(),
+                )],
+                action: CommandAction::CreateBind,
+            },
+        ];
+
+        let toggle_title = if load_ui_settings().hide_previews {
+            t!("command_palette.show_previews")
+        } else {
+            t!("command_palette.hide_previews")
+        }
+        .to_string();
+        commands.push(PaletteCommand {
+            id: "toggle_previews".to_string(),
+            title: toggle_title.clone(),
+            subtitle: action,
+            fields: vec![
+                (FieldKind::Name, toggle_title),
+                (
+                    FieldKind::Context,
+                    t!("command_palette.live_preview").to_string(),
+                ),
+            ],
+            action: CommandAction::TogglePreviews,
+        });
+
+        let active = self.history.borrow().profile().to_string();
+        for profile in profile_list() {
+            if profile == active {
+                continue;
+            }
+            let title = t!("command_palette.switch_to_profile_", name = profile).to_string();
+            commands.push(PaletteCommand {
+                id: format!("profile:{}", profile),
+                title: title.clone(),
+                subtitle: t!("command_palette.profile").to_string(),
+                fields: vec![(FieldKind::Name, title), (FieldKind::Name, profile.clone())],
+                action: CommandAction::SwitchProfile(profile),
+            });
+        }
+
+        for item in self.search_index() {
+            commands.push(PaletteCommand {
+                id: format!("jump:{}:{}", item.category, item.title),
+                title: item.title,
+                subtitle: item.subtitle,
+                fields: item.fields,
+                action: CommandAction::Jump {
+                    category: item.category,
+                    widget: item.widget,
+                },
+            });
+        }
 
-                        child = widget.next_sibling();
+        // The index rendered every page, so only the history needs borrowing here.
+        let history = self.history.borrow();
+        for (display_name, category) in category_list() {
+            self.rendered_widget(category, |widget| {
+                for (key, data) in &widget.options {
+                    if key == category {
+                        continue;
+                    }
+
+                    let value = history
+                        .get_current_state()
+                        .get(&(category.to_string(), key.clone()))
+                        .cloned()
+                        .or_else(|| history.get_initial_value(category, key))
+                        .unwrap_or_else(|| data.default.clone());
+                    if value == data.default {
+                        continue;
                     }
+
+                    let path = option_path(category, key);
+                    let title = t!("command_palette.reset_", name = data.label).to_string();
+                    commands.push(PaletteCommand {
+                        id: format!("reset:{}:{}", category, key),
+                        title: title.clone(),
+                        subtitle: format!("{} › {} = {}", display_name, path, data.default),
+                        fields: vec![
+                            (FieldKind::Name, title),
+                            (FieldKind::Name, path),
+                            (FieldKind::Context, display_name.clone()),
+                        ],
+                        action: CommandAction::ResetOption {
+                            category: category.to_string(),
+                            widget: data.widget.clone(),
+                            default: data.default.clone(),
+                        },
+                    });
                 }
+            });
+        }
+
+        commands
+    }
 
-                config_widget.scrolled_window.set_visible(has_matches);
+    fn open_command_palette(gui: &Rc<RefCell<ConfigGUI>>) {
+        let this = gui.borrow();
+        let commands = this.palette_commands();
+        let gui_clone = Rc::clone(gui);
+        show_command_palette(&this.window, commands, move |command| {
+            Self::run_palette_command(&gui_clone, command);
+        });
+    }
+
+    fn run_palette_command(gui: &Rc<RefCell<ConfigGUI>>, command: &PaletteCommand) {
+        match &command.action {
+            CommandAction::Jump { category, widget } => {
+                let this = gui.borrow();
+                this.switch_to_category_tab(category);
+                this.focus_and_scroll_to_widget(widget);
+                animate_change(widget);
+            }
+            CommandAction::Click(button) => button.emit_clicked(),
+            CommandAction::SwitchProfile(profile) => gui.borrow().select_profile(profile),
+            CommandAction::ResetOption {
+                category,
+                widget,
+                default,
+            } => {
+                let this = gui.borrow();
+                this.switch_to_category_tab(category);
+                this.focus_and_scroll_to_widget(widget);
+                set_widget_value(widget, default);
+                animate_change(widget);
+            }
+            CommandAction::CreateBind => {
+                let this = gui.borrow();
+                let Some(append_row) = this
+                    .rendered_widget("bind", |widget| widget.append_row.borrow().clone())
+                    .flatten()
+                else {
+                    return;
+                };
+                this.switch_to_category_tab("bind");
+
+                let existing: BTreeSet<(String, String)> =
+                    this.top_level_rows.borrow().keys().cloned().collect();
+                append_row("bind", "");
+                let row = this
+                    .top_level_rows
+                    .borrow()
+                    .iter()
+                    .find(|(key, _)| key.0 == "bind" && !existing.contains(*key))
+                    .map(|(_, row)| row.clone());
+                if let Some(row) = row {
+                    this.focus_and_scroll_to_widget(row.vbox.upcast_ref());
+                    row.value_entry.grab_focus();
+                    animate_change(row.vbox.upcast_ref());
+                }
+            }
+            CommandAction::TogglePreviews => {
+                let mut settings = load_ui_settings();
+                settings.hide_previews = !settings.hide_previews;
+                if let Err(e) = save_ui_settings(&settings) {
+                    eprintln!("Failed to save UI settings: {}", e);
+                }
+
+                let this = gui.borrow();
+                for widget in this.config_widgets.borrow().values() {
+                    if let Some(preview) = &widget.preview {
+                        preview.set_visible(!settings.hide_previews);
+                    }
+                }
             }
         }
     }
 
-    fn save_config_file(&self) {
-        let profile_name = {
-            let selected_index = self.profile_dropdown.selected();
-            let model = self.profile_dropdown.model().unwrap();
+    /// Shows an error and returns `false` if no new profile can be called `name`.
+    fn validate_new_profile_name(&self, name: &str) -> bool {
+        if name.is_empty() || name == "Default" {
+            self.custom_error_popup(
+                &t!("gui.invalid_profile_name"),
+                &t!("gui.profile_name_cannot_be_empty_or_default"),
+            );
+            return false;
+        }
 
-            if let Some(item) = model.item(selected_index)
-                && let Some(string_object) = item.downcast_ref::<StringObject>()
-            {
-                string_object.string().as_str().to_string()
-            } else {
-                "Default".to_string()
+        if name.contains(['/', '\\']) {
+            self.custom_error_popup(
+                &t!("gui.invalid_profile_name"),
+                &t!("gui.profile_name_cannot_contain_slashes"),
+            );
+            return false;
+        }
+
+        if get_config_path(true, name).exists() {
+            self.custom_error_popup(
+                &t!("gui.profile_exists"),
+                &t!("gui.profile__already_exists", name = name),
+            );
+            return false;
+        }
+
+        true
+    }
+
+    /// Rebuilds the profile list, keeping `active` selected without offering a switch.
+    fn refresh_profile_list(&self, active: &str) {
+        let profiles = profile_list();
+        let profiles_str_vec: Vec<&str> = profiles.iter().map(|s| s.as_str()).collect();
+        let index = profiles
+            .iter()
+            .position(|p| p == active)
+            .map_or(gtk::INVALID_LIST_POSITION, |pos| pos as u32);
+
+        self.is_programmatic_switch.set(true);
+        self.profile_dropdown
+            .set_model(Some(&StringList::new(&profiles_str_vec)));
+        self.profile_dropdown.set_selected(index);
+        self.is_programmatic_switch.set(false);
+        self.last_confirmed_index.set(index);
+    }
+
+    /// Rebuilds the profile list and selects `profile`, which offers switching to it.
+    fn select_profile(&self, profile: &str) {
+        let active = self.history.borrow().profile().to_string();
+        self.refresh_profile_list(&active);
+
+        if let Some(pos) = profile_list().iter().position(|p| p == profile) {
+            self.profile_dropdown.set_selected(pos as u32);
+        }
+    }
+
+    /// Applies an entry copied in the profile comparison as one undoable change. Returns
+    /// whether it was applied.
+    fn copy_from_profile(&self, source: &str, copy: DiffCopy) -> bool {
+        let history = Rc::clone(&self.history);
+        history
+            .borrow_mut()
+            .begin_transaction(t!("gui.copied_from_", name = source));
+        let copied = self.apply_copy(copy);
+        history.borrow_mut().commit_transaction();
+
+        if !copied {
+            self.custom_error_popup(
+                &t!("gui.copy_failed"),
+                &t!("gui.row_not_editable_in_profile"),
+            );
+        }
+        copied
+    }
+
+    /// Makes the active profile match `copy`, returning whether the row it names could be
+    /// edited. Callers group the change into a transaction.
+    fn apply_copy(&self, copy: DiffCopy) -> bool {
+        match copy {
+            DiffCopy::Option {
+                category,
+                key,
+                value,
+            } => {
+                self.apply_value_to_ui(&category, &key, &value);
+                true
             }
-        };
-        let path = get_config_path(true, &profile_name);
+            DiffCopy::AddRow {
+                category,
+                name,
+                value,
+            } => {
+                let append_row =
+                    self.rendered_widget(&category, |widget| widget.append_row.borrow().clone());
+                match append_row.flatten() {
+                    Some(append_row) => {
+                        append_row(&name, &value);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            DiffCopy::RemoveRow {
+                category,
+                name,
+                value,
+            } => {
+                self.rendered_widget(&category, |_| ());
+                let raw = self
+                    .top_level_rows
+                    .borrow()
+                    .iter()
+                    .find(|((row_category, _), row)| {
+                        *row_category == category
+                            && row.vbox.parent().is_some()
+                            && row.name_entry.text().trim() == name
+                            && row.value_entry.text().trim() == value
+                    })
+                    .map(|((_, raw), _)| raw.clone());
+                match raw {
+                    Some(raw) => {
+                        for suffix in ["_name", "_value"] {
+                            self.history
+                                .borrow_mut()
+                                .record_removal(category.clone(), format!("{raw}{suffix}"));
+                        }
+                        self.apply_value_to_ui(&category, &format!("{raw}_delete"), "DELETE");
+                        true
+                    }
+                    None => false,
+                }
+            }
+        }
+    }
 
-        if !path.exists()
-            && let Err(e) = fs::File::create(&path)
+    /// Renders the page of `category` if it has not been shown yet, then passes it to `f`.
+    fn rendered_widget<T>(&self, category: &str, f: impl FnOnce(&ConfigWidget) -> T) -> Option<T> {
+        let mut widgets = self.config_widgets.borrow_mut();
+        let widget = widgets.get_mut(category)?;
+        widget.render();
+        Some(f(widget))
+    }
+
+    /// Shows the icon and description of the active profile next to the profile list.
+    fn update_profile_header(&self) {
+        let meta = load_profile_meta(&self.selected_profile());
+        self.profile_icon.set_icon_name(Some(meta.icon.as_str()));
+        self.profile_icon.set_visible(!meta.icon.is_empty());
+
+        let mut tooltip = Vec::new();
+        if !meta.description.is_empty() {
+            tooltip.push(meta.description.clone());
+        }
+        if let Some(parent) = &meta.extends {
+            tooltip.push(t!("gui.inherits_from_", name = parent).to_string());
+        }
+        let tooltip = tooltip.join("\n");
+        self.profile_dropdown
+            .set_tooltip_text((!tooltip.is_empty()).then_some(tooltip.as_str()));
+    }
+
+    fn selected_profile(&self) -> String {
+        let selected_index = self.profile_dropdown.selected();
+        let model = self.profile_dropdown.model().unwrap();
+
+        if let Some(item) = model.item(selected_index)
+            && let Some(string_object) = item.downcast_ref::<StringObject>()
         {
-            self.custom_error_popup_critical(
-                &t!("gui.failed_to_create_hyprviz_config_file"),
-                &t!("gui.failed_to_create_hyprviz_config_file_", error = e),
+            string_object.string().as_str().to_string()
+        } else {
+            "Default".to_string()
+        }
+    }
+
+    /// Shows the diff between the files on disk and what saving would write, letting the user
+    /// leave out individual changes before saving.
+    pub fn review_and_save(gui: Rc<RefCell<ConfigGUI>>) {
+        let this = gui.borrow();
+        let changes = this.history.borrow().get_current_state().clone();
+
+        if changes.is_empty() {
+            this.custom_error_popup(&t!("gui.saving_failed"), &t!("gui.no_changes_to_save"));
+            return;
+        }
+
+        if let Err(e) = this.render_files(&changes) {
+            this.custom_error_popup_critical(
+                &t!("gui.reading_failed"),
+                &t!("gui.failed_to_read_the_configuration_file_", error = e),
             );
+            return;
         }
 
-        let config_str = match fs::read_to_string(&path) {
-            Ok(s) => s,
-            Err(e) => {
-                self.custom_error_popup_critical(
-                    &t!("gui.reading_failed"),
-                    &t!("gui.failed_to_read_the_configuration_file_", error = e),
-                );
-                return;
+        let subset = |changes: &HashMap<(String, String), String>, keys: &[(String, String)]| {
+            keys.iter()
+                .filter_map(|key| Some((key.clone(), changes.get(key)?.clone())))
+                .collect::<HashMap<_, _>>()
+        };
+
+        let render = {
+            let gui = Rc::clone(&gui);
+            let changes = changes.clone();
+            move |keys: &[(String, String)]| {
+                gui.borrow()
+                    .render_files(&subset(&changes, keys))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|file| {
+                        (
+                            file.path.display().to_string(),
+                            file.original,
+                            file.document.to_string(),
+                        )
+                    })
+                    .collect()
             }
         };
 
-        let base_config_str = match expand_base_config() {
-            Ok(s) => s,
+        let on_save = {
+            let gui = Rc::clone(&gui);
+            let pending = changes.clone();
+            move |keys: Vec<(String, String)>, reload: bool| {
+                gui.borrow()
+                    .save_config_file(&subset(&pending, &keys), reload);
+            }
+        };
+
+        show_save_review(
+            &this.window,
+            group_pending_changes(&changes),
+            render,
+            on_save,
+        );
+    }
+
+    /// Returns the document of `path`, re-parsing it only when `text` no longer matches the
+    /// cached one, e.g. because the file was edited outside of hyprviz.
+    fn document_for(&self, path: &Path, text: &str) -> ConfigDocument {
+        let mut documents = self.documents.borrow_mut();
+        match documents.get(path) {
+            Some(document) if document.to_string() == text => document.clone(),
+            _ => {
+                let document = ConfigDocument::parse(text);
+                documents.insert(path.to_path_buf(), document.clone());
+                document
+            }
+        }
+    }
+
+    /// Applies `changes` to every file they touch: the profile, and the files of rows and
+    /// options that are edited in place. The profile comes first.
+    fn render_files(
+        &self,
+        changes: &HashMap<(String, String), String>,
+    ) -> io::Result<Vec<RenderedFile>> {
+        let profile_path = get_config_path(true, &self.selected_profile());
+
+        // Options toggled to be edited in place go to the file they are defined in.
+        let in_place_path = |category: &str, key: &str| -> Option<PathBuf> {
+            let widgets = self.config_widgets.borrow();
+            let widget = widgets.get(category)?;
+            if !widget.in_place_options.borrow().contains(key) {
+                return None;
+            }
+            let sources = widget.option_sources.borrow();
+            Some(sources.get(key)?.origin.as_ref()?.path.clone())
+        };
+
+        let mut by_file: BTreeMap<PathBuf, HashMap<(String, String), String>> = BTreeMap::new();
+        let mut profile_changes = HashMap::new();
+        for ((category, key), value) in changes {
+            let entry = ((category.clone(), key.clone()), value.clone());
+            let path = split_origin_raw(key)
+                .map(|(path, _)| path)
+                .or_else(|| in_place_path(category, key));
+            match path {
+                Some(path) => {
+                    by_file.entry(path).or_default().insert(entry.0, entry.1);
+                }
+                None => {
+                    profile_changes.insert(entry.0, entry.1);
+                }
+            }
+        }
+
+        let mut files = Vec::new();
+        if !profile_changes.is_empty() {
+            files.push((profile_path.clone(), profile_changes));
+        }
+        files.extend(by_file);
+
+        files
+            .into_iter()
+            .map(|(path, changes)| -> io::Result<RenderedFile> {
+                let original = if path == profile_path && !path.exists() {
+                    String::new()
+                } else {
+                    fs::read_to_string(&path)?
+                };
+
+                let mut document = self.document_for(&path, &original);
+                let renamed = self.apply_changes(&mut document, &changes);
+
+                Ok(RenderedFile {
+                    path,
+                    original,
+                    document,
+                    renamed,
+                })
+            })
+            .collect()
+    }
+
+    fn save_config_file(&self, changes: &HashMap<(String, String), String>, reload: bool) {
+        if changes.is_empty() {
+            self.custom_error_popup(&t!("gui.saving_failed"), &t!("gui.no_changes_to_save"));
+            return;
+        }
+
+        let files = match self.render_files(changes) {
+            Ok(files) => files,
             Err(e) => {
                 self.custom_error_popup_critical(
                     &t!("gui.reading_failed"),
@@ -1040,83 +2431,73 @@ along with this program; if not, see
             }
         };
 
-        let history = self.history.clone();
-
-        if !history.borrow().get_current_state().is_empty() {
-            let mut parsed_config = mute_stdout(|| parse_config(&config_str));
-
-            self.apply_changes(&mut parsed_config);
-
-            let updated_config_str = parsed_config.to_string();
-            let temp_path = path.with_extension("tmp");
-
-            let result = atomic_write(&path, &updated_config_str);
-
-            let mut load_config = false;
+        let writes: Vec<(PathBuf, String)> = files
+            .iter()
+            .map(|file| (file.path.clone(), file.document.to_string()))
+            .collect();
 
-            let parsed_config_rc = Rc::new(RefCell::new(parsed_config));
-            let base_config_rc =
-                Rc::new(RefCell::new(mute_stdout(|| parse_config(&base_config_str))));
+        if let Err(e) = atomic_write_all(&writes) {
+            self.custom_error_popup(
+                &t!("gui.saving_failed"),
+                &t!(
+                    "gui.failed_to_save_the_configuration_automatically_",
+                    error = e
+                ),
+            );
+            return;
+        }
 
-            for category_widget in self.config_widgets.borrow_mut().values_mut() {
-                for widget_data in category_widget.options.values() {
-                    let widget = &widget_data.widget;
+        let profile = self.selected_profile();
+        let profile_path = get_config_path(true, &profile);
+        if writes.iter().any(|(path, _)| *path == profile_path)
+            && let Err(e) = touch_profile_meta(&profile)
+        {
+            eprintln!("Failed to write profile metadata: {}", e);
+        }
 
-                    if let Some(gtkbox) = widget.downcast_ref::<Box>() {
-                        while let Some(child) = gtkbox.first_child() {
-                            gtkbox.remove(&child);
-                        }
+        let applied = self.history.borrow().applied_changes(changes);
 
-                        load_config = true
+        {
+            let mut history = self.history.borrow_mut();
+            history.mark_saved(changes);
+
+            let mut top_level_rows = self.top_level_rows.borrow_mut();
+            for file in files {
+                for (category, old_raw, new_raw) in file.renamed {
+                    history.rename_row(&category, &old_raw, &new_raw);
+                    if let Some(row) = top_level_rows.remove(&(category.clone(), old_raw)) {
+                        *row.raw.borrow_mut() = new_raw.clone();
+                        top_level_rows.insert((category, new_raw), row);
                     }
                 }
 
-                if load_config {
-                    category_widget.load_config(
-                        self.window.clone(),
-                        parsed_config_rc.clone(),
-                        base_config_rc.clone(),
-                        profile_name.clone(),
-                        self.history.clone(),
-                        self.top_level_rows.clone(),
-                    );
-                    load_config = false;
-                }
+                println!("Configuration saved automatically to: {:?}", file.path);
+                self.documents.borrow_mut().insert(file.path, file.document);
             }
 
             let config_path_full = get_config_path(false, "Default");
-            let expanded_config_str = match expand_source(&config_path_full) {
-                Ok(s) => s,
-                Err(e) => {
-                    eprintln!("Failed to expand sources: {}", e);
-                    updated_config_str.clone()
+            match expand_source(&config_path_full) {
+                Ok(expanded_config_str) => {
+                    history.set_initial_config_hash(&expanded_config_str);
+                    *self.config_snapshot.borrow_mut() = expanded_config_str;
                 }
-            };
-            let expanded_parsed_config = mute_stdout(|| parse_config(&expanded_config_str));
-
-            self.history
-                .borrow_mut()
-                .set_initial_config_hash(&expanded_parsed_config.content);
-            self.history.borrow_mut().reset_unsaved_changes();
+                Err(e) => eprintln!("Failed to expand sources: {}", e),
+            }
+        }
+        self.apply_view_filter(false);
 
-            match result {
-                Ok(()) => {
-                    println!("Configuration saved automatically to: {:?}", path);
-                    reload_hyprland();
-                }
-                Err(e) => {
-                    let _ = fs::remove_file(&temp_path);
-                    self.custom_error_popup(
-                        &t!("gui.saving_failed"),
-                        &t!(
-                            "gui.failed_to_save_the_configuration_automatically_",
-                            error = e
-                        ),
-                    );
-                }
+        if load_git_settings().enabled {
+            let paths: Vec<PathBuf> = writes.into_iter().map(|(path, _)| path).collect();
+            if let Err(e) = git_commit(&paths, &git_commit_message(&profile, &applied)) {
+                self.custom_error_popup(
+                    &t!("gui.git_commit_failed"),
+                    &t!("gui.failed_to_commit_the_saved_files_", error = e),
+                );
             }
-        } else {
-            self.custom_error_popup(&t!("gui.saving_failed"), &t!("gui.no_changes_to_save"));
+        }
+
+        if reload {
+            reload_hyprland();
         }
     }
 
@@ -1192,11 +2573,11 @@ along with this program; if not, see
             .title(t!("gui.history_manager"))
             .transient_for(&gui.borrow().window)
             .modal(true)
-            .default_width(450)
+            .default_width(560)
             .default_height(750)
             .build();
 
-        let history = &gui.borrow().history;
+        let history = Rc::clone(&gui.borrow().history);
 
         let main_box = Box::new(Orientation::Vertical, 16);
         main_box.set_margin_start(MARGIN_NORMAL * 2);
@@ -1204,41 +2585,31 @@ along with this program; if not, see
         main_box.set_margin_top(MARGIN_NORMAL * 2);
         main_box.set_margin_bottom(MARGIN_NORMAL * 2);
 
+        let filter_box = Box::new(Orientation::Horizontal, 8);
+        let filter_label = Label::new(Some(&t!("gui.filter_by_category")));
+        let category_dropdown = DropDown::from_strings(&[]);
+        category_dropdown.set_hexpand(true);
+        filter_box.append(&filter_label);
+        filter_box.append(&category_dropdown);
+        main_box.append(&filter_box);
+
+        let hint_label = Label::new(Some(&t!("gui.history_timeline_hint")));
+        hint_label.set_halign(gtk::Align::Start);
+        hint_label.set_wrap(true);
+        hint_label.add_css_class("dim-label");
+        main_box.append(&hint_label);
+
         let scrolled_window = ScrolledWindow::new();
         scrolled_window.set_vexpand(true);
         scrolled_window.set_hexpand(true);
         scrolled_window.set_min_content_height(400);
 
-        let content_box = Box::new(Orientation::Vertical, 20);
-
-        let create_section = |title: &str| -> (Label, TextView) {
-            let label = Label::new(Some(title));
-            label.set_halign(gtk::Align::Start);
-            label.add_css_class("heading");
-
-            let text_view = TextView::new();
-            text_view.set_editable(false);
-            text_view.set_cursor_visible(false);
-            text_view.set_monospace(true);
-            text_view.set_wrap_mode(WrapMode::WordChar);
-            text_view.set_vexpand(true);
-            text_view.set_hexpand(true);
-
-            (label, text_view)
-        };
-
-        let (label_current, text_view_current) = create_section(&t!("gui.current_state"));
-        let (label_undo, text_view_undo) = create_section(&t!("gui.undo_stack"));
-        let (label_redo, text_view_redo) = create_section(&t!("gui.redo_stack"));
+        let list_box = ListBox::new();
+        list_box.set_selection_mode(SelectionMode::None);
+        list_box.set_activate_on_single_click(true);
+        list_box.add_css_class("boxed-list");
 
-        content_box.append(&label_current);
-        content_box.append(&text_view_current);
-        content_box.append(&label_undo);
-        content_box.append(&text_view_undo);
-        content_box.append(&label_redo);
-        content_box.append(&text_view_redo);
-
-        scrolled_window.set_child(Some(&content_box));
+        scrolled_window.set_child(Some(&list_box));
         main_box.append(&scrolled_window);
 
         let button_box = Box::new(Orientation::Horizontal, 8);
@@ -1249,10 +2620,12 @@ along with this program; if not, see
         let button_reset_undo = Button::with_label(&t!("gui.reset_undo_stack"));
         let button_reset_redo = Button::with_label(&t!("gui.reset_redo_stack"));
         let button_reset_all = Button::with_label(&t!("gui.reset_all"));
+        let button_drafts = Button::with_label(&t!("gui.drafts"));
         let button_close = Button::with_label(&t!("gui.close"));
 
         button_reset_all.add_css_class("destructive-action");
 
+        button_box.append(&button_drafts);
         button_box.append(&button_reset_current);
         button_box.append(&button_reset_undo);
         button_box.append(&button_reset_redo);
@@ -1262,91 +2635,106 @@ along with this program; if not, see
 
         window.set_child(Some(&main_box));
 
-        let history_clone = Rc::clone(history);
-        let text_view_current_clone = text_view_current.clone();
-        let text_view_undo_clone = text_view_undo.clone();
-        let text_view_redo_clone = text_view_redo.clone();
-        let update_views = Rc::new(move || {
-            let history = history_clone.borrow();
+        let entries: Rc<RefCell<Vec<TimelineEntry>>> = Rc::new(RefCell::new(Vec::new()));
+
+        let entries_clone = Rc::clone(&entries);
+        let category_dropdown_clone = category_dropdown.clone();
+        list_box.set_filter_func(move |row| {
+            let Some(category) = category_dropdown_clone
+                .selected_item()
+                .and_downcast::<StringObject>()
+                .filter(|_| category_dropdown_clone.selected() > 0)
+            else {
+                return true;
+            };
 
-            if history.get_current_state().is_empty() {
-                text_view_current_clone.buffer().set_text(&t!("gui.empty"));
-            } else {
-                let mut state_lines: Vec<String> = history
-                    .get_current_state()
-                    .iter()
-                    .map(|((category, key), value)| format!("{}:{} = {}", category, key, value))
-                    .collect();
-                state_lines.sort();
-                text_view_current_clone
-                    .buffer()
-                    .set_text(&state_lines.join("\n"));
+            entries_clone
+                .borrow()
+                .get(row.index() as usize)
+                .is_none_or(|entry| {
+                    entry.categories.is_empty()
+                        || entry
+                            .categories
+                            .iter()
+                            .any(|c| c.as_str() == category.string().as_str())
+                })
+        });
+
+        let list_box_clone = list_box.clone();
+        category_dropdown.connect_selected_notify(move |_| list_box_clone.invalidate_filter());
+
+        let refresh = Rc::new({
+            let gui = Rc::clone(&gui);
+            let list_box = list_box.clone();
+            let category_dropdown = category_dropdown.clone();
+            let entries = Rc::clone(&entries);
+            move || {
+                Self::fill_history_timeline(&gui, &list_box, &category_dropdown, &entries);
             }
+        });
 
-            let format_change = |change: &ConfigChange| {
-                let format_opt = |v: &Option<String>| v.clone().unwrap_or("—".to_string());
-                format!(
-                    "{}:{}\n{}: {}\n{}: {}",
-                    change.category,
-                    change.key,
-                    t!("gui.old"),
-                    format_opt(&change.old_value),
-                    t!("gui.new"),
-                    format_opt(&change.new_value)
-                )
+        refresh();
+
+        let gui_clone = Rc::clone(&gui);
+        let entries_clone = Rc::clone(&entries);
+        let refresh_clone = Rc::clone(&refresh);
+        list_box.connect_row_activated(move |_, row| {
+            let Some(position) = entries_clone
+                .borrow()
+                .get(row.index() as usize)
+                .map(|entry| entry.position)
+            else {
+                return;
             };
 
-            if history.get_undo_stack().is_empty() {
-                text_view_undo_clone.buffer().set_text(&t!("gui.empty"));
-            } else {
-                let undo_lines: Vec<String> =
-                    history.get_undo_stack().iter().map(format_change).collect();
-                text_view_undo_clone
-                    .buffer()
-                    .set_text(&undo_lines.join("\n\n"));
-            }
+            let history = Rc::clone(&gui_clone.borrow().history);
+            let stepped = history.borrow_mut().jump_to(position);
+            gui_clone.borrow().apply_history_steps_to_ui(&stepped);
 
-            if history.get_redo_stack().is_empty() {
-                text_view_redo_clone.buffer().set_text(&t!("gui.empty"));
-            } else {
-                let redo_lines: Vec<String> =
-                    history.get_redo_stack().iter().map(format_change).collect();
-                text_view_redo_clone
-                    .buffer()
-                    .set_text(&redo_lines.join("\n\n"));
-            }
+            refresh_clone();
         });
 
-        update_views();
-
-        let update_views_clone = Rc::clone(&update_views);
+        let refresh_clone = Rc::clone(&refresh);
         let gui_clone = Rc::clone(&gui);
         button_reset_current.connect_clicked(move |_| {
             gui_clone.borrow_mut().reload_ui(true);
-            update_views_clone();
+            refresh_clone();
         });
 
-        let history_clone = Rc::clone(history);
-        let update_views_clone = Rc::clone(&update_views);
+        let history_clone = Rc::clone(&history);
+        let refresh_clone = Rc::clone(&refresh);
         button_reset_undo.connect_clicked(move |_| {
             history_clone.borrow_mut().clear_undo_stack();
-            update_views_clone();
+            refresh_clone();
         });
 
-        let history_clone = Rc::clone(history);
-        let update_views_clone = Rc::clone(&update_views);
+        let history_clone = Rc::clone(&history);
+        let refresh_clone = Rc::clone(&refresh);
         button_reset_redo.connect_clicked(move |_| {
             history_clone.borrow_mut().clear_redo_stack();
-            update_views_clone();
+            refresh_clone();
         });
 
-        let history_clone = Rc::clone(history);
+        let history_clone = Rc::clone(&history);
         let gui_clone = Rc::clone(&gui);
-        let update_views_clone = Rc::clone(&update_views);
+        let refresh_clone = Rc::clone(&refresh);
         button_reset_all.connect_clicked(move |_| {
             history_clone.borrow_mut().clear_stacks();
             gui_clone.borrow_mut().reload_ui(true);
-            update_views_clone();
+            refresh_clone();
+        });
+
+        let gui_clone = Rc::clone(&gui);
+        let window_clone = window.clone();
+        button_drafts.connect_clicked(move |_| {
+            let profile = gui_clone.borrow().history.borrow().profile().to_string();
+            let gui = Rc::clone(&gui_clone);
+            let window = window_clone.clone();
+            show_drafts_list(&window_clone, &profile, move |draft| {
+                // The timeline would be stale after applying the draft.
+                window.close();
+                Self::show_draft(Rc::clone(&gui), draft);
+            });
         });
 
         let window_clone = window.clone();
@@ -1357,8 +2745,498 @@ along with this program; if not, see
         window.present();
     }
 
+    /// Offers the pending changes that could not be restored on startup or profile switch.
+    pub fn offer_recovered_draft(gui: Rc<RefCell<ConfigGUI>>) {
+        let draft = gui.borrow().history.borrow_mut().take_recovered_draft();
+        if let Some(draft) = draft {
+            Self::show_draft(gui, draft);
+        }
+    }
+
+    fn show_draft(gui: Rc<RefCell<ConfigGUI>>, draft: Draft) {
+        let window = gui.borrow().window.clone();
+        // Read from disk, since the initial values of pages not opened yet are unknown.
+        let disk_config =
+            transform_config(expand_source(&get_config_path(false, "Default")).unwrap_or_default());
+
+        show_draft_dialog(
+            &window,
+            draft,
+            move |category, key| {
+                let row = ["_name", "_value"]
+                    .iter()
+                    .find_map(|suffix| Some((key.strip_suffix(suffix)?, *suffix)));
+                match row {
+                    // Rows are keyed by their line on disk, new rows by a counter.
+                    Some((raw, suffix)) => {
+                        let line = split_origin_raw(raw).map_or(raw, |(_, line)| line);
+                        let (name, value) = line.split_once('=')?;
+                        let part = if suffix == "_name" { name } else { value };
+                        Some(part.trim().to_string())
+                    }
+                    None => extract_value(&disk_config, category, key),
+                }
+            },
+            move |draft| {
+                let history = Rc::clone(&gui.borrow().history);
+                history.borrow_mut().begin_transaction(draft_title(draft));
+
+                let mut changes: Vec<_> = draft.changes.iter().collect();
+                changes.sort();
+                for ((category, key), value) in changes {
+                    gui.borrow().apply_value_to_ui(category, key, value);
+                }
+
+                history.borrow_mut().commit_transaction();
+            },
+        );
+    }
+
+    /// Watches every file of the expanded config, so edits made outside of hyprviz are
+    /// noticed. Call again after a reload, since the set of sourced files may have changed.
+    pub fn watch_config_files(gui: Rc<RefCell<ConfigGUI>>) {
+        fn collect_paths(node: &IncludeNode, paths: &mut BTreeSet<PathBuf>) {
+            if node.error.is_none() {
+                paths.insert(node.path.clone());
+            }
+            for child in &node.children {
+                collect_paths(child, paths);
+            }
+        }
+
+        let config_path = get_config_path(false, "Default");
+        let mut paths = BTreeSet::new();
+        collect_paths(&build_include_graph(&config_path), &mut paths);
+        paths.insert(get_config_path(true, &gui.borrow().selected_profile()));
+
+        let this = gui.borrow();
+        match expand_source(&config_path) {
+            Ok(config) => *this.config_snapshot.borrow_mut() = config,
+            Err(e) => eprintln!("Failed to expand sources: {}", e),
+        }
+
+        let mut monitors = this.file_monitors.borrow_mut();
+        for monitor in monitors.drain(..) {
+            monitor.cancel();
+        }
+
+        for path in paths {
+            let monitor = match gio::File::for_path(&path)
+                .monitor_file(gio::FileMonitorFlags::WATCH_MOVES, gio::Cancellable::NONE)
+            {
+                Ok(monitor) => monitor,
+                Err(e) => {
+                    eprintln!("Failed to watch {:?}: {}", path, e);
+                    continue;
+                }
+            };
+
+            let gui = Rc::clone(&gui);
+            monitor.connect_changed(move |_, _, _, event| {
+                if matches!(
+                    event,
+                    gio::FileMonitorEvent::ChangesDoneHint
+                        | gio::FileMonitorEvent::Created
+                        | gio::FileMonitorEvent::Deleted
+                        | gio::FileMonitorEvent::Renamed
+                        | gio::FileMonitorEvent::MovedIn
+                ) {
+                    gui.borrow().changed_files.borrow_mut().insert(path.clone());
+                    Self::schedule_external_change_check(&gui);
+                }
+            });
+            monitors.push(monitor);
+        }
+    }
+
+    /// Rebuilds the UI from the files on disk, keeping pending changes, and makes the loaded
+    /// config the new base for detecting external edits.
+    fn reload_from_disk(gui: &Rc<RefCell<ConfigGUI>>) {
+        gui.borrow_mut().reload_ui(false);
+        match expand_source(&get_config_path(false, "Default")) {
+            Ok(config) => gui
+                .borrow()
+                .history
+                .borrow_mut()
+                .set_initial_config_hash(&config),
+            Err(e) => eprintln!("Failed to expand sources: {}", e),
+        }
+        Self::watch_config_files(Rc::clone(gui));
+    }
+
+    /// Editors often write a file in several steps; wait for them to settle before comparing.
+    fn schedule_external_change_check(gui: &Rc<RefCell<ConfigGUI>>) {
+        if gui.borrow().external_check_scheduled.replace(true) {
+            return;
+        }
+
+        let gui = Rc::clone(gui);
+        glib::timeout_add_local_once(Duration::from_millis(300), move || {
+            gui.borrow().external_check_scheduled.set(false);
+            Self::check_external_changes(gui);
+        });
+    }
+
+    fn check_external_changes(gui: Rc<RefCell<ConfigGUI>>) {
+        let this = gui.borrow();
+        if this.external_dialog_open.get() {
+            return;
+        }
+
+        // The main config was pointed to another profile outside of hyprviz, e.g. by
+        // `hyprviz --daemon`; follow it like a switch made from the profile list.
+        let sourced_profile = fs::read_to_string(get_config_path(false, "Default"))
+            .map(|content| get_current_profile(&content))
+            .ok();
+        if let Some(profile) = sourced_profile
+            && profile != this.history.borrow().profile()
+            && get_config_path(true, &profile).exists()
+        {
+            this.changed_files.borrow_mut().clear();
+            this.refresh_profile_list(&profile);
+            drop(this);
+            Self::reload_from_disk(&gui);
+            gui.borrow().custom_info_popup(
+                &t!("gui.profile_switched"),
+                &t!("gui.profile_switched_to_", name = profile),
+            );
+            Self::offer_recovered_draft(gui);
+            return;
+        }
+
+        let current = match expand_source(&get_config_path(false, "Default")) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Failed to expand sources: {}", e);
+                return;
+            }
+        };
+
+        // Saves made by hyprviz update the snapshot, so they end here.
+        let snapshot = this.config_snapshot.borrow().clone();
+        if current == snapshot {
+            this.changed_files.borrow_mut().clear();
+            return;
+        }
+
+        let changed_files: Vec<String> = this
+            .changed_files
+            .take()
+            .iter()
+            .map(|path| display_path(path))
+            .collect();
+
+        let has_pending_changes = !this.history.borrow().get_current_state().is_empty();
+        this.external_dialog_open.set(true);
+
+        if !has_pending_changes {
+            let gui_confirm = Rc::clone(&gui);
+            let gui_cancel = Rc::clone(&gui);
+            this.show_confirmation_dialog(
+                &t!("gui.config_changed_on_disk"),
+                &t!(
+                    "gui.config_changed_on_disk_reload_",
+                    files = changed_files.join(", ")
+                ),
+                move || {
+                    gui_confirm.borrow().external_dialog_open.set(false);
+                    Self::reload_from_disk(&gui_confirm);
+                },
+                move || {
+                    gui_cancel.borrow().external_dialog_open.set(false);
+                },
+            );
+            return;
+        }
+
+        let entries = this.merge_entries(&snapshot, &current);
+        let gui_merge = Rc::clone(&gui);
+        let gui_later = Rc::clone(&gui);
+        show_merge_dialog(
+            &this.window,
+            &changed_files,
+            entries,
+            move |dropped| {
+                gui_merge.borrow().external_dialog_open.set(false);
+                gui_merge
+                    .borrow()
+                    .history
+                    .borrow_mut()
+                    .drop_pending_changes(&dropped);
+                Self::reload_from_disk(&gui_merge);
+            },
+            move || {
+                gui_later.borrow().external_dialog_open.set(false);
+            },
+        );
+    }
+
+    /// Pairs every pending change with its value in `base` (the config as hyprviz loaded it)
+    /// and in `disk` (the config now).
+    fn merge_entries(&self, base: &str, disk: &str) -> Vec<MergeEntry> {
+        let base_config = transform_config(base.to_string());
+        let disk_config = transform_config(disk.to_string());
+        let profile_path = get_config_path(true, &self.selected_profile());
+        let history = self.history.borrow();
+
+        let mut file_texts: HashMap<PathBuf, ConfigDocument> = HashMap::new();
+        let mut line_exists = |path: PathBuf, line: &str| {
+            file_texts
+                .entry(path)
+                .or_insert_with_key(|path| {
+                    ConfigDocument::parse(&fs::read_to_string(path).unwrap_or_default())
+                })
+                .find_line(line)
+                .is_some()
+        };
+
+        let mut changes: Vec<_> = history.get_current_state().iter().collect();
+        changes.sort();
+
+        changes
+            .into_iter()
+            .map(|((category, key), pending)| {
+                let row = ["_name", "_value", "_delete"]
+                    .iter()
+                    .find_map(|suffix| Some((key.strip_suffix(suffix)?, *suffix)));
+
+                let (label, base, disk) = match row {
+                    None => (
+                        option_path(category, key),
+                        extract_value(&base_config, category, key),
+                        extract_value(&disk_config, category, key),
+                    ),
+                    Some((raw, suffix)) => {
+                        let (path, line) = match split_origin_raw(raw) {
+                            Some((path, line)) => (path, line.to_string()),
+                            None => (profile_path.clone(), raw.to_string()),
+                        };
+                        let base = history.get_initial_value(category, key);
+                        let disk = if raw.parse::<u64>().is_ok() || line_exists(path, &line) {
+                            // New rows and untouched lines merge cleanly.
+                            base.clone()
+                        } else if suffix == "_delete" {
+                            Some(pending.clone())
+                        } else {
+                            None
+                        };
+                        (line, base, disk)
+                    }
+                };
+
+                MergeEntry {
+                    category: category.clone(),
+                    key: key.clone(),
+                    label,
+                    base,
+                    disk,
+                    pending: pending.clone(),
+                }
+            })
+            .collect()
+    }
+
+    fn fill_history_timeline(
+        gui: &Rc<RefCell<ConfigGUI>>,
+        list_box: &ListBox,
+        category_dropdown: &DropDown,
+        entries: &Rc<RefCell<Vec<TimelineEntry>>>,
+    ) {
+        let history = Rc::clone(&gui.borrow().history);
+        let history = history.borrow();
+        let undo_stack = history.get_undo_stack();
+        let redo_stack = history.get_redo_stack();
+        let current_position = undo_stack.len();
+
+        // The redo stack is popped from the back, so its last change is the next one in time.
+        let changes: Vec<&ConfigChange> =
+            undo_stack.iter().chain(redo_stack.iter().rev()).collect();
+
+        let mut categories: Vec<&str> = changes.iter().map(|c| c.category.as_str()).collect();
+        categories.sort();
+        categories.dedup();
+
+        let selected_category = category_dropdown
+            .selected_item()
+            .and_downcast::<StringObject>()
+            .filter(|_| category_dropdown.selected() > 0)
+            .map(|category| category.string().to_string());
+
+        let all_categories = t!("gui.all_categories");
+        let mut category_items = vec![&*all_categories];
+        category_items.extend(categories.iter().copied());
+        category_dropdown.set_model(Some(&StringList::new(&category_items)));
+        category_dropdown.set_selected(
+            selected_category
+                .and_then(|selected| categories.iter().position(|c| *c == selected))
+                .map_or(0, |pos| pos as u32 + 1),
+        );
+
+        // Consecutive changes of one transaction are shown as a single entry.
+        let mut units: Vec<std::ops::Range<usize>> = Vec::new();
+        for (i, change) in changes.iter().enumerate() {
+            match units.last_mut() {
+                Some(unit) if changes[unit.start].is_same_unit(change) => unit.end = i + 1,
+                _ => units.push(i..i + 1),
+            }
+        }
+
+        *entries.borrow_mut() = std::iter::once(TimelineEntry {
+            position: 0,
+            categories: Vec::new(),
+        })
+        .chain(units.iter().map(|unit| {
+            let mut categories: Vec<String> = changes[unit.clone()]
+                .iter()
+                .map(|change| change.category.clone())
+                .collect();
+            categories.sort();
+            categories.dedup();
+
+            TimelineEntry {
+                position: unit.end,
+                categories,
+            }
+        }))
+        .collect();
+
+        list_box.remove_all();
+
+        let initial_row = Self::create_timeline_row(
+            &t!("gui.initial_state"),
+            &t!("gui.initial_state_description"),
+            None,
+            current_position == 0,
+        );
+        list_box.append(&initial_row);
+
+        let format_opt = |v: &Option<String>| v.clone().unwrap_or("—".to_string());
+
+        for unit in units {
+            let first_index = unit.start;
+            let position = unit.end;
+            let unit_changes = &changes[unit];
+            let last_change = unit_changes[unit_changes.len() - 1];
+
+            let row = match &last_change.group {
+                Some(group) => Self::create_timeline_row(
+                    &group.label,
+                    &t!(
+                        "gui.grouped_changes_",
+                        count = unit_changes.len(),
+                        keys = unit_changes
+                            .iter()
+                            .map(|change| format!("{}:{}", change.category, change.key))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    Some(last_change.timestamp),
+                    position == current_position,
+                ),
+                None => Self::create_timeline_row(
+                    &format!("{}:{}", last_change.category, last_change.key),
+                    &format!(
+                        "{} → {}",
+                        format_opt(&last_change.old_value),
+                        format_opt(&last_change.new_value)
+                    ),
+                    Some(last_change.timestamp),
+                    position == current_position,
+                ),
+            };
+
+            if position > current_position {
+                row.add_css_class("dim-label");
+                row.set_tooltip_text(Some(&t!("gui.undone_change")));
+            } else if let Some(row_box) = row.child().and_downcast::<Box>() {
+                let revert_button = Button::from_icon_name("edit-undo-symbolic");
+                revert_button.set_valign(gtk::Align::Center);
+                revert_button.set_tooltip_text(Some(&t!("gui.revert_this_change")));
+                revert_button.add_css_class("flat");
+
+                let gui_clone = Rc::clone(gui);
+                let list_box_clone = list_box.clone();
+                let category_dropdown_clone = category_dropdown.clone();
+                let entries_clone = Rc::clone(entries);
+                revert_button.connect_clicked(move |_| {
+                    let history = Rc::clone(&gui_clone.borrow().history);
+                    let reverted = history.borrow_mut().revert_change(first_index);
+                    let steps: Vec<(ConfigChange, bool)> =
+                        reverted.into_iter().map(|change| (change, false)).collect();
+                    gui_clone.borrow().apply_history_steps_to_ui(&steps);
+
+                    Self::fill_history_timeline(
+                        &gui_clone,
+                        &list_box_clone,
+                        &category_dropdown_clone,
+                        &entries_clone,
+                    );
+                });
+
+                row_box.append(&revert_button);
+            }
+
+            list_box.append(&row);
+        }
+
+        list_box.invalidate_filter();
+    }
+
+    fn create_timeline_row(
+        title: &str,
+        subtitle: &str,
+        timestamp: Option<u64>,
+        is_current: bool,
+    ) -> ListBoxRow {
+        let row_box = Box::new(Orientation::Horizontal, 8);
+        row_box.set_margin_start(MARGIN_NORMAL / 2);
+        row_box.set_margin_end(MARGIN_NORMAL / 2);
+        row_box.set_margin_top(MARGIN_NORMAL / 2);
+        row_box.set_margin_bottom(MARGIN_NORMAL / 2);
+
+        let text_box = Box::new(Orientation::Vertical, 2);
+        text_box.set_hexpand(true);
+
+        let title_label = Label::new(Some(title));
+        title_label.set_halign(gtk::Align::Start);
+        title_label.set_ellipsize(gtk::pango::EllipsizeMode::Middle);
+        title_label.add_css_class("heading");
+
+        let subtitle_label = Label::new(Some(subtitle));
+        subtitle_label.set_halign(gtk::Align::Start);
+        subtitle_label.set_wrap(true);
+        subtitle_label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
+        subtitle_label.set_xalign(0.0);
+        subtitle_label.add_css_class("monospace");
+
+        text_box.append(&title_label);
+        text_box.append(&subtitle_label);
+
+        if let Some(timestamp) = timestamp {
+            let time_label = Label::new(Some(&format_change_time(timestamp)));
+            time_label.set_halign(gtk::Align::Start);
+            time_label.add_css_class("dim-label");
+            time_label.add_css_class("caption");
+            text_box.append(&time_label);
+        }
+
+        row_box.append(&text_box);
+
+        if is_current {
+            let current_label = Label::new(Some(&t!("gui.current_position")));
+            current_label.set_valign(gtk::Align::Center);
+            current_label.add_css_class("accent");
+            row_box.append(&current_label);
+        }
+
+        let row = ListBoxRow::new();
+        row.set_child(Some(&row_box));
+        row
+    }
+
     pub fn load_config(&mut self, config: HyprlandConfig, profile_name: &str) {
         self.config_widgets.borrow_mut().clear();
+        self.search_index.borrow_mut().take();
         self.content_box.set_visible(true);
 
         while let Some(child) = self.stack.first_child() {
@@ -1385,36 +3263,7 @@ along with this program; if not, see
             }
         });
 
-        let mut categories = vec![
-            (t!("gui.general").to_string(), "general"),
-            (t!("gui.decoration").to_string(), "decoration"),
-            (t!("gui.animations_settings").to_string(), "animations"),
-            (t!("gui.input").to_string(), "input"),
-            (t!("gui.gestures_settings").to_string(), "gestures"),
-            (t!("gui.misc").to_string(), "misc"),
-            (t!("gui.bind_settings").to_string(), "binds"),
-            (t!("gui.group").to_string(), "group"),
-            (t!("gui.layouts").to_string(), "layouts"),
-            ("XWayland".to_string(), "xwayland"),
-            ("OpenGL".to_string(), "opengl"),
-            (t!("gui.render").to_string(), "render"),
-            (t!("gui.cursor").to_string(), "cursor"),
-            (t!("gui.ecosystem").to_string(), "ecosystem"),
-            (t!("gui.quirks").to_string(), "quirks"),
-            (t!("gui.debug").to_string(), "debug"),
-            (t!("gui.monitors").to_string(), "monitor"),
-            (t!("gui.workspaces").to_string(), "workspace"),
-            (t!("gui.animations").to_string(), "animation"),
-            (t!("gui.binds").to_string(), "bind"),
-            (t!("gui.gestures").to_string(), "gesture"),
-            (t!("gui.window_rules").to_string(), "windowrule"),
-            (t!("gui.layer_rules").to_string(), "layerrule"),
-            (t!("gui.execs").to_string(), "exec"),
-            (t!("gui.permissions").to_string(), "permission"),
-            (t!("gui.envs").to_string(), "env"),
-            (t!("gui.all_top_level").to_string(), "top_level"),
-            (t!("gui.system_info").to_string(), "systeminfo"),
-        ];
+        let mut categories = category_list();
 
         if is_development_mode() {
             categories.push((t!("gui.togtkbox_test").to_string(), "togtkbox_test"));
@@ -1444,6 +3293,7 @@ along with this program; if not, see
 
         let config_rc = Rc::new(RefCell::new(config));
         let base_config_rc = Rc::new(RefCell::new(mute_stdout(|| parse_config(&base_config_str))));
+        let option_origins = Rc::new(option_origins(&get_config_path(false, "Default")));
 
         for (_, category) in &categories {
             if let Some(widget) = self.config_widgets.borrow_mut().get_mut(*category) {
@@ -1454,16 +3304,31 @@ along with this program; if not, see
                     profile_name.to_string(),
                     self.history.clone(),
                     self.top_level_rows.clone(),
+                    option_origins.clone(),
                 );
             }
         }
 
+        // Pages rendered on demand are filtered as they appear.
         let widgets = Rc::clone(&self.config_widgets);
+        let history = Rc::clone(&self.history);
+        let filtered_out = Rc::clone(&self.filtered_out);
+        let view_filter_dropdown = self.view_filter_dropdown.clone();
         self.stack.connect_visible_child_notify(move |stack| {
             if let Some(name) = stack.visible_child_name()
                 && let Some(widget) = widgets.borrow_mut().get_mut(name.as_str())
             {
+                let was_rendered = widget.is_rendered;
                 widget.render();
+                if !was_rendered {
+                    filter_page(
+                        stack,
+                        widget,
+                        selected_view_filter(&view_filter_dropdown),
+                        &history.borrow(),
+                        &mut filtered_out.borrow_mut(),
+                    );
+                }
             }
         });
 
@@ -1475,11 +3340,48 @@ along with this program; if not, see
         {
             widget.render();
         }
+
+        self.apply_view_filter(false);
     }
 
-    pub fn apply_changes(&self, config: &mut HyprlandConfig) {
+    /// Applies the view filter to every rendered page. With `render_pages`, which is meant for
+    /// when the user picks a filter, the other pages are rendered first so every category gets
+    /// a count; otherwise they are filtered when they are first shown.
+    fn apply_view_filter(&self, render_pages: bool) {
+        let filter = selected_view_filter(&self.view_filter_dropdown);
+
+        for widget in self.filtered_out.borrow_mut().drain(..) {
+            widget.set_visible(true);
+        }
+
+        if render_pages && filter != ViewFilter::All {
+            let categories: Vec<String> = self
+                .config_widgets
+                .borrow()
+                .iter()
+                .filter(|(_, widget)| !widget.is_rendered)
+                .map(|(category, _)| category.clone())
+                .collect();
+            for category in categories {
+                self.rendered_widget(&category, |_| ());
+            }
+        }
+
+        // Borrowed after rendering, which records the initial values.
         let history = self.history.borrow();
-        let changes = history.get_current_state();
+        let mut filtered_out = self.filtered_out.borrow_mut();
+        for widget in self.config_widgets.borrow().values() {
+            filter_page(&self.stack, widget, filter, &history, &mut filtered_out);
+        }
+    }
+
+    /// Writes `changes` into `document`, touching only the lines they affect. Returns the
+    /// `(category, old raw, new raw)` of every top-level row whose line changed.
+    pub fn apply_changes(
+        &self,
+        document: &mut ConfigDocument,
+        changes: &HashMap<(String, String), String>,
+    ) -> Vec<(String, String, String)> {
         for (category, widget) in self.config_widgets.borrow().iter() {
             for (name, widget_data) in &widget.options {
                 let widget = &widget_data.widget;
@@ -1503,122 +3405,69 @@ along with this program; if not, see
                             value.clone()
                         };
 
-                    if !formatted_value.is_empty() {
-                        if category == "layouts" {
-                            let parts: Vec<&str> = name.split(':').collect();
-                            if parts.len() == 2 {
-                                config.add_entry(
-                                    parts[0],
-                                    &format!("{} = {}", parts[1], formatted_value),
-                                );
-                            }
-                        } else if name.contains(':') {
-                            let parts: Vec<&str> = name.split(':').collect();
-                            if parts.len() == 2 {
-                                config.add_entry(
-                                    &format!("{}.{}", category, parts[0]),
-                                    &format!("{} = {}", parts[1], formatted_value),
-                                );
-                            }
-                        } else {
-                            config.add_entry(category, &format!("{name} = {formatted_value}"));
-                        }
+                    if formatted_value.is_empty() {
+                        continue;
                     }
+
+                    document.set_option(&option_path(category, name), &formatted_value);
                 }
             }
         }
 
-        let mut names: HashMap<String, String> = HashMap::new();
-        let mut values: HashMap<String, String> = HashMap::new();
-        let mut delete: Vec<String> = Vec::new();
-        for ((_, raw), new) in changes.iter() {
-            if raw.ends_with("_name") {
-                let formatted_raw = raw.strip_suffix("_name").unwrap().to_string();
-                names.insert(formatted_raw.clone(), new.clone());
-            }
-
-            if raw.ends_with("_value") {
-                let formatted_raw = raw.strip_suffix("_value").unwrap().to_string();
-                values.insert(formatted_raw.clone(), new.clone());
-            }
-
-            if raw.ends_with("_delete") {
-                let formatted_raw = raw.strip_suffix("_delete").unwrap().to_string();
-                delete.push(formatted_raw.clone());
+        let mut names: HashMap<(&str, &str), &str> = HashMap::new();
+        let mut values: HashMap<(&str, &str), &str> = HashMap::new();
+        let mut delete: Vec<(&str, &str)> = Vec::new();
+        for ((category, key), new) in changes.iter() {
+            if let Some(raw) = key.strip_suffix("_name") {
+                names.insert((category, raw), new);
+            } else if let Some(raw) = key.strip_suffix("_value") {
+                values.insert((category, raw), new);
+            } else if let Some(raw) = key.strip_suffix("_delete") {
+                delete.push((category, raw));
             }
         }
 
-        let mut lines: Vec<String> = config.to_string().lines().map(String::from).collect();
+        // Rows edited in place carry their file in the raw id; only the line is looked up.
+        let line_of =
+            |raw: &str| split_origin_raw(raw).map_or(raw.to_string(), |(_, line)| line.to_string());
 
-        let mut delete_tracker = HashMap::new();
-
-        for raw in &delete {
-            *delete_tracker.entry(raw.clone()).or_insert(0) += 1;
+        for (_, raw) in &delete {
+            document.remove_line(&line_of(raw));
         }
 
-        lines.retain(|line| {
-            let normalized_line = line.trim_start().to_string();
-
-            if let Some(count) = delete_tracker.get_mut(&normalized_line)
-                && *count > 0
-            {
-                *count -= 1;
-                return false;
-            }
-
-            true
-        });
-
-        let mut changed: Vec<String> = Vec::new();
-
-        for ((_, name), _) in changes.iter() {
-            if name.ends_with("_name") || name.ends_with("_value") {
-                let key = name
-                    .strip_suffix("_name")
-                    .or_else(|| name.strip_suffix("_value"))
-                    .unwrap_or(name);
-                if changed.contains(&key.to_string()) {
-                    continue;
-                }
-
-                let has_name = names.contains_key(key);
-                let has_value = values.contains_key(key);
-
-                let mut new_name = names.get(key).cloned().unwrap_or_default();
-                let mut new_value = values.get(key).cloned().unwrap_or_default();
-
-                let mut found = false;
-                for line in &mut lines {
-                    if line.trim_start().starts_with(key)
-                        && let Some((original_name, original_value)) = line.split_once('=')
-                    {
-                        let indent = line
-                            .chars()
-                            .take_while(|c| c.is_whitespace())
-                            .collect::<String>();
-
-                        if !has_name {
-                            new_name = original_name.trim().to_string();
-                        }
-                        if !has_value {
-                            new_value = original_value.trim_start().to_string();
-                        }
-
-                        *line = format!("{}{} = {}", indent, new_name, new_value);
-                        found = true;
-                        break;
-                    }
+        let mut edited: Vec<(&str, &str)> = names
+            .keys()
+            .chain(values.keys())
+            .copied()
+            .filter(|row| !delete.contains(row))
+            .collect();
+        // New rows are keyed by a counter; keep them in the order they were added.
+        edited.sort_by_key(|(category, raw)| (raw.parse::<u64>().ok(), *raw, *category));
+        edited.dedup();
+
+        let mut renamed = Vec::new();
+        for row in edited {
+            let name = names.get(&row).copied();
+            let value = values.get(&row).copied();
+
+            let index = match document.find_line(&line_of(row.1)) {
+                Some(index) => {
+                    document.update_line(index, name, value);
+                    index
                 }
+                None => document.push_line(name.unwrap_or_default(), value.unwrap_or_default()),
+            };
 
-                if !found {
-                    lines.push(format!("{} = {}", new_name, new_value));
-                }
-                changed.push(key.to_string());
+            let new_raw = match split_origin_raw(row.1) {
+                Some((path, _)) => origin_raw(&path, document.line(index)),
+                None => document.line(index).to_string(),
+            };
+            if new_raw != row.1 {
+                renamed.push((row.0.to_string(), row.1.to_string(), new_raw));
             }
         }
 
-        let new_config = mute_stdout(|| parse_config(&lines.join("\n")));
-        *config = new_config;
+        renamed
     }
 
     pub fn reload_ui(&mut self, reset_unsaved_changes: bool) {
@@ -1653,6 +3502,11 @@ along with this program; if not, see
         if reset_unsaved_changes {
             self.history.borrow_mut().reset_unsaved_changes();
         }
+        if self.history.borrow().profile() != current_profile {
+            self.history
+                .borrow_mut()
+                .switch_profile(&current_profile, &config_str);
+        }
         self.load_config(parsed_config, &current_profile);
 
         if let Some(page) = current_page
@@ -1668,12 +3522,44 @@ along with this program; if not, see
 
         self.create_profile_button
             .set_label(&t!("gui.create_profile"));
+        self.rename_profile_button
+            .set_label(&t!("gui.rename_profile"));
+        self.duplicate_profile_button
+            .set_label(&t!("gui.duplicate_profile"));
+        self.profile_properties_button
+            .set_label(&t!("gui.profile_properties"));
+        self.compare_profiles_button
+            .set_label(&t!("gui.compare_profiles"));
+        self.profile_rules_button
+            .set_label(&t!("gui.automatic_switching"));
         self.delete_profile_button
             .set_label(&t!("gui.delete_profile"));
+        self.palette_button.set_label(&t!("gui.wallpaper_palette"));
+        self.include_graph_button
+            .set_label(&t!("gui.included_files"));
+        self.backups_button.set_label(&t!("gui.backups"));
+        self.git_log_button.set_label(&t!("gui.version_history"));
+        self.paste_snippet_button
+            .set_label(&t!("gui.paste_snippet"));
+        self.copy_hyprlang_button
+            .set_label(&t!("gui.copy_as_hyprlang"));
         self.load_config_button
             .set_label(&t!("gui.load_hyprviz_config"));
         self.save_config_button
             .set_label(&t!("gui.save_hyprviz_config"));
         self.copy_button.set_label(&t!("gui.copyright"));
+
+        let view_filter_labels = view_filter_labels();
+        let view_filter_labels: Vec<&str> = view_filter_labels.iter().map(String::as_str).collect();
+        let selected_filter = self.view_filter_dropdown.selected();
+        self.is_programmatic_filter.set(true);
+        self.view_filter_dropdown
+            .set_model(Some(&StringList::new(&view_filter_labels)));
+        self.view_filter_dropdown.set_selected(selected_filter);
+        self.is_programmatic_filter.set(false);
+        self.view_filter_dropdown
+            .set_tooltip_text(Some(&t!("gui.view_filter")));
+
+        self.update_profile_header();
     }
 }
@@ -59,8 +59,14 @@ pub fn create_guide(name: &str) -> Box {
 }
 
 fn get_content(name: &str) -> Vec<ContentBlock> {
+    let lines: Vec<&str> = guide_source(name).lines().collect();
+    parse_lines(&lines, name)
+}
+
+/// Markdown source of the guide `name` in the current locale.
+pub fn guide_source(name: &str) -> &'static str {
     let current_locale = locale().to_string();
-    let content = match name {
+    match name {
         "Dispatchers" => match current_locale.as_str() {
             "ru" => include_str!("../guides/ru/Dispatchers.md"),
             "zh-CN" => include_str!("../guides/zh-CN/Dispatchers.md"),
@@ -137,9 +143,7 @@ fn get_content(name: &str) -> Vec<ContentBlock> {
             _ => include_str!("../guides/en/Environment-variables.md"),
         },
         name => panic!("Invalid content name: {name}"),
-    };
-    let lines: Vec<&str> = content.lines().collect();
-    parse_lines(&lines, name)
+    }
 }
 
 fn parse_lines(lines: &[&str], guide_name: &str) -> Vec<ContentBlock> {
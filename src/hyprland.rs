@@ -49,6 +49,7 @@ pub mod key_state;
 pub mod layer_rule_effect;
 pub mod layer_rule_effect_or_prop;
 pub mod layer_rule_prop;
+pub mod live_workspace;
 pub mod modifier;
 pub mod monitor;
 pub mod monitor_selector;
@@ -132,7 +133,7 @@ pub use gesture_fullscreen::GestureFullscreen;
 pub use group_lock_action::GroupLockAction;
 pub use hypr_color::HyprColor;
 pub use hypr_coord::HyprCoord;
-pub use hypr_expression::HyprExpression;
+pub use hypr_expression::{EvaluationError, ExpressionContext, HyprExpression};
 pub use hypr_gradient::HyprGradient;
 pub use hypr_layout::HyprLayout;
 pub use hypr_opacity::HyprOpacity;
@@ -146,6 +147,7 @@ pub use key_state::KeyState;
 pub use layer_rule_effect::LayerRuleEffect;
 pub use layer_rule_effect_or_prop::LayerRuleEffectOrProp;
 pub use layer_rule_prop::LayerRuleProp;
+pub use live_workspace::LiveWorkspace;
 pub use modifier::Modifier;
 pub use monitor::Monitor;
 pub use monitor_selector::MonitorSelector;
@@ -20,6 +20,20 @@ pub enum CssGaps {
     TopRightBottomLeft(u32, u32, u32, u32),
 }
 
+impl CssGaps {
+    /// Returns the gaps as `(top, right, bottom, left)`, following CSS shorthand rules.
+    pub fn to_sides(self) -> (u32, u32, u32, u32) {
+        match self {
+            CssGaps::All(gap) => (gap, gap, gap, gap),
+            CssGaps::VerticalHorizontal(vertical, horizontal) => {
+                (vertical, horizontal, vertical, horizontal)
+            }
+            CssGaps::TopSidesBottom(top, sides, bottom) => (top, sides, bottom, sides),
+            CssGaps::TopRightBottomLeft(top, right, bottom, left) => (top, right, bottom, left),
+        }
+    }
+}
+
 impl HasDiscriminant for CssGaps {
     type Discriminant = CssGapsDiscriminant;
 
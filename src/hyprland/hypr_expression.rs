@@ -22,6 +22,64 @@ pub enum HyprExpression {
     Formula(Box<HyprExpression>, Operator, Box<HyprExpression>),
 }
 
+/// Values substituted for [`HyprVariable`]s when evaluating an expression.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ExpressionContext {
+    pub monitor_w: f64,
+    pub monitor_h: f64,
+    pub window_x: f64,
+    pub window_y: f64,
+    pub window_w: f64,
+    pub window_h: f64,
+    pub cursor_x: f64,
+    pub cursor_y: f64,
+}
+
+impl ExpressionContext {
+    pub fn get(&self, variable: HyprVariable) -> f64 {
+        match variable {
+            HyprVariable::MonitorW => self.monitor_w,
+            HyprVariable::MonitorH => self.monitor_h,
+            HyprVariable::WindowX => self.window_x,
+            HyprVariable::WindowY => self.window_y,
+            HyprVariable::WindowW => self.window_w,
+            HyprVariable::WindowH => self.window_h,
+            HyprVariable::CursorX => self.cursor_x,
+            HyprVariable::CursorY => self.cursor_y,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvaluationError {
+    /// The divisor of the contained formula evaluated to zero.
+    DivisionByZero(String),
+}
+
+impl HyprExpression {
+    pub fn evaluate(&self, context: &ExpressionContext) -> Result<f64, EvaluationError> {
+        match self {
+            HyprExpression::Float(v) => Ok(*v),
+            HyprExpression::Uint(v) => Ok(*v as f64),
+            HyprExpression::Variable(v) => Ok(context.get(*v)),
+            HyprExpression::Formula(left, op, right) => {
+                let left_value = left.evaluate(context)?;
+                let right_value = right.evaluate(context)?;
+
+                match op {
+                    Operator::Add => Ok(left_value + right_value),
+                    Operator::Subtract => Ok(left_value - right_value),
+                    Operator::Multiply => Ok(left_value * right_value),
+                    Operator::Divide if right_value == 0.0 => {
+                        Err(EvaluationError::DivisionByZero(self.to_string()))
+                    }
+                    Operator::Divide => Ok(left_value / right_value),
+                }
+            }
+        }
+    }
+}
+
 impl HasDiscriminant for HyprExpression {
     type Discriminant = HyprExpressionDiscriminant;
 
@@ -285,3 +343,61 @@ register_togtkbox_with_separator_names!(
     (HyprVariable,),
     (HyprExpression, Operator, HyprExpression)
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(expression: &str, context: &ExpressionContext) -> Result<f64, EvaluationError> {
+        HyprExpression::from_str(expression)
+            .expect("expression parses")
+            .evaluate(context)
+    }
+
+    #[test]
+    fn multiplication_binds_tighter_than_addition() {
+        let context = ExpressionContext::default();
+        assert_eq!(evaluate("2*3+4", &context), Ok(10.0));
+        assert_eq!(evaluate("4+2*3", &context), Ok(10.0));
+        assert_eq!(evaluate("(4+2)*3", &context), Ok(18.0));
+        assert_eq!(evaluate("1.5*2", &context), Ok(3.0));
+    }
+
+    #[test]
+    fn operators_of_one_level_are_left_associative() {
+        let context = ExpressionContext::default();
+        assert_eq!(evaluate("10-4-3", &context), Ok(3.0));
+        assert_eq!(evaluate("24/4/2", &context), Ok(3.0));
+    }
+
+    #[test]
+    fn variables_come_from_the_context() {
+        let context = ExpressionContext {
+            monitor_w: 1920.0,
+            window_w: 800.0,
+            ..Default::default()
+        };
+        assert_eq!(evaluate("monitor_w-window_w/2", &context), Ok(1520.0));
+        assert_eq!(evaluate("(monitor_w-window_w)/2", &context), Ok(560.0));
+    }
+
+    #[test]
+    fn division_by_zero_is_an_error() {
+        let context = ExpressionContext::default();
+        assert!(matches!(
+            evaluate("100/0", &context),
+            Err(EvaluationError::DivisionByZero(_))
+        ));
+        // A divisor that only evaluates to zero fails too, naming the formula.
+        assert_eq!(
+            evaluate("100/window_h", &context),
+            Err(EvaluationError::DivisionByZero(
+                "(100/window_h)".to_string()
+            ))
+        );
+        assert!(matches!(
+            evaluate("1+100/(window_w-window_w)", &context),
+            Err(EvaluationError::DivisionByZero(_))
+        ));
+    }
+}
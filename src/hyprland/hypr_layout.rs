@@ -1,7 +1,7 @@
 use std::{fmt::Display, str::FromStr};
 use strum::EnumIter;
 
-#[derive(Debug, Default, EnumIter)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumIter)]
 pub enum HyprLayout {
     #[default]
     Dwindle,
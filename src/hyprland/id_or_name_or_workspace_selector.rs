@@ -1,4 +1,7 @@
-use super::{WorkspaceSelector, workspace_type::parse_workspace_selector};
+use super::{
+    LiveWorkspace, WorkspaceSelector, workspace_selector::selectors_match,
+    workspace_type::parse_workspace_selector,
+};
 use crate::{
     gtk_converters::{
         EnumConfigForGtk, ToGtkBoxWithSeparator, ToGtkBoxWithSeparatorAndNames,
@@ -21,6 +24,18 @@ pub enum IdOrNameOrWorkspaceSelector {
     WorkspaceSelector(Vec<WorkspaceSelector>),
 }
 
+impl IdOrNameOrWorkspaceSelector {
+    pub fn matches(&self, workspace: &LiveWorkspace) -> bool {
+        match self {
+            IdOrNameOrWorkspaceSelector::Id(id) => workspace.id == *id as i64,
+            IdOrNameOrWorkspaceSelector::Name(name) => workspace.name == *name,
+            IdOrNameOrWorkspaceSelector::WorkspaceSelector(selectors) => {
+                selectors_match(selectors, workspace)
+            }
+        }
+    }
+}
+
 impl HasDiscriminant for IdOrNameOrWorkspaceSelector {
     type Discriminant = IdOrNameOrWorkspaceSelectorDiscriminant;
 
@@ -0,0 +1,156 @@
+use serde_json::Value;
+
+/// A window as reported by `hyprctl clients -j`, reduced to what workspace selectors need.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LiveWindow {
+    pub floating: bool,
+    pub pinned: bool,
+    pub hidden: bool,
+    /// Whether the window is the first member of a group.
+    pub group_head: bool,
+    /// Internal fullscreen mode: 0 none, 1 maximized, 2 fullscreen.
+    pub fullscreen: i64,
+    /// Fullscreen mode the client was told about.
+    pub fullscreen_client: i64,
+}
+
+/// A workspace as reported by `hyprctl workspaces -j`, with its windows attached.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LiveWorkspace {
+    pub id: i64,
+    pub name: String,
+    pub monitor: String,
+    pub monitor_description: String,
+    pub windows: Vec<LiveWindow>,
+}
+
+impl LiveWorkspace {
+    pub fn is_special(&self) -> bool {
+        self.name.starts_with("special:")
+    }
+
+    pub fn is_named(&self) -> bool {
+        !self.is_special() && self.name != self.id.to_string()
+    }
+
+    /// Returns the fullscreen state in the numbering used by `f[]` selectors.
+    pub fn fullscreen_state(&self) -> i8 {
+        match self.windows.iter().find(|window| window.fullscreen != 0) {
+            None => -1,
+            Some(window) if window.fullscreen == 1 => 1,
+            Some(window) if window.fullscreen_client == window.fullscreen => 0,
+            Some(_) => 2,
+        }
+    }
+}
+
+/// Builds workspaces from the JSON output of `hyprctl workspaces -j`, `hyprctl clients -j`
+/// and `hyprctl monitors -j`. Missing or malformed client and monitor data is ignored.
+pub fn parse_live_workspaces(
+    workspaces_json: &str,
+    clients_json: &str,
+    monitors_json: &str,
+) -> Option<Vec<LiveWorkspace>> {
+    let workspaces = serde_json::from_str::<Vec<Value>>(workspaces_json).ok()?;
+    let clients = serde_json::from_str::<Vec<Value>>(clients_json).unwrap_or_default();
+    let monitors = serde_json::from_str::<Vec<Value>>(monitors_json).unwrap_or_default();
+
+    let str_of = |value: &Value, key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let bool_of = |value: &Value, key: &str| value.get(key).and_then(|v| v.as_bool());
+    let int_of = |value: &Value, key: &str| value.get(key).and_then(|v| v.as_i64());
+
+    let mut result: Vec<LiveWorkspace> = workspaces
+        .iter()
+        .filter_map(|workspace| {
+            let monitor = str_of(workspace, "monitor");
+            let monitor_description = monitors
+                .iter()
+                .find(|m| str_of(m, "name") == monitor)
+                .map(|m| str_of(m, "description"))
+                .unwrap_or_default();
+
+            Some(LiveWorkspace {
+                id: int_of(workspace, "id")?,
+                name: str_of(workspace, "name"),
+                monitor,
+                monitor_description,
+                windows: Vec::new(),
+            })
+        })
+        .collect();
+
+    for client in &clients {
+        let Some(workspace_id) = client.get("workspace").and_then(|w| int_of(w, "id")) else {
+            continue;
+        };
+        let Some(workspace) = result.iter_mut().find(|w| w.id == workspace_id) else {
+            continue;
+        };
+
+        let address = str_of(client, "address");
+        let group_head = client
+            .get("grouped")
+            .and_then(|g| g.as_array())
+            .and_then(|g| g.first())
+            .and_then(|g| g.as_str())
+            .is_some_and(|head| head == address);
+
+        workspace.windows.push(LiveWindow {
+            floating: bool_of(client, "floating").unwrap_or(false),
+            pinned: bool_of(client, "pinned").unwrap_or(false),
+            hidden: bool_of(client, "hidden").unwrap_or(false),
+            group_head,
+            fullscreen: int_of(client, "fullscreen").unwrap_or(0),
+            fullscreen_client: int_of(client, "fullscreenClient").unwrap_or(0),
+        });
+    }
+
+    result.sort_by_key(|w| w.id);
+    Some(result)
+}
+
+/// Workspaces used when Hyprland is not reachable, so selectors can still be tried out.
+pub fn sample_workspaces() -> Vec<LiveWorkspace> {
+    let tiled = LiveWindow::default();
+    let floating = LiveWindow {
+        floating: true,
+        ..LiveWindow::default()
+    };
+    let fullscreen = LiveWindow {
+        fullscreen: 2,
+        fullscreen_client: 2,
+        ..LiveWindow::default()
+    };
+    let group_head = LiveWindow {
+        group_head: true,
+        ..LiveWindow::default()
+    };
+    let group_member = LiveWindow {
+        hidden: true,
+        ..LiveWindow::default()
+    };
+
+    let workspace = |id: i64, name: &str, monitor: &str, windows: Vec<LiveWindow>| LiveWorkspace {
+        id,
+        name: name.to_string(),
+        monitor: monitor.to_string(),
+        monitor_description: String::new(),
+        windows,
+    };
+
+    vec![
+        workspace(-98, "special:magic", "DP-1", vec![floating.clone()]),
+        workspace(-1337, "web", "DP-1", vec![tiled.clone(), tiled.clone()]),
+        workspace(1, "1", "DP-1", vec![tiled.clone()]),
+        workspace(2, "2", "DP-1", vec![tiled.clone(), tiled.clone(), floating]),
+        workspace(3, "3", "DP-1", vec![fullscreen]),
+        workspace(4, "4", "HDMI-A-1", vec![group_head, group_member, tiled]),
+        workspace(5, "5", "HDMI-A-1", Vec::new()),
+    ]
+}
@@ -1,5 +1,5 @@
 use super::{
-    MonitorSelector, Range, WorkspaceSelectorFullscreen, WorkspaceSelectorNamed,
+    LiveWorkspace, MonitorSelector, Range, WorkspaceSelectorFullscreen, WorkspaceSelectorNamed,
     WorkspaceSelectorWindowCount,
 };
 use crate::{
@@ -41,6 +41,35 @@ impl WorkspaceSelector {
             t!("hyprland.workspace_selector.fullscreen").to_string(),
         ]
     }
+
+    pub fn matches(&self, workspace: &LiveWorkspace) -> bool {
+        match self {
+            WorkspaceSelector::None => true,
+            WorkspaceSelector::Range(Range { start, end }) => {
+                (*start as i64..=*end as i64).contains(&workspace.id)
+            }
+            WorkspaceSelector::Special(is_special) => workspace.is_special() == *is_special,
+            WorkspaceSelector::Named(named) => match named {
+                WorkspaceSelectorNamed::IsNamed(is_named) => workspace.is_named() == *is_named,
+                WorkspaceSelectorNamed::Starts(prefix) => workspace.name.starts_with(prefix),
+                WorkspaceSelectorNamed::Ends(suffix) => workspace.name.ends_with(suffix),
+            },
+            WorkspaceSelector::Monitor(monitor) => match monitor {
+                MonitorSelector::All => true,
+                MonitorSelector::Name(name) => workspace.monitor == *name,
+                MonitorSelector::Description(description) => {
+                    workspace.monitor_description.starts_with(description)
+                }
+            },
+            WorkspaceSelector::WindowCount(window_count) => window_count.matches(workspace),
+            WorkspaceSelector::Fullscreen(state) => workspace.fullscreen_state() == state.to_num(),
+        }
+    }
+}
+
+/// Every selector in a workspace selector string has to match.
+pub fn selectors_match(selectors: &[WorkspaceSelector], workspace: &LiveWorkspace) -> bool {
+    selectors.iter().all(|selector| selector.matches(workspace))
 }
 
 impl HasDiscriminant for WorkspaceSelector {
@@ -236,3 +265,66 @@ register_togtkbox_with_separator_names!(
     (WorkspaceSelectorWindowCount,),
     (WorkspaceSelectorFullscreen,)
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hyprland::live_workspace::LiveWindow;
+
+    fn selector(s: &str) -> WorkspaceSelector {
+        WorkspaceSelector::from_str(s).expect("selector should parse")
+    }
+
+    fn workspace(id: i64, windows: Vec<LiveWindow>) -> LiveWorkspace {
+        LiveWorkspace {
+            id,
+            name: id.to_string(),
+            windows,
+            ..Default::default()
+        }
+    }
+
+    fn tiled() -> LiveWindow {
+        LiveWindow::default()
+    }
+
+    fn floating() -> LiveWindow {
+        LiveWindow {
+            floating: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn range_includes_both_ends() {
+        let range = selector("r[1-3]");
+        assert!(!range.matches(&workspace(0, vec![])));
+        assert!(range.matches(&workspace(1, vec![])));
+        assert!(range.matches(&workspace(3, vec![])));
+        assert!(!range.matches(&workspace(4, vec![])));
+    }
+
+    #[test]
+    fn window_count_only_counts_flagged_windows() {
+        let count = selector("w[t1-3]");
+        assert!(!count.matches(&workspace(1, vec![])));
+        assert!(count.matches(&workspace(1, vec![tiled(), floating()])));
+        assert!(count.matches(&workspace(1, vec![tiled(), tiled(), tiled()])));
+        assert!(!count.matches(&workspace(1, vec![floating(), floating()])));
+        assert!(!count.matches(&workspace(1, vec![tiled(); 4])));
+    }
+
+    #[test]
+    fn fullscreen_none_matches_workspaces_without_fullscreen_windows() {
+        let no_fullscreen = selector("f[-1]");
+        assert!(no_fullscreen.matches(&workspace(1, vec![])));
+        assert!(no_fullscreen.matches(&workspace(1, vec![tiled(), floating()])));
+
+        let maximized = LiveWindow {
+            fullscreen: 1,
+            ..Default::default()
+        };
+        assert!(!no_fullscreen.matches(&workspace(1, vec![tiled(), maximized.clone()])));
+        assert!(selector("f[1]").matches(&workspace(1, vec![maximized])));
+    }
+}
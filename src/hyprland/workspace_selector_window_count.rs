@@ -1,4 +1,4 @@
-use super::WorkspaceSelectorWindowCountFlags;
+use super::{LiveWorkspace, WorkspaceSelectorWindowCountFlags};
 use crate::{
     gtk_converters::{
         EnumConfigForGtk, ToGtkBoxWithSeparatorAndNames, ToGtkBoxWithSeparatorAndNamesBuilder,
@@ -26,6 +26,33 @@ pub enum WorkspaceSelectorWindowCount {
     },
 }
 
+impl WorkspaceSelectorWindowCount {
+    pub fn matches(&self, workspace: &LiveWorkspace) -> bool {
+        let (flags, start, end) = match *self {
+            Self::Range {
+                flags,
+                range_start,
+                range_end,
+            } => (flags, range_start, range_end),
+            Self::Single { flags, count } => (flags, count, count),
+        };
+
+        let count = workspace
+            .windows
+            .iter()
+            .filter(|window| {
+                (!flags.tiled || !window.floating)
+                    && (!flags.floating || window.floating)
+                    && (!flags.groups || window.group_head)
+                    && (!flags.visible || !window.hidden)
+                    && (!flags.pinned || window.pinned)
+            })
+            .count() as u32;
+
+        (start..=end).contains(&count)
+    }
+}
+
 impl HasDiscriminant for WorkspaceSelectorWindowCount {
     type Discriminant = WorkspaceSelectorWindowCountDiscriminant;
 
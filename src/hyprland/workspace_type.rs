@@ -1,4 +1,7 @@
-use super::{WorkspaceSelector, workspace_selector::parse_single_selector};
+use super::{
+    LiveWorkspace, WorkspaceSelector,
+    workspace_selector::{parse_single_selector, selectors_match},
+};
 use rust_i18n::t;
 use std::fmt::Display;
 
@@ -19,6 +22,15 @@ impl WorkspaceType {
             t!("hyprland.workspace_type.selector").to_string(),
         ]
     }
+
+    pub fn matches(&self, workspace: &LiveWorkspace) -> bool {
+        match self {
+            WorkspaceType::Named(name) => workspace.name == *name,
+            WorkspaceType::Special(name) => workspace.name == format!("special:{name}"),
+            WorkspaceType::Numbered(num) => workspace.id == *num as i64,
+            WorkspaceType::Selector(selectors) => selectors_match(selectors, workspace),
+        }
+    }
 }
 
 impl Display for WorkspaceType {
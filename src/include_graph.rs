@@ -0,0 +1,158 @@
+use crate::utils::{IncludeError, IncludeNode, MARGIN_NORMAL, display_path};
+use gtk::{
+    Align, Box, Button, Image, Label, ListBox, Orientation, ScrolledWindow, SelectionMode, Window,
+    prelude::*,
+};
+use rust_i18n::t;
+use std::path::Path;
+
+const INDENT_PER_LEVEL: i32 = 20;
+
+fn count_files(node: &IncludeNode) -> (usize, usize) {
+    node.children.iter().map(count_files).fold(
+        (usize::from(node.error.is_none()), node.option_count),
+        |(files, options), (f, o)| (files + f, options + o),
+    )
+}
+
+fn count_problems(node: &IncludeNode) -> usize {
+    usize::from(node.error.is_some()) + node.children.iter().map(count_problems).sum::<usize>()
+}
+
+fn append_node(list_box: &ListBox, node: &IncludeNode, depth: i32, active_profile: &Path) {
+    let row_box = Box::new(Orientation::Horizontal, 8);
+    row_box.set_margin_start(MARGIN_NORMAL / 2 + depth * INDENT_PER_LEVEL);
+    row_box.set_margin_end(MARGIN_NORMAL / 2);
+    row_box.set_margin_top(MARGIN_NORMAL / 3);
+    row_box.set_margin_bottom(MARGIN_NORMAL / 3);
+
+    let icon = Image::from_icon_name(if node.error.is_some() {
+        "dialog-warning-symbolic"
+    } else {
+        "text-x-generic-symbolic"
+    });
+    row_box.append(&icon);
+
+    let text_box = Box::new(Orientation::Vertical, 2);
+    text_box.set_hexpand(true);
+
+    let path_label = Label::new(Some(&display_path(&node.path)));
+    path_label.set_halign(Align::Start);
+    path_label.set_selectable(true);
+    text_box.append(&path_label);
+
+    let mut details = Vec::new();
+    if let Some(pattern) = &node.pattern {
+        details.push(t!("include_graph.matched_by_", pattern = pattern).to_string());
+    }
+    match &node.error {
+        Some(IncludeError::Missing(error)) => {
+            details.push(t!("include_graph.missing_", error = error).to_string());
+        }
+        Some(IncludeError::Cycle) => details.push(t!("include_graph.cycle").to_string()),
+        Some(IncludeError::NoMatches) => {
+            details.push(t!("include_graph.no_matches").to_string());
+        }
+        None => {}
+    }
+
+    if !details.is_empty() {
+        let details_label = Label::new(Some(&details.join(" · ")));
+        details_label.set_halign(Align::Start);
+        details_label.set_wrap(true);
+        details_label.set_xalign(0.0);
+        details_label.add_css_class("caption");
+        if node.error.is_some() {
+            details_label.add_css_class("error");
+        } else {
+            details_label.add_css_class("dim-label");
+        }
+        text_box.append(&details_label);
+    }
+
+    row_box.append(&text_box);
+
+    if node.path == active_profile {
+        let profile_label = Label::new(Some(&t!("include_graph.active_profile")));
+        profile_label.set_valign(Align::Center);
+        profile_label.add_css_class("accent");
+        row_box.append(&profile_label);
+    }
+
+    if node.error.is_none() {
+        let count_label = Label::new(Some(&t!(
+            "include_graph.options_",
+            count = node.option_count
+        )));
+        count_label.set_valign(Align::Center);
+        count_label.add_css_class("dim-label");
+        row_box.append(&count_label);
+    }
+
+    list_box.append(&row_box);
+
+    for child in &node.children {
+        append_node(list_box, child, depth + 1, active_profile);
+    }
+}
+
+/// Shows the tree of files sourced by the config, marking the file of the active profile.
+pub fn show_include_graph(parent: &impl IsA<Window>, graph: &IncludeNode, active_profile: &Path) {
+    let dialog_window = Window::builder()
+        .title(t!("include_graph.title").to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(640)
+        .default_height(480)
+        .build();
+
+    let main_box = Box::new(Orientation::Vertical, 10);
+    main_box.set_margin_top(MARGIN_NORMAL);
+    main_box.set_margin_bottom(MARGIN_NORMAL);
+    main_box.set_margin_start(MARGIN_NORMAL);
+    main_box.set_margin_end(MARGIN_NORMAL);
+
+    let (files, options) = count_files(graph);
+    let summary_label = Label::new(Some(&t!(
+        "include_graph.summary_",
+        files = files,
+        options = options
+    )));
+    summary_label.set_halign(Align::Start);
+    summary_label.add_css_class("heading");
+    main_box.append(&summary_label);
+
+    let problems = count_problems(graph);
+    if problems > 0 {
+        let problems_label = Label::new(Some(&t!("include_graph.problems_", count = problems)));
+        problems_label.set_halign(Align::Start);
+        problems_label.add_css_class("error");
+        main_box.append(&problems_label);
+    }
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::None);
+    list_box.add_css_class("boxed-list");
+    append_node(&list_box, graph, 0, active_profile);
+
+    let scrolled_window = ScrolledWindow::new();
+    scrolled_window.set_vexpand(true);
+    scrolled_window.set_child(Some(&list_box));
+    main_box.append(&scrolled_window);
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+    let close_button = Button::with_label(&t!("include_graph.close"));
+    buttons_box.append(&close_button);
+    main_box.append(&buttons_box);
+
+    dialog_window.set_child(Some(&main_box));
+
+    let dialog_window_clone = dialog_window.clone();
+    close_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    dialog_window.present();
+}
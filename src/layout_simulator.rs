@@ -0,0 +1,1012 @@
+use crate::{
+    advanced_editors::create_spin_button,
+    desktop_preview::connect_redraw,
+    hyprland::{CssGaps, HyprLayout, Orientation as MasterOrientation, Vec2},
+    utils::{HistoryManager, MARGIN_NORMAL, TransformedConfig, parse_bool, transform_config},
+    widget::WidgetData,
+};
+use gtk::{
+    Align, Box as GtkBox, Button, DrawingArea, DropDown, GestureClick, Label, Orientation,
+    StringList,
+    cairo::{Context, FontSlant, FontWeight},
+    glib,
+    prelude::*,
+};
+use hyprparser::HyprlandConfig;
+use rust_i18n::t;
+use std::{cell::RefCell, collections::HashMap, rc::Rc, str::FromStr};
+use strum::IntoEnumIterator;
+
+pub const LAYOUT_SIMULATOR_CATEGORY: &str = "layouts";
+
+const MAX_WINDOWS: usize = 12;
+const EDGE_EPSILON: f64 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Rect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Rect {
+    fn from_edges(x0: f64, y0: f64, x1: f64, y1: f64) -> Self {
+        Self {
+            x: x0,
+            y: y0,
+            width: (x1 - x0).max(0.0),
+            height: (y1 - y0).max(0.0),
+        }
+    }
+
+    fn right(self) -> f64 {
+        self.x + self.width
+    }
+
+    fn bottom(self) -> f64 {
+        self.y + self.height
+    }
+
+    fn contains(self, x: f64, y: f64) -> bool {
+        x >= self.x && x <= self.right() && y >= self.y && y <= self.bottom()
+    }
+
+    /// Splits the rectangle in two; `ratio` is the share of the first part times two,
+    /// matching the semantics of `dwindle:default_split_ratio`.
+    fn split(self, top_bottom: bool, ratio: f64) -> (Self, Self) {
+        if top_bottom {
+            let first = self.height * ratio / 2.0;
+            (
+                Self {
+                    height: first,
+                    ..self
+                },
+                Self {
+                    y: self.y + first,
+                    height: self.height - first,
+                    ..self
+                },
+            )
+        } else {
+            let first = self.width * ratio / 2.0;
+            (
+                Self {
+                    width: first,
+                    ..self
+                },
+                Self {
+                    x: self.x + first,
+                    width: self.width - first,
+                    ..self
+                },
+            )
+        }
+    }
+
+    fn stack(self, count: usize, vertical: bool) -> Vec<Self> {
+        (0..count)
+            .map(|i| {
+                if vertical {
+                    let height = self.height / count as f64;
+                    Self {
+                        y: self.y + height * i as f64,
+                        height,
+                        ..self
+                    }
+                } else {
+                    let width = self.width / count as f64;
+                    Self {
+                        x: self.x + width * i as f64,
+                        width,
+                        ..self
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+struct LayoutOptions {
+    gaps_in: (f64, f64, f64, f64),
+    gaps_out: (f64, f64, f64, f64),
+    single_window_aspect_ratio: Vec2,
+    single_window_aspect_ratio_tolerance: f64,
+    force_split: u32,
+    preserve_split: bool,
+    split_width_multiplier: f64,
+    default_split_ratio: f64,
+    mfact: f64,
+    new_status: String,
+    new_on_top: bool,
+    new_on_active: String,
+    orientation: MasterOrientation,
+    slave_count_for_center_master: usize,
+    center_master_fallback: MasterOrientation,
+    fullscreen_on_one_column: bool,
+    column_width: f64,
+    focus_fit_method: u32,
+    direction: String,
+}
+
+impl LayoutOptions {
+    fn resolve(history: &HistoryManager, config: &TransformedConfig) -> Self {
+        let get = |category: &str, name: &str, default: &str| {
+            history.resolve_value_with_history(config, category, name, default)
+        };
+        let float = |name: &str, default: f64| {
+            get("layouts", name, &default.to_string())
+                .trim()
+                .parse::<f64>()
+                .unwrap_or(default)
+        };
+        let boolean = |name: &str, default: bool| {
+            parse_bool(&get("layouts", name, &default.to_string())).unwrap_or(default)
+        };
+        let gaps = |name: &str, default: u32| {
+            let (top, right, bottom, left) =
+                CssGaps::from_str(&get("general", name, &default.to_string()))
+                    .unwrap_or(CssGaps::All(default))
+                    .to_sides();
+            (top as f64, right as f64, bottom as f64, left as f64)
+        };
+
+        Self {
+            gaps_in: gaps("gaps_in", 5),
+            gaps_out: gaps("gaps_out", 20),
+            single_window_aspect_ratio: Vec2::from_str(&get(
+                "layouts",
+                "layout:single_window_aspect_ratio",
+                "0 0",
+            ))
+            .unwrap_or_default(),
+            single_window_aspect_ratio_tolerance: float(
+                "layout:single_window_aspect_ratio_tolerance",
+                0.1,
+            ),
+            force_split: float("dwindle:force_split", 0.0) as u32,
+            preserve_split: boolean("dwindle:preserve_split", false),
+            split_width_multiplier: float("dwindle:split_width_multiplier", 1.0),
+            default_split_ratio: float("dwindle:default_split_ratio", 1.0).clamp(0.1, 1.9),
+            mfact: float("master:mfact", 0.55).clamp(0.05, 0.95),
+            new_status: get("layouts", "master:new_status", "slave"),
+            new_on_top: boolean("master:new_on_top", false),
+            new_on_active: get("layouts", "master:new_on_active", "none"),
+            orientation: MasterOrientation::from_str(&get("layouts", "master:orientation", "left"))
+                .unwrap_or_default(),
+            slave_count_for_center_master: float("master:slave_count_for_center_master", 2.0)
+                as usize,
+            center_master_fallback: MasterOrientation::from_str(&get(
+                "layouts",
+                "master:center_master_fallback",
+                "left",
+            ))
+            .unwrap_or_default(),
+            fullscreen_on_one_column: boolean("scrolling:fullscreen_on_one_column", true),
+            column_width: float("scrolling:column_width", 0.5).clamp(0.1, 1.0),
+            focus_fit_method: float("scrolling:focus_fit_method", 0.0) as u32,
+            direction: get("layouts", "scrolling:direction", "right"),
+        }
+    }
+}
+
+enum DwindleNode {
+    Window(usize),
+    Split {
+        /// `None` until the direction is fixed by `preserve_split` or a toggle.
+        split_top: Option<bool>,
+        /// Direction used by the last layout pass.
+        last_top: bool,
+        children: Box<[DwindleNode; 2]>,
+    },
+}
+
+impl DwindleNode {
+    fn contains(&self, target: usize) -> bool {
+        match self {
+            Self::Window(id) => *id == target,
+            Self::Split { children, .. } => children.iter().any(|c| c.contains(target)),
+        }
+    }
+
+    fn first_window(&self) -> usize {
+        match self {
+            Self::Window(id) => *id,
+            Self::Split { children, .. } => children[0].first_window(),
+        }
+    }
+
+    fn insert(self, target: usize, new_id: usize, new_first: bool) -> Self {
+        match self {
+            Self::Window(id) if id == target => {
+                let children = if new_first {
+                    [Self::Window(new_id), Self::Window(id)]
+                } else {
+                    [Self::Window(id), Self::Window(new_id)]
+                };
+                Self::Split {
+                    split_top: None,
+                    last_top: false,
+                    children: Box::new(children),
+                }
+            }
+            Self::Window(id) => Self::Window(id),
+            Self::Split {
+                split_top,
+                last_top,
+                children,
+            } => {
+                let [first, second] = *children;
+                Self::Split {
+                    split_top,
+                    last_top,
+                    children: Box::new([
+                        first.insert(target, new_id, new_first),
+                        second.insert(target, new_id, new_first),
+                    ]),
+                }
+            }
+        }
+    }
+
+    fn remove(self, target: usize) -> Option<Self> {
+        match self {
+            Self::Window(id) if id == target => None,
+            Self::Window(id) => Some(Self::Window(id)),
+            Self::Split {
+                split_top,
+                last_top,
+                children,
+            } => {
+                let [first, second] = *children;
+                match (first.remove(target), second.remove(target)) {
+                    (Some(first), Some(second)) => Some(Self::Split {
+                        split_top,
+                        last_top,
+                        children: Box::new([first, second]),
+                    }),
+                    (Some(node), None) | (None, Some(node)) => Some(node),
+                    (None, None) => None,
+                }
+            }
+        }
+    }
+
+    fn reset_splits(&mut self) {
+        if let Self::Split {
+            split_top,
+            children,
+            ..
+        } = self
+        {
+            *split_top = None;
+            children.iter_mut().for_each(Self::reset_splits);
+        }
+    }
+
+    /// Flips the split that directly contains `target`, like the `togglesplit` dispatcher.
+    fn toggle_split(&mut self, target: usize) -> bool {
+        let Self::Split {
+            split_top,
+            last_top,
+            children,
+        } = self
+        else {
+            return false;
+        };
+
+        if children
+            .iter()
+            .any(|c| matches!(c, Self::Window(id) if *id == target))
+        {
+            *split_top = Some(!*last_top);
+            return true;
+        }
+
+        children.iter_mut().any(|c| c.toggle_split(target))
+    }
+
+    fn layout(&mut self, rect: Rect, options: &LayoutOptions, out: &mut Vec<(usize, Rect)>) {
+        match self {
+            Self::Window(id) => out.push((*id, rect)),
+            Self::Split {
+                split_top,
+                last_top,
+                children,
+            } => {
+                let top = match split_top {
+                    Some(top) => *top,
+                    None => {
+                        let auto = rect.height * options.split_width_multiplier > rect.width;
+                        if options.preserve_split {
+                            *split_top = Some(auto);
+                        }
+                        auto
+                    }
+                };
+                *last_top = top;
+
+                let (first, second) = rect.split(top, options.default_split_ratio);
+                children[0].layout(first, options, out);
+                children[1].layout(second, options, out);
+            }
+        }
+    }
+}
+
+fn next_orientation(orientation: MasterOrientation) -> MasterOrientation {
+    match orientation {
+        MasterOrientation::Left => MasterOrientation::Top,
+        MasterOrientation::Top => MasterOrientation::Right,
+        MasterOrientation::Right => MasterOrientation::Bottom,
+        MasterOrientation::Bottom => MasterOrientation::Center,
+        MasterOrientation::Center => MasterOrientation::Left,
+    }
+}
+
+fn layout_master(
+    windows: &[usize],
+    area: Rect,
+    options: &LayoutOptions,
+    orientation: MasterOrientation,
+) -> Vec<(usize, Rect)> {
+    let Some((&master, slaves)) = windows.split_first() else {
+        return Vec::new();
+    };
+
+    if slaves.is_empty() {
+        return vec![(master, area)];
+    }
+
+    let orientation = if orientation == MasterOrientation::Center
+        && slaves.len() < options.slave_count_for_center_master
+    {
+        options.center_master_fallback
+    } else {
+        orientation
+    };
+
+    let mfact = options.mfact;
+    let mut result = Vec::with_capacity(windows.len());
+
+    match orientation {
+        MasterOrientation::Left | MasterOrientation::Right => {
+            let master_width = area.width * mfact;
+            let (master_rect, stack_rect) = if orientation == MasterOrientation::Left {
+                area.split(false, mfact * 2.0)
+            } else {
+                let (stack, master) =
+                    area.split(false, (area.width - master_width) / area.width * 2.0);
+                (master, stack)
+            };
+            result.push((master, master_rect));
+            result.extend(
+                slaves
+                    .iter()
+                    .copied()
+                    .zip(stack_rect.stack(slaves.len(), true)),
+            );
+        }
+        MasterOrientation::Top | MasterOrientation::Bottom => {
+            let master_height = area.height * mfact;
+            let (master_rect, stack_rect) = if orientation == MasterOrientation::Top {
+                area.split(true, mfact * 2.0)
+            } else {
+                let (stack, master) =
+                    area.split(true, (area.height - master_height) / area.height * 2.0);
+                (master, stack)
+            };
+            result.push((master, master_rect));
+            result.extend(
+                slaves
+                    .iter()
+                    .copied()
+                    .zip(stack_rect.stack(slaves.len(), false)),
+            );
+        }
+        MasterOrientation::Center => {
+            let master_width = area.width * mfact;
+            let side_width = (area.width - master_width) / 2.0;
+            let left = Rect {
+                width: side_width,
+                ..area
+            };
+            let master_rect = Rect {
+                x: area.x + side_width,
+                width: master_width,
+                ..area
+            };
+            let right = Rect {
+                x: area.x + side_width + master_width,
+                width: side_width,
+                ..area
+            };
+
+            let right_slaves: Vec<usize> = slaves.iter().copied().step_by(2).collect();
+            let left_slaves: Vec<usize> = slaves.iter().copied().skip(1).step_by(2).collect();
+
+            result.push((master, master_rect));
+            result.extend(
+                right_slaves
+                    .iter()
+                    .copied()
+                    .zip(right.stack(right_slaves.len(), true)),
+            );
+            result.extend(
+                left_slaves
+                    .iter()
+                    .copied()
+                    .zip(left.stack(left_slaves.len(), true)),
+            );
+        }
+    }
+
+    result
+}
+
+fn layout_scrolling(
+    windows: &[usize],
+    focused: Option<usize>,
+    area: Rect,
+    options: &LayoutOptions,
+) -> Vec<(usize, Rect)> {
+    if windows.len() == 1 && options.fullscreen_on_one_column {
+        return vec![(windows[0], area)];
+    }
+
+    let horizontal = matches!(options.direction.as_str(), "right" | "left");
+    let reversed = matches!(options.direction.as_str(), "left" | "up");
+    let extent = if horizontal { area.width } else { area.height };
+    let column = extent * options.column_width;
+
+    let focused_index = focused
+        .and_then(|f| windows.iter().position(|w| *w == f))
+        .unwrap_or(windows.len().saturating_sub(1));
+    let focused_start = focused_index as f64 * column;
+    let total = windows.len() as f64 * column;
+
+    let scroll = if options.focus_fit_method == 0 {
+        focused_start + column / 2.0 - extent / 2.0
+    } else {
+        (focused_start + column - extent)
+            .max(0.0)
+            .min(focused_start)
+            .min((total - extent).max(0.0))
+    };
+
+    windows
+        .iter()
+        .enumerate()
+        .map(|(i, id)| {
+            let offset = i as f64 * column - scroll;
+            let rect = match (horizontal, reversed) {
+                (true, false) => Rect {
+                    x: area.x + offset,
+                    width: column,
+                    ..area
+                },
+                (true, true) => Rect {
+                    x: area.right() - offset - column,
+                    width: column,
+                    ..area
+                },
+                (false, false) => Rect {
+                    y: area.y + offset,
+                    height: column,
+                    ..area
+                },
+                (false, true) => Rect {
+                    y: area.bottom() - offset - column,
+                    height: column,
+                    ..area
+                },
+            };
+            (*id, rect)
+        })
+        .collect()
+}
+
+/// Shrinks every edge that does not touch the usable area by the matching `gaps_in` side.
+fn apply_gaps_in(rect: Rect, area: Rect, gaps_in: (f64, f64, f64, f64)) -> Rect {
+    let (top, right, bottom, left) = gaps_in;
+    let inner = |edge: f64, bound: f64, gap: f64| {
+        if (edge - bound).abs() > EDGE_EPSILON {
+            gap
+        } else {
+            0.0
+        }
+    };
+
+    Rect::from_edges(
+        rect.x + inner(rect.x, area.x, left),
+        rect.y + inner(rect.y, area.y, top),
+        rect.right() - inner(rect.right(), area.right(), right),
+        rect.bottom() - inner(rect.bottom(), area.bottom(), bottom),
+    )
+}
+
+fn apply_single_window_aspect_ratio(rect: Rect, options: &LayoutOptions) -> Rect {
+    let Vec2(ratio_x, ratio_y) = options.single_window_aspect_ratio;
+    if ratio_x <= 0.0 || ratio_y <= 0.0 || rect.height <= 0.0 {
+        return rect;
+    }
+
+    let requested = ratio_x / ratio_y;
+    let current = rect.width / rect.height;
+
+    if (current / requested - 1.0).abs() <= options.single_window_aspect_ratio_tolerance {
+        return rect;
+    }
+
+    if current > requested {
+        let width = rect.height * requested;
+        Rect {
+            x: rect.x + (rect.width - width) / 2.0,
+            width,
+            ..rect
+        }
+    } else {
+        let height = rect.width / requested;
+        Rect {
+            y: rect.y + (rect.height - height) / 2.0,
+            height,
+            ..rect
+        }
+    }
+}
+
+struct Simulator {
+    history: Rc<RefCell<HistoryManager>>,
+    config: TransformedConfig,
+    layout: HyprLayout,
+    monitor: (f64, f64),
+    windows: Vec<usize>,
+    dwindle: Option<DwindleNode>,
+    focused: Option<usize>,
+    next_id: usize,
+    cursor_first: bool,
+    /// Runtime orientation and the configured value it was derived from.
+    orientation_override: Option<(MasterOrientation, MasterOrientation)>,
+    /// Last computed geometry and the transform used to draw it, for hit testing.
+    geometry: Vec<(usize, Rect)>,
+    transform: (f64, f64, f64),
+}
+
+impl Simulator {
+    fn options(&self) -> LayoutOptions {
+        LayoutOptions::resolve(&self.history.borrow(), &self.config)
+    }
+
+    fn master_orientation(&mut self, options: &LayoutOptions) -> MasterOrientation {
+        match self.orientation_override {
+            Some((base, orientation)) if base == options.orientation => orientation,
+            _ => {
+                self.orientation_override = None;
+                options.orientation
+            }
+        }
+    }
+
+    fn add_window(&mut self) {
+        if self.windows.len() >= MAX_WINDOWS {
+            return;
+        }
+
+        let options = self.options();
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let focused_index = self
+            .focused
+            .and_then(|f| self.windows.iter().position(|w| *w == f));
+
+        let becomes_master = match options.new_status.as_str() {
+            "master" => true,
+            "inherit" => focused_index == Some(0),
+            _ => false,
+        };
+
+        let master_index = if self.windows.is_empty() || becomes_master {
+            0
+        } else {
+            match (options.new_on_active.as_str(), focused_index) {
+                ("before", Some(index)) if index > 0 => index,
+                ("after", Some(index)) => index + 1,
+                _ if options.new_on_top => 1,
+                _ => self.windows.len(),
+            }
+        };
+        let master_index = master_index.min(self.windows.len());
+
+        match self.layout {
+            HyprLayout::Scrolling => {
+                let index = focused_index.map_or(self.windows.len(), |i| i + 1);
+                self.windows.insert(index, id);
+            }
+            HyprLayout::Monocle => self.windows.push(id),
+            _ => self.windows.insert(master_index, id),
+        }
+
+        self.dwindle = Some(match self.dwindle.take() {
+            None => DwindleNode::Window(id),
+            Some(root) => {
+                let target = self
+                    .focused
+                    .filter(|f| root.contains(*f))
+                    .unwrap_or_else(|| root.first_window());
+                let new_first = match options.force_split {
+                    1 => true,
+                    2 => false,
+                    _ => self.cursor_first,
+                };
+                let mut root = root.insert(target, id, new_first);
+                if !options.preserve_split {
+                    root.reset_splits();
+                }
+                root
+            }
+        });
+
+        self.focused = Some(id);
+    }
+
+    fn remove_window(&mut self) {
+        let Some(target) = self.focused.or_else(|| self.windows.last().copied()) else {
+            return;
+        };
+
+        let options = self.options();
+        let index = self.windows.iter().position(|w| *w == target);
+        self.windows.retain(|w| *w != target);
+
+        self.dwindle = self.dwindle.take().and_then(|root| root.remove(target));
+        if !options.preserve_split
+            && let Some(root) = self.dwindle.as_mut()
+        {
+            root.reset_splits();
+        }
+
+        self.focused = index
+            .and_then(|i| self.windows.get(i.saturating_sub(1)))
+            .or_else(|| self.windows.first())
+            .copied();
+    }
+
+    fn toggle_split(&mut self) {
+        if let (Some(root), Some(focused)) = (self.dwindle.as_mut(), self.focused) {
+            root.toggle_split(focused);
+        }
+    }
+
+    fn cycle_orientation(&mut self) {
+        let options = self.options();
+        let current = self.master_orientation(&options);
+        self.orientation_override = Some((options.orientation, next_orientation(current)));
+    }
+
+    fn compute(&mut self) -> Vec<(usize, Rect)> {
+        let options = self.options();
+        let (monitor_width, monitor_height) = self.monitor;
+        let (out_top, out_right, out_bottom, out_left) = options.gaps_out;
+        let area = Rect::from_edges(
+            out_left,
+            out_top,
+            monitor_width - out_right,
+            monitor_height - out_bottom,
+        );
+
+        let mut tiles = match self.layout {
+            HyprLayout::Dwindle => {
+                let mut out = Vec::new();
+                if let Some(root) = self.dwindle.as_mut() {
+                    root.layout(area, &options, &mut out);
+                }
+                out
+            }
+            HyprLayout::Master => {
+                let orientation = self.master_orientation(&options);
+                layout_master(&self.windows, area, &options, orientation)
+            }
+            HyprLayout::Scrolling => layout_scrolling(&self.windows, self.focused, area, &options),
+            HyprLayout::Monocle => self.windows.iter().map(|id| (*id, area)).collect(),
+        };
+
+        for (_, rect) in tiles.iter_mut() {
+            *rect = apply_gaps_in(*rect, area, options.gaps_in);
+        }
+
+        if tiles.len() == 1 && matches!(self.layout, HyprLayout::Dwindle | HyprLayout::Master) {
+            tiles[0].1 = apply_single_window_aspect_ratio(tiles[0].1, &options);
+        }
+
+        if self.layout == HyprLayout::Monocle
+            && let Some(focused) = self.focused
+            && let Some(index) = tiles.iter().position(|(id, _)| *id == focused)
+        {
+            let tile = tiles.remove(index);
+            tiles.push(tile);
+        }
+
+        tiles
+    }
+
+    fn focus_at(&mut self, x: f64, y: f64) {
+        let (offset_x, offset_y, scale) = self.transform;
+        if scale <= 0.0 {
+            return;
+        }
+
+        let (x, y) = ((x - offset_x) / scale, (y - offset_y) / scale);
+
+        if let Some((id, rect)) = self
+            .geometry
+            .iter()
+            .rev()
+            .find(|(_, rect)| rect.contains(x, y))
+        {
+            self.focused = Some(*id);
+            self.cursor_first = if rect.width >= rect.height {
+                x < rect.x + rect.width / 2.0
+            } else {
+                y < rect.y + rect.height / 2.0
+            };
+        }
+    }
+}
+
+fn draw_simulation(
+    cr: &Context,
+    width: f64,
+    height: f64,
+    simulator: &mut Simulator,
+) -> Result<(), gtk::cairo::Error> {
+    let get_theme_color = |name: &str, default: (f64, f64, f64)| {
+        gtk::gdk::RGBA::parse(name)
+            .ok()
+            .map(|rgba| (rgba.red() as f64, rgba.green() as f64, rgba.blue() as f64))
+            .unwrap_or(default)
+    };
+    let (bg_r, bg_g, bg_b) = get_theme_color("@theme_bg_color", (0.18, 0.18, 0.2));
+    let (fg_r, fg_g, fg_b) = get_theme_color("@theme_fg_color", (0.9, 0.9, 0.9));
+    let (accent_r, accent_g, accent_b) =
+        get_theme_color("@theme_selected_bg_color", (0.2, 0.5, 1.0));
+
+    cr.set_source_rgb(bg_r, bg_g, bg_b);
+    cr.paint()?;
+
+    let tiles = simulator.compute();
+    let (monitor_width, monitor_height) = simulator.monitor;
+    let margin = MARGIN_NORMAL as f64;
+    let scale = ((width - margin * 2.0) / monitor_width)
+        .min((height - margin * 2.0) / monitor_height)
+        .max(0.0);
+    let offset_x = (width - monitor_width * scale) / 2.0;
+    let offset_y = (height - monitor_height * scale) / 2.0;
+    simulator.transform = (offset_x, offset_y, scale);
+
+    cr.set_source_rgba(fg_r, fg_g, fg_b, 0.08);
+    cr.rectangle(
+        offset_x,
+        offset_y,
+        monitor_width * scale,
+        monitor_height * scale,
+    );
+    cr.fill_preserve()?;
+    cr.set_source_rgba(fg_r, fg_g, fg_b, 0.4);
+    cr.set_line_width(1.0);
+    cr.stroke()?;
+
+    cr.select_font_face("Sans", FontSlant::Normal, FontWeight::Bold);
+    cr.set_font_size(14.0);
+
+    for (id, rect) in &tiles {
+        let is_focused = simulator.focused == Some(*id);
+        let visible = rect.x >= -EDGE_EPSILON
+            && rect.y >= -EDGE_EPSILON
+            && rect.right() <= monitor_width + EDGE_EPSILON
+            && rect.bottom() <= monitor_height + EDGE_EPSILON;
+        let alpha = if visible { 1.0 } else { 0.35 };
+
+        let x = offset_x + rect.x * scale;
+        let y = offset_y + rect.y * scale;
+        let w = rect.width * scale;
+        let h = rect.height * scale;
+
+        cr.set_source_rgba(bg_r, bg_g, bg_b, alpha);
+        cr.rectangle(x, y, w, h);
+        cr.fill()?;
+
+        let fill = if is_focused { 0.45 } else { 0.2 };
+        cr.set_source_rgba(accent_r, accent_g, accent_b, fill * alpha);
+        cr.rectangle(x, y, w, h);
+        cr.fill_preserve()?;
+        cr.set_source_rgba(accent_r, accent_g, accent_b, alpha);
+        cr.set_line_width(if is_focused { 2.0 } else { 1.0 });
+        cr.stroke()?;
+
+        let label = (id + 1).to_string();
+        let extents = cr.text_extents(&label)?;
+        cr.set_source_rgba(fg_r, fg_g, fg_b, alpha);
+        cr.move_to(
+            x + (w - extents.width()) / 2.0 - extents.x_bearing(),
+            y + (h - extents.height()) / 2.0 - extents.y_bearing(),
+        );
+        cr.show_text(&label)?;
+    }
+
+    simulator.geometry = tiles;
+
+    Ok(())
+}
+
+fn layout_index(layout: HyprLayout) -> u32 {
+    HyprLayout::iter().position(|l| l == layout).unwrap_or(0) as u32
+}
+
+/// Builds the tiling layout simulator card. Like the desktop preview, it redraws
+/// whenever one of `options` changes and reads pending values from `history`.
+pub fn create_layout_simulator(
+    history: Rc<RefCell<HistoryManager>>,
+    config: &HyprlandConfig,
+    options: &HashMap<String, WidgetData>,
+) -> GtkBox {
+    let card = GtkBox::new(Orientation::Vertical, 6);
+    card.add_css_class("card");
+    card.set_margin_bottom(MARGIN_NORMAL * 2 / 3);
+
+    let title_label = Label::new(Some(&t!("layout_simulator.layout_simulator")));
+    title_label.set_halign(Align::Start);
+    title_label.add_css_class("heading");
+    title_label.set_margin_start(MARGIN_NORMAL);
+    title_label.set_margin_top(MARGIN_NORMAL);
+
+    let hint_label = Label::new(Some(&t!("layout_simulator.layout_simulator_description")));
+    hint_label.set_halign(Align::Start);
+    hint_label.set_wrap(true);
+    hint_label.add_css_class("dim-label");
+    hint_label.set_margin_start(MARGIN_NORMAL);
+    hint_label.set_margin_end(MARGIN_NORMAL);
+
+    let config = transform_config(config.to_string());
+    let layout = HyprLayout::from_str(
+        &history
+            .borrow()
+            .resolve_value_with_history(&config, "general", "layout", "dwindle"),
+    )
+    .unwrap_or_default();
+
+    let simulator = Rc::new(RefCell::new(Simulator {
+        history,
+        config,
+        layout,
+        monitor: (1920.0, 1080.0),
+        windows: Vec::new(),
+        dwindle: None,
+        focused: None,
+        next_id: 0,
+        cursor_first: false,
+        orientation_override: None,
+        geometry: Vec::new(),
+        transform: (0.0, 0.0, 0.0),
+    }));
+    for _ in 0..3 {
+        simulator.borrow_mut().add_window();
+    }
+
+    let controls_box = GtkBox::new(Orientation::Horizontal, 6);
+    controls_box.set_margin_start(MARGIN_NORMAL);
+    controls_box.set_margin_end(MARGIN_NORMAL);
+
+    let layouts: Vec<String> = HyprLayout::iter().map(|l| l.to_string()).collect();
+    let layouts: Vec<&str> = layouts.iter().map(|s| s.as_str()).collect();
+    let layout_dropdown = DropDown::new(Some(StringList::new(&layouts)), None::<gtk::Expression>);
+    layout_dropdown.set_selected(layout_index(layout));
+    layout_dropdown.set_valign(Align::Center);
+
+    let width_spin = create_spin_button(320.0, 7680.0, 10.0);
+    width_spin.set_value(1920.0);
+    let height_spin = create_spin_button(240.0, 4320.0, 10.0);
+    height_spin.set_value(1080.0);
+
+    let add_button = Button::from_icon_name("list-add-symbolic");
+    add_button.set_tooltip_text(Some(&t!("layout_simulator.add_window")));
+    add_button.set_valign(Align::Center);
+    let remove_button = Button::from_icon_name("list-remove-symbolic");
+    remove_button.set_tooltip_text(Some(&t!("layout_simulator.remove_window")));
+    remove_button.set_valign(Align::Center);
+    let toggle_split_button = Button::with_label(&t!("layout_simulator.toggle_split"));
+    toggle_split_button.set_valign(Align::Center);
+    let orientation_button = Button::with_label(&t!("layout_simulator.next_orientation"));
+    orientation_button.set_valign(Align::Center);
+
+    controls_box.append(&layout_dropdown);
+    controls_box.append(&Label::new(Some(&t!("layout_simulator.monitor"))));
+    controls_box.append(&width_spin);
+    controls_box.append(&Label::new(Some("×")));
+    controls_box.append(&height_spin);
+    controls_box.append(&add_button);
+    controls_box.append(&remove_button);
+    controls_box.append(&toggle_split_button);
+    controls_box.append(&orientation_button);
+
+    let drawing_area = DrawingArea::new();
+    drawing_area.set_content_height(280);
+    drawing_area.set_hexpand(true);
+    drawing_area.set_margin_start(MARGIN_NORMAL);
+    drawing_area.set_margin_end(MARGIN_NORMAL);
+    drawing_area.set_margin_bottom(MARGIN_NORMAL);
+
+    let simulator_clone = Rc::clone(&simulator);
+    drawing_area.set_draw_func(move |_, cr, width, height| {
+        let mut simulator = simulator_clone.borrow_mut();
+        if let Err(e) = draw_simulation(cr, width as f64, height as f64, &mut simulator) {
+            glib::g_warning!("hyprviz", "Cairo draw error (layout simulator): {}", e);
+        }
+    });
+
+    let click = GestureClick::new();
+    let simulator_clone = Rc::clone(&simulator);
+    let drawing_area_clone = drawing_area.clone();
+    click.connect_pressed(move |_, _, x, y| {
+        simulator_clone.borrow_mut().focus_at(x, y);
+        drawing_area_clone.queue_draw();
+    });
+    drawing_area.add_controller(click);
+
+    let update_sensitivity = {
+        let toggle_split_button = toggle_split_button.clone();
+        let orientation_button = orientation_button.clone();
+        move |layout: HyprLayout| {
+            toggle_split_button.set_sensitive(layout == HyprLayout::Dwindle);
+            orientation_button.set_sensitive(layout == HyprLayout::Master);
+        }
+    };
+    update_sensitivity(layout);
+
+    let simulator_clone = Rc::clone(&simulator);
+    let drawing_area_clone = drawing_area.clone();
+    layout_dropdown.connect_selected_notify(move |dropdown| {
+        let layout = HyprLayout::iter()
+            .nth(dropdown.selected() as usize)
+            .unwrap_or_default();
+        simulator_clone.borrow_mut().layout = layout;
+        update_sensitivity(layout);
+        drawing_area_clone.queue_draw();
+    });
+
+    for spin_button in [&width_spin, &height_spin] {
+        let simulator_clone = Rc::clone(&simulator);
+        let drawing_area_clone = drawing_area.clone();
+        let width_spin_clone = width_spin.clone();
+        let height_spin_clone = height_spin.clone();
+        spin_button.connect_value_changed(move |_| {
+            simulator_clone.borrow_mut().monitor =
+                (width_spin_clone.value(), height_spin_clone.value());
+            drawing_area_clone.queue_draw();
+        });
+    }
+
+    let actions: [(&Button, fn(&mut Simulator)); 4] = [
+        (&add_button, Simulator::add_window),
+        (&remove_button, Simulator::remove_window),
+        (&toggle_split_button, Simulator::toggle_split),
+        (&orientation_button, Simulator::cycle_orientation),
+    ];
+    for (button, action) in actions {
+        let simulator_clone = Rc::clone(&simulator);
+        let drawing_area_clone = drawing_area.clone();
+        button.connect_clicked(move |_| {
+            action(&mut simulator_clone.borrow_mut());
+            drawing_area_clone.queue_draw();
+        });
+    }
+
+    connect_redraw(&drawing_area, options);
+
+    card.append(&title_label);
+    card.append(&hint_label);
+    card.append(&controls_box);
+    card.append(&drawing_area);
+
+    card
+}
@@ -1,3 +1,4 @@
+use config_document::ConfigDocument;
 use gtk::{Application, StringList, StringObject, glib, prelude::*};
 use gui::ConfigGUI;
 use hyprparser::parse_config;
@@ -17,13 +18,36 @@ use utils::{
 };
 
 mod advanced_editors;
+mod backups;
+mod categories;
+mod command_palette;
+mod config_document;
+mod daemon;
+mod desktop_preview;
+mod diff;
+mod drafts;
+mod export;
+mod git_log;
 mod gtk_converters;
 mod gui;
 mod guides;
 mod hyprland;
+mod include_graph;
+mod layout_simulator;
+mod merge;
+mod palette;
+mod profile_diff;
+mod profile_rules;
+mod profiles;
+mod rule_preview;
+mod save_review;
+mod search;
+mod snippet;
 mod system_info;
 mod utils;
 mod widget;
+mod workspace_matcher;
+mod workspace_matrix;
 
 i18n!("locales", fallback = "en");
 
@@ -31,6 +55,15 @@ fn main() {
     initialize_development_mode();
 
     let args: Vec<String> = env::args().collect();
+
+    if args.iter().any(|arg| arg == "--daemon") {
+        if let Err(e) = daemon::run_daemon() {
+            eprintln!("hyprviz daemon: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let filtered_args: Vec<String> = args.into_iter().filter(|arg| arg != "--dev").collect();
     let filtered_args_str: Vec<&str> = filtered_args.iter().map(|s| s.as_str()).collect();
 
@@ -62,8 +95,8 @@ fn build_ui(app: &Application) {
     let gui = Rc::new(RefCell::new(ConfigGUI::new(app)));
     gui::ConfigGUI::setup_ui_events(Rc::clone(&gui));
 
-    let is_programmatic_switch = Rc::new(std::cell::Cell::new(false));
-    let last_confirmed_index = Rc::new(std::cell::Cell::new(0u32));
+    let is_programmatic_switch = Rc::clone(&gui.borrow().is_programmatic_switch);
+    let last_confirmed_index = Rc::clone(&gui.borrow().last_confirmed_index);
 
     let config_path_full = get_config_path(false, "Default");
 
@@ -108,12 +141,12 @@ fn build_ui(app: &Application) {
         };
 
         if !check_last_non_empty_line_contains(&config_str, "source = ./hyprviz") {
-            let mut parsed_config = mute_stdout(|| parse_config(&config_str));
+            let mut document = ConfigDocument::parse(&config_str);
 
-            parsed_config.add_entry_headless("#", "Source for hyprviz");
-            parsed_config.add_entry_headless("source", "./hyprviz.conf");
+            document.push_raw_line("# Source for hyprviz");
+            document.push_line("source", "./hyprviz.conf");
 
-            let updated_config_str = parsed_config.to_string();
+            let updated_config_str = document.to_string();
 
             let hyprviz_path: PathBuf = config_path_full
                 .parent()
@@ -203,14 +236,15 @@ fn build_ui(app: &Application) {
 
         let parsed_config = mute_stdout(|| parse_config(&config_str_for_read));
 
+        gui.borrow_mut().history.borrow_mut().set_profile(&profile);
         gui.borrow_mut()
             .history
             .borrow_mut()
-            .set_initial_config_hash(&parsed_config.content);
+            .set_initial_config_hash(&config_str_for_read);
         gui.borrow_mut()
             .history
             .borrow_mut()
-            .restore_persisted_ui_state(&parsed_config.content);
+            .restore_persisted_ui_state(&config_str_for_read);
 
         let time = Instant::now();
         gui.borrow_mut().load_config(parsed_config, &profile);
@@ -243,6 +277,7 @@ fn build_ui(app: &Application) {
                     match found_index {
                         Some(index) => {
                             gui.borrow().profile_dropdown.set_selected(index);
+                            last_confirmed_index.set(index);
                         }
                         None => {
                             let config_dir = Path::new(HYPRVIZ_CONFIG_PATH)
@@ -319,7 +354,11 @@ fn build_ui(app: &Application) {
                             reload_hyprland();
                             last_confirmed_index_clone_clone.set(new_index);
                             glib::MainContext::default().spawn_local(async move {
-                                gui_clone_clone.borrow_mut().reload_ui(true);
+                                // Pending changes are kept per profile, so they survive
+                                // switching away and back.
+                                gui_clone_clone.borrow_mut().reload_ui(false);
+                                gui::ConfigGUI::offer_recovered_draft(Rc::clone(&gui_clone_clone));
+                                gui::ConfigGUI::watch_config_files(Rc::clone(&gui_clone_clone));
                             });
                         }
                         Err(e) => {
@@ -346,4 +385,6 @@ fn build_ui(app: &Application) {
     }
 
     gui.borrow().window.present();
+    gui::ConfigGUI::offer_recovered_draft(Rc::clone(&gui));
+    gui::ConfigGUI::watch_config_files(Rc::clone(&gui));
 }
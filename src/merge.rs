@@ -0,0 +1,257 @@
+use crate::{diff::value_label, utils::MARGIN_NORMAL};
+use gtk::{
+    Align, Box, Button, DropDown, Grid, Label, ListBox, Orientation, ScrolledWindow, SelectionMode,
+    Window, glib, prelude::*,
+};
+use rust_i18n::t;
+use std::{cell::Cell, rc::Rc};
+
+/// A pending change next to the value it was made against (`base`) and the value the file on
+/// disk has now. `None` means the option or line is not there.
+pub struct MergeEntry {
+    pub category: String,
+    pub key: String,
+    /// What the user sees: the option path, or the line of a top-level row.
+    pub label: String,
+    pub base: Option<String>,
+    pub disk: Option<String>,
+    pub pending: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeOutcome {
+    /// The disk still has the base value, so the pending change applies cleanly.
+    KeepPending,
+    /// The disk already has the pending value.
+    AlreadyOnDisk,
+    /// Both the disk and hyprviz changed the value.
+    Conflict,
+}
+
+impl MergeEntry {
+    pub fn outcome(&self) -> MergeOutcome {
+        if self.disk.as_deref() == Some(self.pending.as_str()) {
+            MergeOutcome::AlreadyOnDisk
+        } else if self.disk == self.base {
+            MergeOutcome::KeepPending
+        } else {
+            MergeOutcome::Conflict
+        }
+    }
+}
+
+/// Shows the pending changes that conflict with edits made on disk and lets the user pick a
+/// side for each. `on_merge` receives the pending changes to drop: those already on disk and
+/// the conflicts resolved in favor of the disk.
+pub fn show_merge_dialog<F, G>(
+    parent: &impl IsA<Window>,
+    changed_files: &[String],
+    entries: Vec<MergeEntry>,
+    on_merge: F,
+    on_later: G,
+) where
+    F: Fn(Vec<(String, String)>) + 'static,
+    G: Fn() + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(t!("merge.title").to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(640)
+        .default_height(520)
+        .build();
+
+    let main_box = Box::new(Orientation::Vertical, 10);
+    main_box.set_margin_top(MARGIN_NORMAL);
+    main_box.set_margin_bottom(MARGIN_NORMAL);
+    main_box.set_margin_start(MARGIN_NORMAL);
+    main_box.set_margin_end(MARGIN_NORMAL);
+
+    let description_label = Label::new(Some(&t!(
+        "merge.description_",
+        files = changed_files.join(", ")
+    )));
+    description_label.set_halign(Align::Start);
+    description_label.set_xalign(0.0);
+    description_label.set_wrap(true);
+    main_box.append(&description_label);
+
+    let count = |outcome: MergeOutcome| entries.iter().filter(|e| e.outcome() == outcome).count();
+    let summary_label = Label::new(Some(&t!(
+        "merge.summary_",
+        clean = count(MergeOutcome::KeepPending),
+        applied = count(MergeOutcome::AlreadyOnDisk),
+        conflicts = count(MergeOutcome::Conflict)
+    )));
+    summary_label.set_halign(Align::Start);
+    summary_label.add_css_class("heading");
+    main_box.append(&summary_label);
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::None);
+    list_box.add_css_class("boxed-list");
+
+    let mut choices = Vec::new();
+    for entry in entries.iter() {
+        match entry.outcome() {
+            MergeOutcome::KeepPending => continue,
+            MergeOutcome::AlreadyOnDisk => {
+                choices.push(((entry.category.clone(), entry.key.clone()), None));
+                continue;
+            }
+            MergeOutcome::Conflict => {}
+        }
+
+        let row_box = Box::new(Orientation::Vertical, 6);
+        row_box.set_margin_start(MARGIN_NORMAL / 2);
+        row_box.set_margin_end(MARGIN_NORMAL / 2);
+        row_box.set_margin_top(MARGIN_NORMAL / 2);
+        row_box.set_margin_bottom(MARGIN_NORMAL / 2);
+
+        let title_label = Label::new(Some(&entry.label));
+        title_label.set_halign(Align::Start);
+        title_label.add_css_class("heading");
+        row_box.append(&title_label);
+
+        let grid = Grid::new();
+        grid.set_column_spacing(12);
+        grid.set_row_spacing(4);
+        for (row, (title, value)) in [
+            (t!("merge.base"), entry.base.as_deref()),
+            (t!("merge.disk"), entry.disk.as_deref()),
+            (t!("merge.pending"), Some(entry.pending.as_str())),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let title_label = Label::new(Some(&title));
+            title_label.set_halign(Align::Start);
+            title_label.add_css_class("dim-label");
+            grid.attach(&title_label, 0, row as i32, 1, 1);
+            grid.attach(
+                &value_label(value, &t!("merge.not_set")),
+                1,
+                row as i32,
+                1,
+                1,
+            );
+        }
+        row_box.append(&grid);
+
+        let choice_dropdown =
+            DropDown::from_strings(&[&t!("merge.keep_pending"), &t!("merge.use_disk")]);
+        choice_dropdown.set_halign(Align::End);
+        row_box.append(&choice_dropdown);
+
+        list_box.append(&row_box);
+        choices.push((
+            (entry.category.clone(), entry.key.clone()),
+            Some(choice_dropdown),
+        ));
+    }
+
+    if count(MergeOutcome::Conflict) > 0 {
+        let scrolled_window = ScrolledWindow::new();
+        scrolled_window.set_vexpand(true);
+        scrolled_window.set_child(Some(&list_box));
+        main_box.append(&scrolled_window);
+    }
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+    buttons_box.set_valign(Align::End);
+    buttons_box.set_vexpand(true);
+
+    let later_button = Button::with_label(&t!("merge.later"));
+    let merge_button = Button::with_label(&t!("merge.merge"));
+    merge_button.add_css_class("suggested-action");
+    buttons_box.append(&later_button);
+    buttons_box.append(&merge_button);
+    main_box.append(&buttons_box);
+
+    dialog_window.set_child(Some(&main_box));
+
+    let on_later = Rc::new(on_later);
+    let merged = Rc::new(Cell::new(false));
+
+    let dialog_window_clone = dialog_window.clone();
+    later_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    let merged_clone = merged.clone();
+    merge_button.connect_clicked(move |_| {
+        let dropped = choices
+            .iter()
+            .filter(|(_, dropdown)| dropdown.as_ref().is_none_or(|d| d.selected() == 1))
+            .map(|(key, _)| key.clone())
+            .collect();
+        merged_clone.set(true);
+        dialog_window_clone.close();
+        on_merge(dropped);
+    });
+
+    dialog_window.connect_close_request(move |_| {
+        if !merged.get() {
+            on_later();
+        }
+        glib::Propagation::Proceed
+    });
+
+    dialog_window.present();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(base: Option<&str>, disk: Option<&str>, pending: &str) -> MergeEntry {
+        MergeEntry {
+            category: "general".to_string(),
+            key: "general:gaps_in".to_string(),
+            label: "general:gaps_in".to_string(),
+            base: base.map(str::to_string),
+            disk: disk.map(str::to_string),
+            pending: pending.to_string(),
+        }
+    }
+
+    #[test]
+    fn unchanged_disk_keeps_the_pending_value() {
+        assert_eq!(
+            entry(Some("5"), Some("5"), "10").outcome(),
+            MergeOutcome::KeepPending
+        );
+        assert_eq!(entry(None, None, "10").outcome(), MergeOutcome::KeepPending);
+    }
+
+    #[test]
+    fn disk_with_the_pending_value_needs_no_merge() {
+        assert_eq!(
+            entry(Some("5"), Some("10"), "10").outcome(),
+            MergeOutcome::AlreadyOnDisk
+        );
+        assert_eq!(
+            entry(None, Some("10"), "10").outcome(),
+            MergeOutcome::AlreadyOnDisk
+        );
+    }
+
+    #[test]
+    fn changes_on_both_sides_conflict() {
+        assert_eq!(
+            entry(Some("5"), Some("8"), "10").outcome(),
+            MergeOutcome::Conflict
+        );
+        assert_eq!(
+            entry(Some("5"), None, "10").outcome(),
+            MergeOutcome::Conflict
+        );
+        assert_eq!(
+            entry(None, Some("8"), "10").outcome(),
+            MergeOutcome::Conflict
+        );
+    }
+}
@@ -0,0 +1,647 @@
+use crate::{
+    hyprland::{Angle, HyprColor, HyprGradient},
+    utils::{HYPRPAPER_CONFIG_PATH, MARGIN_NORMAL, ONE_OVER_255, expand_tilde},
+};
+use gtk::{
+    Align, ApplicationWindow, Box, Button, DrawingArea, DropDown, Entry, FileDialog, Grid, Label,
+    Orientation, StringList, Window, gdk_pixbuf::Pixbuf, glib, prelude::*,
+};
+use rust_i18n::t;
+use std::{
+    cell::RefCell,
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+use strum::{EnumIter, IntoEnumIterator};
+
+const PALETTE_SIZE: usize = 6;
+const SAMPLE_SIZE: i32 = 128;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+enum PaletteTarget {
+    ActiveBorderStart,
+    ActiveBorderEnd,
+    InactiveBorder,
+    GroupbarActive,
+    GroupbarInactive,
+    Shadow,
+}
+
+impl PaletteTarget {
+    fn label(self) -> String {
+        match self {
+            Self::ActiveBorderStart => t!("palette.active_border_start").to_string(),
+            Self::ActiveBorderEnd => t!("palette.active_border_end").to_string(),
+            Self::InactiveBorder => t!("palette.inactive_border").to_string(),
+            Self::GroupbarActive => t!("palette.groupbar_active").to_string(),
+            Self::GroupbarInactive => t!("palette.groupbar_inactive").to_string(),
+            Self::Shadow => t!("palette.shadow").to_string(),
+        }
+    }
+}
+
+/// Returns the first wallpaper referenced in `hyprpaper.conf`, if any.
+pub fn find_hyprpaper_wallpaper() -> Option<PathBuf> {
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    let content = fs::read_to_string(Path::new(&home).join(HYPRPAPER_CONFIG_PATH)).ok()?;
+
+    let mut wallpaper = None;
+    let mut preload = None;
+
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+
+        match key.trim() {
+            // wallpaper = monitor,path
+            "wallpaper" if wallpaper.is_none() => {
+                wallpaper = value
+                    .split_once(',')
+                    .map(|(_, path)| path.trim().to_string());
+            }
+            // path = ... (inside a `wallpaper { }` block)
+            "path" if wallpaper.is_none() => {
+                wallpaper = Some(value.to_string());
+            }
+            "preload" if preload.is_none() => {
+                preload = Some(value.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    let path = wallpaper.or(preload).filter(|p| !p.is_empty())?;
+    let path = path.replace("$HOME", &home);
+
+    expand_tilde(Path::new(&path)).ok()
+}
+
+/// Loads a downscaled copy of the image and returns its dominant colors, most common first.
+pub fn extract_palette(path: &Path, count: usize) -> Result<Vec<HyprColor>, Box<dyn Error>> {
+    let pixbuf = Pixbuf::from_file_at_scale(path, SAMPLE_SIZE, SAMPLE_SIZE, true)?;
+
+    let width = pixbuf.width() as usize;
+    let height = pixbuf.height() as usize;
+    let n_channels = pixbuf.n_channels() as usize;
+    let rowstride = pixbuf.rowstride() as usize;
+    let has_alpha = pixbuf.has_alpha();
+    let bytes = pixbuf.read_pixel_bytes();
+
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let offset = y * rowstride + x * n_channels;
+            let Some(pixel) = bytes.get(offset..offset + n_channels) else {
+                continue;
+            };
+
+            if has_alpha && pixel[3] < 128 {
+                continue;
+            }
+
+            pixels.push([pixel[0], pixel[1], pixel[2]]);
+        }
+    }
+
+    if pixels.is_empty() {
+        return Err(format!("No opaque pixels found in {}", path.display()).into());
+    }
+
+    Ok(median_cut(pixels, count)
+        .into_iter()
+        .map(|[r, g, b]| HyprColor::Rgb(r, g, b))
+        .collect())
+}
+
+fn median_cut(pixels: Vec<[u8; 3]>, count: usize) -> Vec<[u8; 3]> {
+    let channel_range = |bucket: &[[u8; 3]], channel: usize| {
+        let (min, max) = bucket.iter().fold((u8::MAX, u8::MIN), |(min, max), p| {
+            (min.min(p[channel]), max.max(p[channel]))
+        });
+        max.saturating_sub(min)
+    };
+
+    let mut buckets = vec![pixels];
+
+    while buckets.len() < count {
+        let Some((index, channel, _)) = buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, bucket)| bucket.len() > 1)
+            .flat_map(|(i, bucket)| {
+                (0..3).map(move |c| (i, c, channel_range(bucket, c) as usize * bucket.len()))
+            })
+            .filter(|(_, _, score)| *score > 0)
+            .max_by_key(|(_, _, score)| *score)
+        else {
+            break;
+        };
+
+        let mut bucket = buckets.swap_remove(index);
+        bucket.sort_unstable_by_key(|p| p[channel]);
+        let upper = bucket.split_off(bucket.len() / 2);
+        buckets.push(bucket);
+        buckets.push(upper);
+    }
+
+    buckets.sort_by_key(|bucket| std::cmp::Reverse(bucket.len()));
+
+    buckets
+        .iter()
+        .map(|bucket| {
+            let len = bucket.len() as u64;
+            let sum = bucket.iter().fold([0u64; 3], |acc, p| {
+                [
+                    acc[0] + p[0] as u64,
+                    acc[1] + p[1] as u64,
+                    acc[2] + p[2] as u64,
+                ]
+            });
+            [
+                (sum[0] / len) as u8,
+                (sum[1] / len) as u8,
+                (sum[2] / len) as u8,
+            ]
+        })
+        .collect()
+}
+
+fn rgb_components(color: HyprColor) -> (u8, u8, u8) {
+    match color {
+        HyprColor::Rgb(r, g, b) | HyprColor::Rgba(r, g, b, _) => (r, g, b),
+    }
+}
+
+fn with_alpha(color: HyprColor, alpha: u8) -> HyprColor {
+    let (r, g, b) = rgb_components(color);
+    HyprColor::Rgba(r, g, b, alpha)
+}
+
+fn saturation_and_luminance(color: HyprColor) -> (f64, f64) {
+    let (r, g, b) = rgb_components(color);
+    let max = r.max(g).max(b) as f64 * ONE_OVER_255;
+    let min = r.min(g).min(b) as f64 * ONE_OVER_255;
+    let saturation = if max <= f64::EPSILON {
+        0.0
+    } else {
+        (max - min) / max
+    };
+    let luminance = (0.2126 * r as f64 + 0.7152 * g as f64 + 0.0722 * b as f64) * ONE_OVER_255;
+
+    (saturation, luminance)
+}
+
+/// Picks a palette index for every target: vivid colors for active elements,
+/// muted ones for inactive elements and the darkest color for the shadow.
+fn suggest_assignment(palette: &[HyprColor]) -> Vec<usize> {
+    if palette.is_empty() {
+        return vec![0; PaletteTarget::iter().count()];
+    }
+
+    let mut by_vividness: Vec<usize> = (0..palette.len()).collect();
+    by_vividness.sort_by(|&a, &b| {
+        let (sa, la) = saturation_and_luminance(palette[a]);
+        let (sb, lb) = saturation_and_luminance(palette[b]);
+        (sb * lb).total_cmp(&(sa * la))
+    });
+
+    let vivid = by_vividness[0];
+    let second_vivid = by_vividness.get(1).copied().unwrap_or(vivid);
+    let muted = *by_vividness.last().unwrap_or(&vivid);
+    let darkest = (0..palette.len())
+        .min_by(|&a, &b| {
+            saturation_and_luminance(palette[a])
+                .1
+                .total_cmp(&saturation_and_luminance(palette[b]).1)
+        })
+        .unwrap_or(muted);
+
+    PaletteTarget::iter()
+        .map(|target| match target {
+            PaletteTarget::ActiveBorderStart | PaletteTarget::GroupbarActive => vivid,
+            PaletteTarget::ActiveBorderEnd => second_vivid,
+            PaletteTarget::InactiveBorder | PaletteTarget::GroupbarInactive => muted,
+            PaletteTarget::Shadow => darkest,
+        })
+        .collect()
+}
+
+/// Resolves the chosen palette entries into `(category, key, value)` triples.
+fn build_changes(palette: &[HyprColor], selection: &[usize]) -> Vec<(String, String, String)> {
+    let pick = |target: PaletteTarget| {
+        selection
+            .get(target as usize)
+            .and_then(|&i| palette.get(i))
+            .copied()
+            .unwrap_or_default()
+    };
+
+    let active_start = with_alpha(pick(PaletteTarget::ActiveBorderStart), 0xFF);
+    let active_end = with_alpha(pick(PaletteTarget::ActiveBorderEnd), 0xFF);
+    let active_border = if active_start == active_end {
+        HyprGradient {
+            colors: vec![active_start],
+            angle: None,
+        }
+    } else {
+        HyprGradient {
+            colors: vec![active_start, active_end],
+            angle: Some(Angle::Degrees(45)),
+        }
+    };
+    let single = |color: HyprColor| HyprGradient {
+        colors: vec![color],
+        angle: None,
+    };
+    let shadow = with_alpha(pick(PaletteTarget::Shadow), 0xEE);
+
+    vec![
+        (
+            "general".to_string(),
+            "col.active_border".to_string(),
+            active_border.to_string(),
+        ),
+        (
+            "general".to_string(),
+            "col.inactive_border".to_string(),
+            single(with_alpha(pick(PaletteTarget::InactiveBorder), 0xAA)).to_string(),
+        ),
+        (
+            "group".to_string(),
+            "groupbar:col.active".to_string(),
+            single(with_alpha(pick(PaletteTarget::GroupbarActive), 0xFF)).to_string(),
+        ),
+        (
+            "group".to_string(),
+            "groupbar:col.inactive".to_string(),
+            single(with_alpha(pick(PaletteTarget::GroupbarInactive), 0xAA)).to_string(),
+        ),
+        (
+            "decoration".to_string(),
+            "shadow:color".to_string(),
+            format!("rgba({})", &shadow.to_rgba_hex()[1..]),
+        ),
+    ]
+}
+
+fn set_source_color(cr: &gtk::cairo::Context, color: HyprColor) {
+    let rgba = color.to_gtk_rgba();
+    cr.set_source_rgba(
+        rgba.red() as f64,
+        rgba.green() as f64,
+        rgba.blue() as f64,
+        rgba.alpha() as f64,
+    );
+}
+
+fn draw_palette_preview(
+    cr: &gtk::cairo::Context,
+    width: f64,
+    height: f64,
+    palette: &[HyprColor],
+    selection: &[usize],
+) -> Result<(), gtk::cairo::Error> {
+    let pick = |target: PaletteTarget| {
+        selection
+            .get(target as usize)
+            .and_then(|&i| palette.get(i))
+            .copied()
+            .unwrap_or(HyprColor::Rgb(0x44, 0x44, 0x44))
+    };
+
+    set_source_color(cr, palette.first().copied().unwrap_or_default());
+    cr.paint()?;
+
+    let gap = MARGIN_NORMAL as f64;
+    let border = 3.0;
+    let bar = 6.0;
+    let window_width = (width - gap * 3.0) / 2.0;
+    let window_height = height - gap * 2.0 - bar - 2.0;
+
+    let inactive = (gap, gap + bar + 2.0);
+    let active = (gap * 2.0 + window_width, gap + bar + 2.0);
+
+    // Shadow under the active window
+    let (sr, sg, sb) = rgb_components(pick(PaletteTarget::Shadow));
+    for i in (1..=8).rev() {
+        let spread = i as f64;
+        cr.set_source_rgba(
+            sr as f64 * ONE_OVER_255,
+            sg as f64 * ONE_OVER_255,
+            sb as f64 * ONE_OVER_255,
+            0.08,
+        );
+        cr.rectangle(
+            active.0 - spread + 2.0,
+            active.1 - spread + 4.0,
+            window_width + spread * 2.0,
+            window_height + spread * 2.0,
+        );
+        cr.fill()?;
+    }
+
+    for (origin, is_active) in [(inactive, false), (active, true)] {
+        let (x, y) = origin;
+
+        if is_active {
+            let start = pick(PaletteTarget::ActiveBorderStart).to_gtk_rgba();
+            let end = pick(PaletteTarget::ActiveBorderEnd).to_gtk_rgba();
+            let gradient =
+                gtk::cairo::LinearGradient::new(x, y + window_height, x + window_width, y);
+            gradient.add_color_stop_rgb(
+                0.0,
+                start.red() as f64,
+                start.green() as f64,
+                start.blue() as f64,
+            );
+            gradient.add_color_stop_rgb(
+                1.0,
+                end.red() as f64,
+                end.green() as f64,
+                end.blue() as f64,
+            );
+            cr.set_source(&gradient)?;
+        } else {
+            set_source_color(cr, pick(PaletteTarget::InactiveBorder));
+        }
+        cr.rectangle(x, y, window_width, window_height);
+        cr.fill()?;
+
+        cr.set_source_rgba(0.12, 0.12, 0.14, 1.0);
+        cr.rectangle(
+            x + border,
+            y + border,
+            window_width - border * 2.0,
+            window_height - border * 2.0,
+        );
+        cr.fill()?;
+
+        let bar_color = if is_active {
+            pick(PaletteTarget::GroupbarActive)
+        } else {
+            pick(PaletteTarget::GroupbarInactive)
+        };
+        set_source_color(cr, bar_color);
+        cr.rectangle(x, y - bar - 2.0, window_width, bar);
+        cr.fill()?;
+    }
+
+    Ok(())
+}
+
+fn create_swatch(color: HyprColor) -> DrawingArea {
+    let swatch = DrawingArea::new();
+    swatch.set_content_width(40);
+    swatch.set_content_height(28);
+    swatch.set_tooltip_text(Some(&color.to_rgb_hex()));
+    swatch.set_draw_func(move |_, cr, _width, _height| {
+        set_source_color(cr, color);
+        if let Err(e) = cr.paint() {
+            glib::g_warning!("hyprviz", "Cairo paint error (swatch): {}", e);
+        }
+    });
+
+    swatch
+}
+
+/// Opens the wallpaper palette dialog; `on_apply` receives `(category, key, value)` triples.
+pub fn show_palette_dialog<F>(parent: &ApplicationWindow, on_apply: F)
+where
+    F: Fn(Vec<(String, String, String)>) + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(t!("palette.wallpaper_palette").to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(520)
+        .build();
+
+    let dialog_box = Box::new(Orientation::Vertical, 10);
+    dialog_box.set_margin_top(MARGIN_NORMAL);
+    dialog_box.set_margin_bottom(MARGIN_NORMAL);
+    dialog_box.set_margin_start(MARGIN_NORMAL);
+    dialog_box.set_margin_end(MARGIN_NORMAL);
+
+    let path_box = Box::new(Orientation::Horizontal, 5);
+    let path_entry = Entry::new();
+    path_entry.set_hexpand(true);
+    path_entry.set_placeholder_text(Some(&t!("palette.image_path")));
+    if let Some(path) = find_hyprpaper_wallpaper() {
+        path_entry.set_text(&path.to_string_lossy());
+    }
+    let browse_button = Button::from_icon_name("document-open-symbolic");
+    browse_button.set_tooltip_text(Some(&t!("palette.choose_image")));
+    let extract_button = Button::with_label(&t!("palette.extract"));
+    path_box.append(&path_entry);
+    path_box.append(&browse_button);
+    path_box.append(&extract_button);
+    dialog_box.append(&path_box);
+
+    let status_label = Label::new(None);
+    status_label.set_halign(Align::Start);
+    status_label.set_wrap(true);
+    status_label.add_css_class("dim-label");
+    dialog_box.append(&status_label);
+
+    let swatches_box = Box::new(Orientation::Horizontal, 6);
+    swatches_box.set_halign(Align::Center);
+    dialog_box.append(&swatches_box);
+
+    let grid = Grid::builder().row_spacing(6).column_spacing(12).build();
+    let dropdowns: Vec<DropDown> = PaletteTarget::iter()
+        .enumerate()
+        .map(|(row, target)| {
+            let label = Label::new(Some(&target.label()));
+            label.set_halign(Align::Start);
+            label.set_hexpand(true);
+            let dropdown = DropDown::new(None::<StringList>, None::<gtk::Expression>);
+            dropdown.set_sensitive(false);
+            grid.attach(&label, 0, row as i32, 1, 1);
+            grid.attach(&dropdown, 1, row as i32, 1, 1);
+            dropdown
+        })
+        .collect();
+    dialog_box.append(&grid);
+
+    let palette: Rc<RefCell<Vec<HyprColor>>> = Rc::new(RefCell::new(Vec::new()));
+
+    let preview = DrawingArea::new();
+    preview.set_content_height(180);
+    preview.set_hexpand(true);
+    let palette_clone = Rc::clone(&palette);
+    let dropdowns_clone = dropdowns.clone();
+    preview.set_draw_func(move |_, cr, width, height| {
+        let palette = palette_clone.borrow();
+        let selection: Vec<usize> = dropdowns_clone
+            .iter()
+            .map(|d| d.selected() as usize)
+            .collect();
+
+        if let Err(e) = draw_palette_preview(cr, width as f64, height as f64, &palette, &selection)
+        {
+            glib::g_warning!("hyprviz", "Cairo draw error (palette preview): {}", e);
+        }
+    });
+    dialog_box.append(&preview);
+
+    for dropdown in &dropdowns {
+        let preview_clone = preview.clone();
+        dropdown.connect_selected_notify(move |_| preview_clone.queue_draw());
+    }
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+    let cancel_button = Button::with_label(&t!("palette.cancel"));
+    let apply_button = Button::with_label(&t!("palette.apply"));
+    apply_button.add_css_class("suggested-action");
+    apply_button.set_sensitive(false);
+    buttons_box.append(&cancel_button);
+    buttons_box.append(&apply_button);
+    dialog_box.append(&buttons_box);
+
+    dialog_window.set_child(Some(&dialog_box));
+
+    let extract = {
+        let path_entry = path_entry.clone();
+        let status_label = status_label.clone();
+        let swatches_box = swatches_box.clone();
+        let dropdowns = dropdowns.clone();
+        let palette = Rc::clone(&palette);
+        let preview = preview.clone();
+        let apply_button = apply_button.clone();
+        Rc::new(move || {
+            let path = match expand_tilde(Path::new(path_entry.text().trim())) {
+                Ok(path) => path,
+                Err(e) => {
+                    status_label.set_text(&t!("palette.failed_to_load_image_", error = e));
+                    return;
+                }
+            };
+
+            match extract_palette(&path, PALETTE_SIZE) {
+                Ok(colors) => {
+                    while let Some(child) = swatches_box.first_child() {
+                        swatches_box.remove(&child);
+                    }
+                    for color in &colors {
+                        swatches_box.append(&create_swatch(*color));
+                    }
+
+                    let items: Vec<String> = colors
+                        .iter()
+                        .enumerate()
+                        .map(|(i, c)| format!("{} · {}", i + 1, c.to_rgb_hex()))
+                        .collect();
+                    let items: Vec<&str> = items.iter().map(|s| s.as_str()).collect();
+                    let suggestion = suggest_assignment(&colors);
+
+                    *palette.borrow_mut() = colors;
+
+                    for (dropdown, index) in dropdowns.iter().zip(suggestion) {
+                        dropdown.set_model(Some(&StringList::new(&items)));
+                        dropdown.set_selected(index as u32);
+                        dropdown.set_sensitive(true);
+                    }
+
+                    status_label
+                        .set_text(&t!("palette.extracted_colors_from_", file = path.display()));
+                    apply_button.set_sensitive(true);
+                    preview.queue_draw();
+                }
+                Err(e) => {
+                    status_label.set_text(&t!("palette.failed_to_load_image_", error = e));
+                }
+            }
+        })
+    };
+
+    if !path_entry.text().is_empty() {
+        extract();
+    }
+
+    let extract_clone = Rc::clone(&extract);
+    extract_button.connect_clicked(move |_| extract_clone());
+
+    let extract_clone = Rc::clone(&extract);
+    path_entry.connect_activate(move |_| extract_clone());
+
+    let dialog_window_clone = dialog_window.clone();
+    let path_entry_clone = path_entry.clone();
+    browse_button.connect_clicked(move |_| {
+        let dialog_window = dialog_window_clone.clone();
+        let path_entry = path_entry_clone.clone();
+        let extract = Rc::clone(&extract);
+
+        glib::MainContext::default().spawn_local(async move {
+            let filter = gtk::FileFilter::new();
+            filter.add_pixbuf_formats();
+            let filters = gtk::gio::ListStore::new::<gtk::FileFilter>();
+            filters.append(&filter);
+
+            let dialog = FileDialog::builder()
+                .title(t!("palette.choose_image"))
+                .accept_label(t!("palette.open"))
+                .filters(&filters)
+                .build();
+
+            if let Ok(file) = dialog.open_future(Some(&dialog_window)).await
+                && let Some(path) = file.path()
+            {
+                path_entry.set_text(&path.to_string_lossy());
+                extract();
+            }
+        });
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    cancel_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    apply_button.connect_clicked(move |_| {
+        let selection: Vec<usize> = dropdowns.iter().map(|d| d.selected() as usize).collect();
+        on_apply(build_changes(&palette.borrow(), &selection));
+        dialog_window_clone.close();
+    });
+
+    dialog_window.present();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_cut_separates_distinct_colors_largest_first() {
+        let mut pixels = vec![[250, 10, 10]; 4];
+        pixels.extend(vec![[10, 10, 250]; 2]);
+        pixels.extend(vec![[10, 250, 10]; 2]);
+        let colors = median_cut(pixels, 3);
+        assert_eq!(colors[0], [250, 10, 10]);
+        let mut rest = colors[1..].to_vec();
+        rest.sort();
+        assert_eq!(rest, vec![[10, 10, 250], [10, 250, 10]]);
+    }
+
+    #[test]
+    fn median_cut_averages_each_bucket() {
+        let pixels = vec![[0, 0, 0], [10, 20, 30], [200, 200, 200], [210, 220, 230]];
+        let mut colors = median_cut(pixels, 2);
+        colors.sort();
+        assert_eq!(colors, vec![[5, 10, 15], [205, 210, 215]]);
+    }
+
+    #[test]
+    fn median_cut_stops_when_no_bucket_can_be_split() {
+        assert_eq!(median_cut(vec![[30, 60, 90]; 8], 4), vec![[30, 60, 90]]);
+        assert_eq!(median_cut(vec![[1, 2, 3], [4, 5, 6]], 5).len(), 2);
+    }
+}
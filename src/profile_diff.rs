@@ -0,0 +1,481 @@
+use crate::{
+    categories::{CategoryKind, LAYOUT_BLOCKS, categories_of},
+    diff::value_label,
+    utils::{
+        MARGIN_NORMAL, expand_base_config, expand_source, get_config_path,
+        parse_top_level_option_lines, transform_config,
+    },
+};
+use gtk::{
+    Align, Box, Button, DropDown, Grid, Label, Orientation, ScrolledWindow, Window, prelude::*,
+};
+use rust_i18n::t;
+use std::{collections::BTreeMap, error::Error, rc::Rc};
+
+/// One side of a comparison.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffSource {
+    /// The main config without the profile, see [`expand_base_config`].
+    Base,
+    Profile(String),
+}
+
+impl DiffSource {
+    fn label(&self) -> String {
+        match self {
+            DiffSource::Base => t!("profile_diff.base_config").to_string(),
+            DiffSource::Profile(profile) => profile.clone(),
+        }
+    }
+
+    fn read(&self) -> Result<String, Box<dyn Error>> {
+        match self {
+            DiffSource::Base => expand_base_config(),
+            DiffSource::Profile(profile) => expand_source(&get_config_path(true, profile)),
+        }
+    }
+}
+
+/// An option or top-level row that differs between the two sides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffEntry {
+    /// `None` means the side does not set the option.
+    Option {
+        category: String,
+        key: String,
+        left: Option<String>,
+        right: Option<String>,
+    },
+    /// A row only one of the sides has.
+    Row {
+        category: String,
+        name: String,
+        value: String,
+        on_left: bool,
+    },
+}
+
+impl DiffEntry {
+    fn category(&self) -> &str {
+        match self {
+            DiffEntry::Option { category, .. } | DiffEntry::Row { category, .. } => category,
+        }
+    }
+}
+
+/// A change that makes the active profile match the other side for one entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffCopy {
+    Option {
+        category: String,
+        key: String,
+        value: String,
+    },
+    AddRow {
+        category: String,
+        name: String,
+        value: String,
+    },
+    RemoveRow {
+        category: String,
+        name: String,
+        value: String,
+    },
+}
+
+/// Category and option name of a `block:option` path, as hyprviz keys them.
+pub fn option_key(path: &str) -> Option<(&'static str, String)> {
+    let (block, name) = path.split_once(':')?;
+    if let Some(category) = categories_of(CategoryKind::Options).find(|c| *c == block) {
+        Some((category, name.to_string()))
+    } else if LAYOUT_BLOCKS.contains(&block) {
+        Some(("layouts", path.to_string()))
+    } else {
+        None
+    }
+}
+
+/// Whether `category` is a page of options rather than of top-level rows.
+pub fn is_option_category(category: &str) -> bool {
+    category == "layouts" || categories_of(CategoryKind::Options).any(|c| c == category)
+}
+
+/// The page a top-level row named `name` is edited on. Rows no row page picks by how their
+/// name starts are only shown on the `top_level` page.
+pub fn row_category(name: &str) -> &'static str {
+    if name.starts_with("unbind") {
+        return "bind";
+    }
+    if name.starts_with("bezier") {
+        return "animation";
+    }
+    categories_of(CategoryKind::Rows)
+        .find(|category| name.starts_with(category))
+        .unwrap_or("top_level")
+}
+
+/// Options (the last definition wins, as in Hyprland) and top-level rows of `config`.
+pub fn summarize(config: &str) -> (BTreeMap<(String, String), String>, Vec<(String, String)>) {
+    let mut options = BTreeMap::new();
+    for line in transform_config(config.to_string()).lines() {
+        if let Some((path, value)) = line.split_once(" = ")
+            && let Some((category, name)) = option_key(path.trim())
+        {
+            options.insert((category.to_string(), name), value.trim().to_string());
+        }
+    }
+
+    let rows = parse_top_level_option_lines(config, false)
+        .into_iter()
+        .map(|(_, (name, value))| (name, value.trim_end().to_string()))
+        .collect();
+
+    (options, rows)
+}
+
+/// Options with different values and rows only one side has, in key order.
+pub fn diff_configs(left: &str, right: &str) -> Vec<DiffEntry> {
+    let (left_options, left_rows) = summarize(left);
+    let (right_options, mut right_rows) = summarize(right);
+
+    let mut keys: Vec<&(String, String)> =
+        left_options.keys().chain(right_options.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut entries: Vec<DiffEntry> = keys
+        .into_iter()
+        .filter_map(|key| {
+            let (left, right) = (left_options.get(key), right_options.get(key));
+            (left != right).then(|| DiffEntry::Option {
+                category: key.0.clone(),
+                key: key.1.clone(),
+                left: left.cloned(),
+                right: right.cloned(),
+            })
+        })
+        .collect();
+
+    // Rows are compared as a multiset, so a repeated line only matches once.
+    let mut row_entries = Vec::new();
+    for (name, value) in left_rows {
+        match right_rows
+            .iter()
+            .position(|(n, v)| *n == name && *v == value)
+        {
+            Some(index) => {
+                right_rows.remove(index);
+            }
+            None => row_entries.push((name, value, true)),
+        }
+    }
+    row_entries.extend(
+        right_rows
+            .into_iter()
+            .map(|(name, value)| (name, value, false)),
+    );
+    row_entries.sort();
+
+    entries.extend(
+        row_entries
+            .into_iter()
+            .map(|(name, value, on_left)| DiffEntry::Row {
+                category: row_category(&name).to_string(),
+                name,
+                value,
+                on_left,
+            }),
+    );
+
+    entries
+}
+
+/// What copying `entry` in the direction of `to_left` does to the target side, if anything.
+fn copy_action(entry: &DiffEntry, to_left: bool) -> Option<DiffCopy> {
+    match entry {
+        DiffEntry::Option {
+            category,
+            key,
+            left,
+            right,
+        } => {
+            let value = if to_left { right } else { left };
+            value.as_ref().map(|value| DiffCopy::Option {
+                category: category.clone(),
+                key: key.clone(),
+                value: value.clone(),
+            })
+        }
+        DiffEntry::Row {
+            category,
+            name,
+            value,
+            on_left,
+        } => {
+            let (category, name, value) = (category.clone(), name.clone(), value.clone());
+            Some(if *on_left == to_left {
+                DiffCopy::RemoveRow {
+                    category,
+                    name,
+                    value,
+                }
+            } else {
+                DiffCopy::AddRow {
+                    category,
+                    name,
+                    value,
+                }
+            })
+        }
+    }
+}
+
+type OnCopy = Rc<dyn Fn(&str, DiffCopy) -> bool>;
+
+/// Rebuilds `content` with the differences between `left` and `right`.
+fn fill_diff(
+    content: &Box,
+    summary_label: &Label,
+    left: &DiffSource,
+    right: &DiffSource,
+    active: &DiffSource,
+    categories: &[(String, &'static str)],
+    on_copy: &OnCopy,
+) {
+    while let Some(child) = content.first_child() {
+        content.remove(&child);
+    }
+
+    let (left_config, right_config) = match (left.read(), right.read()) {
+        (Ok(left_config), Ok(right_config)) => (left_config, right_config),
+        (Err(e), _) | (_, Err(e)) => {
+            summary_label.set_label(&t!("profile_diff.failed_to_read_", error = e));
+            return;
+        }
+    };
+
+    let entries = diff_configs(&left_config, &right_config);
+    let option_count = entries
+        .iter()
+        .filter(|entry| matches!(entry, DiffEntry::Option { .. }))
+        .count();
+    summary_label.set_label(&if entries.is_empty() {
+        t!("profile_diff.identical").to_string()
+    } else {
+        t!(
+            "profile_diff.summary_",
+            options = option_count,
+            rows = entries.len() - option_count
+        )
+        .to_string()
+    });
+
+    for (display_name, category) in categories {
+        let category_entries: Vec<&DiffEntry> = entries
+            .iter()
+            .filter(|entry| entry.category() == *category)
+            .collect();
+        if category_entries.is_empty() {
+            continue;
+        }
+
+        let title_label = Label::new(Some(display_name.as_str()));
+        title_label.set_halign(Align::Start);
+        title_label.set_margin_top(MARGIN_NORMAL / 2);
+        title_label.add_css_class("heading");
+        content.append(&title_label);
+
+        let grid = Grid::new();
+        grid.set_column_spacing(12);
+        grid.set_row_spacing(4);
+
+        for (row, entry) in category_entries.into_iter().enumerate() {
+            let row = row as i32;
+            let (name, left_value, right_value) = match entry {
+                DiffEntry::Option {
+                    key, left, right, ..
+                } => (key.as_str(), left.as_deref(), right.as_deref()),
+                DiffEntry::Row {
+                    name,
+                    value,
+                    on_left,
+                    ..
+                } => {
+                    let value = Some(value.as_str());
+                    if *on_left {
+                        (name.as_str(), value, None)
+                    } else {
+                        (name.as_str(), None, value)
+                    }
+                }
+            };
+
+            let name_label = Label::new(Some(name));
+            name_label.set_halign(Align::Start);
+            name_label.add_css_class("dim-label");
+            grid.attach(&name_label, 0, row, 1, 1);
+            let not_set = t!("profile_diff.not_set");
+            grid.attach(&value_label(left_value, &not_set), 1, row, 1, 1);
+            grid.attach(&value_label(right_value, &not_set), 4, row, 1, 1);
+
+            let to_right_button = Button::from_icon_name("go-next-symbolic");
+            let to_left_button = Button::from_icon_name("go-previous-symbolic");
+            let buttons = [to_right_button.clone(), to_left_button.clone()];
+
+            for (button, to_left) in [(to_right_button, false), (to_left_button, true)] {
+                button.add_css_class("flat");
+                button.set_valign(Align::Start);
+                grid.attach(&button, if to_left { 3 } else { 2 }, row, 1, 1);
+
+                let (source, target) = if to_left {
+                    (right, left)
+                } else {
+                    (left, right)
+                };
+                let Some(action) = copy_action(entry, to_left) else {
+                    button.set_sensitive(false);
+                    continue;
+                };
+                if target != active {
+                    button.set_sensitive(false);
+                    button.set_tooltip_text(Some(&t!(
+                        "profile_diff.only_active_profile_",
+                        name = active.label()
+                    )));
+                    continue;
+                }
+                button.set_tooltip_text(Some(&t!("profile_diff.use_in_", name = target.label())));
+
+                let source_label = source.label();
+                let buttons = buttons.clone();
+                let on_copy = on_copy.clone();
+                button.connect_clicked(move |button| {
+                    if on_copy(&source_label, action.clone()) {
+                        for button in &buttons {
+                            button.set_sensitive(false);
+                        }
+                        button.set_icon_name("object-select-symbolic");
+                        button.set_tooltip_text(Some(&t!("profile_diff.copied")));
+                    }
+                });
+            }
+        }
+
+        content.append(&grid);
+    }
+}
+
+/// Compares two profiles, or a profile and the base config, side by side. Entries can be
+/// copied into `active`; `on_copy` receives the name of the side they come from and returns
+/// whether the copy was applied.
+pub fn show_profile_diff<F>(
+    parent: &impl IsA<Window>,
+    profiles: Vec<String>,
+    active: &str,
+    categories: Vec<(String, &'static str)>,
+    on_copy: F,
+) where
+    F: Fn(&str, DiffCopy) -> bool + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(t!("profile_diff.title").to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(860)
+        .default_height(600)
+        .build();
+
+    let main_box = Box::new(Orientation::Vertical, 10);
+    main_box.set_margin_top(MARGIN_NORMAL);
+    main_box.set_margin_bottom(MARGIN_NORMAL);
+    main_box.set_margin_start(MARGIN_NORMAL);
+    main_box.set_margin_end(MARGIN_NORMAL);
+
+    let mut sources = vec![DiffSource::Base];
+    sources.extend(profiles.into_iter().map(DiffSource::Profile));
+    let labels: Vec<String> = sources.iter().map(DiffSource::label).collect();
+    let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+
+    let active = DiffSource::Profile(active.to_string());
+    let left_index = sources.iter().position(|s| *s == active).unwrap_or(0);
+    // Compare against another profile if there is one, otherwise against the base config.
+    let right_index = (1..sources.len())
+        .find(|index| *index != left_index)
+        .unwrap_or(0);
+
+    let sides_box = Box::new(Orientation::Horizontal, 6);
+    let left_dropdown = DropDown::from_strings(&labels);
+    left_dropdown.set_selected(left_index as u32);
+    left_dropdown.set_hexpand(true);
+    let right_dropdown = DropDown::from_strings(&labels);
+    right_dropdown.set_selected(right_index as u32);
+    right_dropdown.set_hexpand(true);
+    sides_box.append(&left_dropdown);
+    sides_box.append(&Label::new(Some("↔")));
+    sides_box.append(&right_dropdown);
+    main_box.append(&sides_box);
+
+    let summary_label = Label::new(None);
+    summary_label.set_halign(Align::Start);
+    summary_label.set_wrap(true);
+    summary_label.add_css_class("heading");
+    main_box.append(&summary_label);
+
+    let content = Box::new(Orientation::Vertical, 6);
+    let scrolled_window = ScrolledWindow::new();
+    scrolled_window.set_vexpand(true);
+    scrolled_window.set_child(Some(&content));
+    main_box.append(&scrolled_window);
+
+    let hint_label = Label::new(Some(&t!("profile_diff.hint")));
+    hint_label.set_halign(Align::Start);
+    hint_label.set_xalign(0.0);
+    hint_label.set_wrap(true);
+    hint_label.add_css_class("dim-label");
+    hint_label.add_css_class("caption");
+    main_box.append(&hint_label);
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+    let close_button = Button::with_label(&t!("profile_diff.close"));
+    buttons_box.append(&close_button);
+    main_box.append(&buttons_box);
+
+    dialog_window.set_child(Some(&main_box));
+
+    let on_copy: OnCopy = Rc::new(on_copy);
+    let refresh: Rc<dyn Fn()> = {
+        let left_dropdown = left_dropdown.clone();
+        let right_dropdown = right_dropdown.clone();
+        Rc::new(move || {
+            let side = |dropdown: &DropDown| {
+                sources
+                    .get(dropdown.selected() as usize)
+                    .cloned()
+                    .unwrap_or(DiffSource::Base)
+            };
+            fill_diff(
+                &content,
+                &summary_label,
+                &side(&left_dropdown),
+                &side(&right_dropdown),
+                &active,
+                &categories,
+                &on_copy,
+            );
+        })
+    };
+    refresh();
+
+    let refresh_clone = refresh.clone();
+    left_dropdown.connect_selected_notify(move |_| refresh_clone());
+    right_dropdown.connect_selected_notify(move |_| refresh());
+
+    let dialog_window_clone = dialog_window.clone();
+    close_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    dialog_window.present();
+}
@@ -0,0 +1,260 @@
+use crate::utils::{MARGIN_NORMAL, ProfileRule, ProfileRules, get_available_monitors};
+use gtk::{
+    Align, Box, Button, DropDown, Entry, Label, ListBox, ListBoxRow, MenuButton, Orientation,
+    Popover, ScrolledWindow, SelectionMode, Window, prelude::*,
+};
+use rust_i18n::t;
+use std::{cell::RefCell, rc::Rc};
+
+/// Widgets of one rule row.
+struct RuleRow {
+    row: ListBoxRow,
+    monitor_entry: Entry,
+    condition_dropdown: DropDown,
+    profile_dropdown: DropDown,
+}
+
+impl RuleRow {
+    fn to_rule(&self, profiles: &[String]) -> Option<ProfileRule> {
+        let monitor = self.monitor_entry.text().trim().to_string();
+        let profile = profiles.get(self.profile_dropdown.selected() as usize)?;
+        (!monitor.is_empty()).then(|| ProfileRule {
+            monitor,
+            connected: self.condition_dropdown.selected() == 0,
+            profile: profile.clone(),
+        })
+    }
+}
+
+/// A button listing the connected monitors; picking one fills `entry`.
+fn monitor_picker(entry: &Entry, monitors: &[String]) -> MenuButton {
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::None);
+    for monitor in monitors {
+        let label = Label::new(Some(monitor));
+        label.set_halign(Align::Start);
+        label.set_margin_start(MARGIN_NORMAL / 2);
+        label.set_margin_end(MARGIN_NORMAL / 2);
+        label.set_margin_top(MARGIN_NORMAL / 3);
+        label.set_margin_bottom(MARGIN_NORMAL / 3);
+        list_box.append(&label);
+    }
+
+    let popover = Popover::new();
+    popover.set_child(Some(&list_box));
+
+    let menu_button = MenuButton::new();
+    menu_button.set_icon_name("video-display-symbolic");
+    menu_button.set_tooltip_text(Some(&t!("profile_rules.connected_monitors")));
+    menu_button.set_popover(Some(&popover));
+    menu_button.set_sensitive(!monitors.is_empty());
+
+    let entry = entry.clone();
+    let monitors = monitors.to_vec();
+    list_box.connect_row_activated(move |_, row| {
+        if let Some(monitor) = monitors.get(row.index() as usize) {
+            entry.set_text(monitor);
+        }
+        popover.popdown();
+    });
+
+    menu_button
+}
+
+fn append_rule_row(
+    list_box: &ListBox,
+    rows: &Rc<RefCell<Vec<RuleRow>>>,
+    rule: Option<&ProfileRule>,
+    profiles: &[String],
+    monitors: &[String],
+) {
+    let row_box = Box::new(Orientation::Horizontal, 6);
+    row_box.set_margin_start(MARGIN_NORMAL / 2);
+    row_box.set_margin_end(MARGIN_NORMAL / 2);
+    row_box.set_margin_top(MARGIN_NORMAL / 3);
+    row_box.set_margin_bottom(MARGIN_NORMAL / 3);
+
+    let if_label = Label::new(Some(&t!("profile_rules.if_monitor")));
+    row_box.append(&if_label);
+
+    let monitor_entry = Entry::new();
+    monitor_entry.set_placeholder_text(Some("desc:Dell U2720Q"));
+    monitor_entry.set_hexpand(true);
+    row_box.append(&monitor_entry);
+    row_box.append(&monitor_picker(&monitor_entry, monitors));
+
+    let condition_dropdown = DropDown::from_strings(&[
+        &t!("profile_rules.is_connected"),
+        &t!("profile_rules.is_not_connected"),
+    ]);
+    row_box.append(&condition_dropdown);
+
+    let use_label = Label::new(Some(&t!("profile_rules.use_profile")));
+    row_box.append(&use_label);
+
+    let profile_names: Vec<&str> = profiles.iter().map(String::as_str).collect();
+    let profile_dropdown = DropDown::from_strings(&profile_names);
+    row_box.append(&profile_dropdown);
+
+    let remove_button = Button::from_icon_name("list-remove-symbolic");
+    remove_button.set_tooltip_text(Some(&t!("profile_rules.remove_rule")));
+    remove_button.add_css_class("flat");
+    row_box.append(&remove_button);
+
+    if let Some(rule) = rule {
+        monitor_entry.set_text(&rule.monitor);
+        condition_dropdown.set_selected(if rule.connected { 0 } else { 1 });
+        if let Some(index) = profiles.iter().position(|p| *p == rule.profile) {
+            profile_dropdown.set_selected(index as u32);
+        }
+    }
+
+    let row = ListBoxRow::new();
+    row.set_child(Some(&row_box));
+    list_box.append(&row);
+
+    rows.borrow_mut().push(RuleRow {
+        row: row.clone(),
+        monitor_entry,
+        condition_dropdown,
+        profile_dropdown,
+    });
+
+    let list_box = list_box.clone();
+    let rows = rows.clone();
+    remove_button.connect_clicked(move |_| {
+        list_box.remove(&row);
+        rows.borrow_mut().retain(|rule_row| rule_row.row != row);
+    });
+}
+
+/// Edits the rules that pick a profile from the connected monitors. `on_save` receives the
+/// new rules.
+pub fn show_profile_rules_dialog<F>(
+    parent: &impl IsA<Window>,
+    rules: ProfileRules,
+    profiles: Vec<String>,
+    on_save: F,
+) where
+    F: Fn(ProfileRules) + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(t!("profile_rules.title").to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(720)
+        .default_height(420)
+        .build();
+
+    let main_box = Box::new(Orientation::Vertical, 10);
+    main_box.set_margin_top(MARGIN_NORMAL);
+    main_box.set_margin_bottom(MARGIN_NORMAL);
+    main_box.set_margin_start(MARGIN_NORMAL);
+    main_box.set_margin_end(MARGIN_NORMAL);
+
+    let description_label = Label::new(Some(&t!("profile_rules.description")));
+    description_label.set_halign(Align::Start);
+    description_label.set_xalign(0.0);
+    description_label.set_wrap(true);
+    main_box.append(&description_label);
+
+    let mut monitors: Vec<String> = get_available_monitors(false).into_iter().collect();
+    monitors.sort();
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::None);
+    list_box.add_css_class("boxed-list");
+
+    let rows = Rc::new(RefCell::new(Vec::new()));
+    for rule in &rules.rules {
+        append_rule_row(&list_box, &rows, Some(rule), &profiles, &monitors);
+    }
+
+    let scrolled_window = ScrolledWindow::new();
+    scrolled_window.set_vexpand(true);
+    scrolled_window.set_child(Some(&list_box));
+    main_box.append(&scrolled_window);
+
+    let add_button = Button::with_label(&t!("profile_rules.add_rule"));
+    add_button.set_halign(Align::Start);
+    main_box.append(&add_button);
+
+    let fallback_box = Box::new(Orientation::Horizontal, 6);
+    let fallback_label = Label::new(Some(&t!("profile_rules.otherwise_use")));
+    fallback_box.append(&fallback_label);
+    let mut fallback_names = vec![t!("profile_rules.keep_current_profile").to_string()];
+    fallback_names.extend(profiles.iter().cloned());
+    let fallback_names: Vec<&str> = fallback_names.iter().map(String::as_str).collect();
+    let fallback_dropdown = DropDown::from_strings(&fallback_names);
+    if let Some(index) = rules
+        .fallback
+        .as_ref()
+        .and_then(|fallback| profiles.iter().position(|p| p == fallback))
+    {
+        fallback_dropdown.set_selected(index as u32 + 1);
+    }
+    fallback_box.append(&fallback_dropdown);
+    main_box.append(&fallback_box);
+
+    let hint_label = Label::new(Some(&t!("profile_rules.daemon_hint")));
+    hint_label.set_halign(Align::Start);
+    hint_label.set_xalign(0.0);
+    hint_label.set_wrap(true);
+    hint_label.set_selectable(true);
+    hint_label.add_css_class("dim-label");
+    hint_label.add_css_class("caption");
+    main_box.append(&hint_label);
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+
+    let cancel_button = Button::with_label(&t!("profile_rules.cancel"));
+    let save_button = Button::with_label(&t!("profile_rules.save"));
+    save_button.add_css_class("suggested-action");
+    buttons_box.append(&cancel_button);
+    buttons_box.append(&save_button);
+    main_box.append(&buttons_box);
+
+    dialog_window.set_child(Some(&main_box));
+
+    let profiles = Rc::new(profiles);
+    let monitors = Rc::new(monitors);
+
+    let list_box_clone = list_box.clone();
+    let rows_clone = rows.clone();
+    let profiles_clone = profiles.clone();
+    add_button.connect_clicked(move |_| {
+        append_rule_row(
+            &list_box_clone,
+            &rows_clone,
+            None,
+            &profiles_clone,
+            &monitors,
+        );
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    cancel_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    save_button.connect_clicked(move |_| {
+        let rules = ProfileRules {
+            rules: rows
+                .borrow()
+                .iter()
+                .filter_map(|row| row.to_rule(&profiles))
+                .collect(),
+            fallback: (fallback_dropdown.selected() as usize)
+                .checked_sub(1)
+                .and_then(|index| profiles.get(index).cloned()),
+        };
+
+        dialog_window_clone.close();
+        on_save(rules);
+    });
+
+    dialog_window.present();
+}
@@ -0,0 +1,242 @@
+use crate::{
+    gui::format_change_time,
+    utils::{MARGIN_NORMAL, ProfileMeta, profile_children},
+};
+use gtk::{
+    Align, Box, Button, DropDown, Entry, Grid, Image, Label, Orientation, ToggleButton, Window,
+    prelude::*,
+};
+use rust_i18n::t;
+use std::rc::Rc;
+
+/// Icons a profile can be shown with in the header bar.
+pub const PROFILE_ICONS: &[&str] = &[
+    "computer-symbolic",
+    "video-display-symbolic",
+    "input-gaming-symbolic",
+    "applications-graphics-symbolic",
+    "weather-clear-night-symbolic",
+    "starred-symbolic",
+    "emblem-system-symbolic",
+];
+
+fn dialog_box() -> Box {
+    let dialog_box = Box::new(Orientation::Vertical, 10);
+    dialog_box.set_margin_top(MARGIN_NORMAL);
+    dialog_box.set_margin_bottom(MARGIN_NORMAL);
+    dialog_box.set_margin_start(MARGIN_NORMAL);
+    dialog_box.set_margin_end(MARGIN_NORMAL);
+    dialog_box
+}
+
+/// Asks for a profile name, e.g. to rename or duplicate a profile. The dialog stays open
+/// while `on_confirm` returns `false`.
+pub fn show_profile_name_dialog<F>(
+    parent: &impl IsA<Window>,
+    title: &str,
+    confirm_label: &str,
+    initial_name: &str,
+    on_confirm: F,
+) where
+    F: Fn(String) -> bool + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(title)
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(300)
+        .build();
+
+    let dialog_box = dialog_box();
+
+    let label = Label::new(Some(&t!("profiles.enter_profile_name")));
+    label.add_css_class("body");
+    let entry = Entry::new();
+    entry.set_text(initial_name);
+
+    dialog_box.append(&label);
+    dialog_box.append(&entry);
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+
+    let cancel_button = Button::with_label(&t!("profiles.cancel"));
+    let confirm_button = Button::with_label(confirm_label);
+    confirm_button.add_css_class("suggested-action");
+
+    buttons_box.append(&cancel_button);
+    buttons_box.append(&confirm_button);
+
+    dialog_box.append(&buttons_box);
+    dialog_window.set_child(Some(&dialog_box));
+
+    let dialog_window_clone = dialog_window.clone();
+    cancel_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    let on_confirm = Rc::new(on_confirm);
+
+    let dialog_window_clone = dialog_window.clone();
+    let entry_clone = entry.clone();
+    let on_confirm_clone = on_confirm.clone();
+    confirm_button.connect_clicked(move |_| {
+        if on_confirm_clone(entry_clone.text().trim().to_string()) {
+            dialog_window_clone.close();
+        }
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    entry.connect_activate(move |entry| {
+        if on_confirm(entry.text().trim().to_string()) {
+            dialog_window_clone.close();
+        }
+    });
+
+    dialog_window.present();
+    entry.grab_focus();
+}
+
+/// Shows the metadata of `profile` for editing. `parents` are the profiles it may inherit
+/// from without creating a cycle.
+pub fn show_profile_properties<F>(
+    parent: &impl IsA<Window>,
+    profile: &str,
+    meta: ProfileMeta,
+    parents: Vec<String>,
+    on_save: F,
+) where
+    F: Fn(ProfileMeta) + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(t!("profiles.properties_of_", name = profile).to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(420)
+        .build();
+
+    let dialog_box = dialog_box();
+
+    let grid = Grid::new();
+    grid.set_column_spacing(12);
+    grid.set_row_spacing(8);
+
+    let attach_title = |title: &str, row: i32| {
+        let label = Label::new(Some(title));
+        label.set_halign(Align::Start);
+        label.add_css_class("dim-label");
+        grid.attach(&label, 0, row, 1, 1);
+    };
+
+    attach_title(&t!("profiles.description"), 0);
+    let description_entry = Entry::new();
+    description_entry.set_text(&meta.description);
+    description_entry.set_hexpand(true);
+    grid.attach(&description_entry, 1, 0, 1, 1);
+
+    attach_title(&t!("profiles.icon"), 1);
+    let icons_box = Box::new(Orientation::Horizontal, 4);
+    let no_icon_button = ToggleButton::with_label(&t!("profiles.no_icon"));
+    no_icon_button.set_active(meta.icon.is_empty());
+    icons_box.append(&no_icon_button);
+    let mut icon_buttons = vec![(String::new(), no_icon_button.clone())];
+    for icon in PROFILE_ICONS {
+        let button = ToggleButton::new();
+        button.set_child(Some(&Image::from_icon_name(icon)));
+        button.set_group(Some(&no_icon_button));
+        button.set_active(meta.icon == *icon);
+        icons_box.append(&button);
+        icon_buttons.push((icon.to_string(), button));
+    }
+    grid.attach(&icons_box, 1, 1, 1, 1);
+
+    attach_title(&t!("profiles.inherits_from"), 2);
+    let mut parent_names = vec![t!("profiles.no_parent").to_string()];
+    parent_names.extend(parents.iter().cloned());
+    let parent_names: Vec<&str> = parent_names.iter().map(String::as_str).collect();
+    let parent_dropdown = DropDown::from_strings(&parent_names);
+    let selected_parent = meta
+        .extends
+        .as_ref()
+        .and_then(|extends| parents.iter().position(|p| p == extends))
+        .map_or(0, |index| index + 1);
+    parent_dropdown.set_selected(selected_parent as u32);
+    grid.attach(&parent_dropdown, 1, 2, 1, 1);
+
+    let children = profile_children(profile);
+    let info = [
+        (t!("profiles.created"), format_change_time(meta.created)),
+        (t!("profiles.modified"), format_change_time(meta.modified)),
+        (
+            t!("profiles.inherited_by"),
+            if children.is_empty() {
+                "—".to_string()
+            } else {
+                children.join(", ")
+            },
+        ),
+    ];
+    for (row, (title, value)) in info.into_iter().enumerate() {
+        let row = row as i32 + 3;
+        attach_title(&title, row);
+        let value_label = Label::new(Some(&value));
+        value_label.set_halign(Align::Start);
+        value_label.set_wrap(true);
+        value_label.set_selectable(true);
+        grid.attach(&value_label, 1, row, 1, 1);
+    }
+
+    dialog_box.append(&grid);
+
+    let hint_label = Label::new(Some(&t!("profiles.inheritance_hint")));
+    hint_label.set_halign(Align::Start);
+    hint_label.set_xalign(0.0);
+    hint_label.set_wrap(true);
+    hint_label.set_max_width_chars(50);
+    hint_label.add_css_class("dim-label");
+    hint_label.add_css_class("caption");
+    dialog_box.append(&hint_label);
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+
+    let cancel_button = Button::with_label(&t!("profiles.cancel"));
+    let save_button = Button::with_label(&t!("profiles.save"));
+    save_button.add_css_class("suggested-action");
+
+    buttons_box.append(&cancel_button);
+    buttons_box.append(&save_button);
+
+    dialog_box.append(&buttons_box);
+    dialog_window.set_child(Some(&dialog_box));
+
+    let dialog_window_clone = dialog_window.clone();
+    cancel_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    save_button.connect_clicked(move |_| {
+        let icon = icon_buttons
+            .iter()
+            .find(|(_, button)| button.is_active())
+            .map(|(icon, _)| icon.clone())
+            .unwrap_or_default();
+        let extends = match parent_dropdown.selected() {
+            0 => None,
+            index => parents.get(index as usize - 1).cloned(),
+        };
+
+        dialog_window_clone.close();
+        on_save(ProfileMeta {
+            description: description_entry.text().trim().to_string(),
+            icon,
+            extends,
+            ..meta.clone()
+        });
+    });
+
+    dialog_window.present();
+}
@@ -0,0 +1,430 @@
+use crate::{
+    advanced_editors::create_spin_button,
+    hyprland::{
+        EvaluationError, ExpressionContext, HyprExpression, WindowRuleEffectOrProp,
+        WindowRuleStaticEffect,
+    },
+    utils::{MARGIN_NORMAL, get_cursor_position, get_focused_monitor_geometry},
+};
+use gtk::{
+    Align, ArrowType, Box, Button, DrawingArea, Entry, Grid, Label, MenuButton, Orientation,
+    Popover, SpinButton,
+    cairo::{Context, FontSlant, FontWeight},
+    glib,
+    prelude::*,
+};
+use rust_i18n::t;
+use std::{cell::RefCell, rc::Rc, str::FromStr};
+
+const DEFAULT_MONITOR_SIZE: (f64, f64) = (1920.0, 1080.0);
+const DEFAULT_WINDOW_SIZE: (f64, f64) = (800.0, 600.0);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Placement {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+enum PlacementIssue {
+    DivisionByZero(String),
+    NonPositiveSize,
+    OutOfBounds,
+}
+
+struct PlacementResult {
+    placement: Placement,
+    has_move: bool,
+    has_size: bool,
+    issues: Vec<PlacementIssue>,
+}
+
+fn find_move_and_size(
+    value: &str,
+) -> (
+    Option<(HyprExpression, HyprExpression)>,
+    Option<(HyprExpression, HyprExpression)>,
+) {
+    let mut move_rule = None;
+    let mut size_rule = None;
+
+    for effect in value
+        .split(',')
+        .filter_map(|part| WindowRuleEffectOrProp::from_str(part).ok())
+    {
+        match effect {
+            WindowRuleEffectOrProp::StaticEffect(WindowRuleStaticEffect::Move(x, y)) => {
+                move_rule = Some((x, y))
+            }
+            WindowRuleEffectOrProp::StaticEffect(WindowRuleStaticEffect::Size(w, h)) => {
+                size_rule = Some((w, h))
+            }
+            _ => {}
+        }
+    }
+
+    (move_rule, size_rule)
+}
+
+fn evaluate_or(
+    expression: &HyprExpression,
+    context: &ExpressionContext,
+    fallback: f64,
+    issues: &mut Vec<PlacementIssue>,
+) -> f64 {
+    expression
+        .evaluate(context)
+        .unwrap_or_else(|EvaluationError::DivisionByZero(formula)| {
+            issues.push(PlacementIssue::DivisionByZero(formula));
+            fallback
+        })
+}
+
+/// Evaluates the `size` rule first and then the `move` rule, so that `window_w` and
+/// `window_h` in a move expression refer to the size the window ends up with.
+fn evaluate_placement(value: &str, mut context: ExpressionContext) -> PlacementResult {
+    let (move_rule, size_rule) = find_move_and_size(value);
+    let mut issues = Vec::new();
+
+    if let Some((w, h)) = &size_rule {
+        let width = evaluate_or(w, &context, context.window_w, &mut issues);
+        let height = evaluate_or(h, &context, context.window_h, &mut issues);
+        context.window_w = width;
+        context.window_h = height;
+        context.window_x = (context.monitor_w - width) / 2.0;
+        context.window_y = (context.monitor_h - height) / 2.0;
+    }
+
+    let (x, y) = match &move_rule {
+        Some((x, y)) => (
+            evaluate_or(x, &context, context.window_x, &mut issues),
+            evaluate_or(y, &context, context.window_y, &mut issues),
+        ),
+        None => (context.window_x, context.window_y),
+    };
+
+    let placement = Placement {
+        x,
+        y,
+        width: context.window_w,
+        height: context.window_h,
+    };
+
+    if placement.width <= 0.0 || placement.height <= 0.0 {
+        issues.push(PlacementIssue::NonPositiveSize);
+    } else if placement.x < 0.0
+        || placement.y < 0.0
+        || placement.x + placement.width > context.monitor_w
+        || placement.y + placement.height > context.monitor_h
+    {
+        issues.push(PlacementIssue::OutOfBounds);
+    }
+
+    PlacementResult {
+        placement,
+        has_move: move_rule.is_some(),
+        has_size: size_rule.is_some(),
+        issues,
+    }
+}
+
+fn draw_placement(
+    cr: &Context,
+    width: f64,
+    height: f64,
+    context: &ExpressionContext,
+    result: &PlacementResult,
+) -> Result<(), gtk::cairo::Error> {
+    let get_theme_color = |name: &str, default: (f64, f64, f64)| {
+        gtk::gdk::RGBA::parse(name)
+            .ok()
+            .map(|rgba| (rgba.red() as f64, rgba.green() as f64, rgba.blue() as f64))
+            .unwrap_or(default)
+    };
+    let (bg_r, bg_g, bg_b) = get_theme_color("@theme_bg_color", (0.18, 0.18, 0.2));
+    let (fg_r, fg_g, fg_b) = get_theme_color("@theme_fg_color", (0.9, 0.9, 0.9));
+    let (accent_r, accent_g, accent_b) =
+        get_theme_color("@theme_selected_bg_color", (0.2, 0.5, 1.0));
+    let (error_r, error_g, error_b) = get_theme_color("@error_color", (0.9, 0.2, 0.2));
+
+    cr.set_source_rgb(bg_r, bg_g, bg_b);
+    cr.paint()?;
+
+    if context.monitor_w <= 0.0 || context.monitor_h <= 0.0 {
+        return Ok(());
+    }
+
+    let placement = result.placement;
+    let bounds_x0 = placement.x.min(0.0);
+    let bounds_y0 = placement.y.min(0.0);
+    let bounds_x1 = (placement.x + placement.width).max(context.monitor_w);
+    let bounds_y1 = (placement.y + placement.height).max(context.monitor_h);
+
+    let margin = MARGIN_NORMAL as f64;
+    let scale = ((width - margin * 2.0) / (bounds_x1 - bounds_x0))
+        .min((height - margin * 2.0) / (bounds_y1 - bounds_y0))
+        .max(0.0);
+    let offset_x = (width - (bounds_x1 - bounds_x0) * scale) / 2.0 - bounds_x0 * scale;
+    let offset_y = (height - (bounds_y1 - bounds_y0) * scale) / 2.0 - bounds_y0 * scale;
+
+    cr.set_source_rgba(fg_r, fg_g, fg_b, 0.08);
+    cr.rectangle(
+        offset_x,
+        offset_y,
+        context.monitor_w * scale,
+        context.monitor_h * scale,
+    );
+    cr.fill_preserve()?;
+    cr.set_source_rgba(fg_r, fg_g, fg_b, 0.5);
+    cr.set_line_width(1.0);
+    cr.stroke()?;
+
+    let has_issue = !result.issues.is_empty();
+    let (r, g, b) = if has_issue {
+        (error_r, error_g, error_b)
+    } else {
+        (accent_r, accent_g, accent_b)
+    };
+
+    if placement.width > 0.0 && placement.height > 0.0 {
+        cr.set_source_rgba(r, g, b, 0.3);
+        cr.rectangle(
+            offset_x + placement.x * scale,
+            offset_y + placement.y * scale,
+            placement.width * scale,
+            placement.height * scale,
+        );
+        cr.fill_preserve()?;
+        cr.set_source_rgb(r, g, b);
+        cr.set_line_width(2.0);
+        cr.stroke()?;
+    }
+
+    let cursor_x = offset_x + context.cursor_x * scale;
+    let cursor_y = offset_y + context.cursor_y * scale;
+    cr.set_source_rgb(fg_r, fg_g, fg_b);
+    cr.set_line_width(1.5);
+    cr.move_to(cursor_x - 5.0, cursor_y);
+    cr.line_to(cursor_x + 5.0, cursor_y);
+    cr.move_to(cursor_x, cursor_y - 5.0);
+    cr.line_to(cursor_x, cursor_y + 5.0);
+    cr.stroke()?;
+
+    cr.select_font_face("Sans", FontSlant::Normal, FontWeight::Normal);
+    cr.set_font_size(11.0);
+    cr.set_source_rgba(fg_r, fg_g, fg_b, 0.8);
+    cr.move_to(offset_x + 4.0, offset_y + 14.0);
+    cr.show_text(&format!("{}×{}", context.monitor_w, context.monitor_h))?;
+
+    Ok(())
+}
+
+fn describe_result(result: &PlacementResult) -> String {
+    if !result.has_move && !result.has_size {
+        return t!("rule_preview.no_move_or_size").to_string();
+    }
+
+    let placement = result.placement;
+    let mut lines = vec![
+        t!(
+            "rule_preview.result",
+            x = format!("{:.0}", placement.x),
+            y = format!("{:.0}", placement.y),
+            width = format!("{:.0}", placement.width),
+            height = format!("{:.0}", placement.height)
+        )
+        .to_string(),
+    ];
+
+    for issue in &result.issues {
+        lines.push(match issue {
+            PlacementIssue::DivisionByZero(formula) => {
+                t!("rule_preview.division_by_zero", formula = formula).to_string()
+            }
+            PlacementIssue::NonPositiveSize => t!("rule_preview.non_positive_size").to_string(),
+            PlacementIssue::OutOfBounds => t!("rule_preview.out_of_bounds").to_string(),
+        });
+    }
+
+    lines.join("\n")
+}
+
+fn add_spin_row(grid: &Grid, row: i32, label: &str, first: &SpinButton, second: &SpinButton) {
+    let label = Label::new(Some(label));
+    label.set_halign(Align::Start);
+    grid.attach(&label, 0, row, 1, 1);
+    grid.attach(first, 1, row, 1, 1);
+    grid.attach(second, 2, row, 1, 1);
+}
+
+/// Creates a button that opens a popover showing where the `move` and `size` effects of
+/// the windowrule in `value_entry` place a window on a scaled monitor.
+pub fn create_window_rule_preview(value_entry: &Entry) -> MenuButton {
+    let menu_button = MenuButton::new();
+    menu_button.set_icon_name("video-display-symbolic");
+    menu_button.set_direction(ArrowType::Down);
+    menu_button.set_valign(Align::Center);
+    menu_button.set_tooltip_text(Some(&t!("rule_preview.placement_preview")));
+
+    let content_box = Box::new(Orientation::Vertical, 6);
+    content_box.set_margin_start(MARGIN_NORMAL / 2);
+    content_box.set_margin_end(MARGIN_NORMAL / 2);
+    content_box.set_margin_top(MARGIN_NORMAL / 2);
+    content_box.set_margin_bottom(MARGIN_NORMAL / 2);
+    content_box.set_width_request(420);
+
+    let title_label = Label::new(Some(&t!("rule_preview.placement_preview")));
+    title_label.set_halign(Align::Start);
+    title_label.add_css_class("heading");
+
+    let grid = Grid::new();
+    grid.set_row_spacing(6);
+    grid.set_column_spacing(6);
+
+    let monitor_w_spin = create_spin_button(1.0, 16384.0, 1.0);
+    let monitor_h_spin = create_spin_button(1.0, 16384.0, 1.0);
+    let window_w_spin = create_spin_button(1.0, 16384.0, 1.0);
+    let window_h_spin = create_spin_button(1.0, 16384.0, 1.0);
+    let cursor_x_spin = create_spin_button(0.0, 16384.0, 1.0);
+    let cursor_y_spin = create_spin_button(0.0, 16384.0, 1.0);
+
+    monitor_w_spin.set_value(DEFAULT_MONITOR_SIZE.0);
+    monitor_h_spin.set_value(DEFAULT_MONITOR_SIZE.1);
+    window_w_spin.set_value(DEFAULT_WINDOW_SIZE.0);
+    window_h_spin.set_value(DEFAULT_WINDOW_SIZE.1);
+    cursor_x_spin.set_value(DEFAULT_MONITOR_SIZE.0 / 2.0);
+    cursor_y_spin.set_value(DEFAULT_MONITOR_SIZE.1 / 2.0);
+
+    add_spin_row(
+        &grid,
+        0,
+        &t!("rule_preview.monitor_size"),
+        &monitor_w_spin,
+        &monitor_h_spin,
+    );
+    add_spin_row(
+        &grid,
+        1,
+        &t!("rule_preview.window_size"),
+        &window_w_spin,
+        &window_h_spin,
+    );
+    add_spin_row(
+        &grid,
+        2,
+        &t!("rule_preview.cursor_position"),
+        &cursor_x_spin,
+        &cursor_y_spin,
+    );
+
+    let live_button = Button::with_label(&t!("rule_preview.use_live_monitor"));
+    live_button.set_halign(Align::Start);
+
+    let drawing_area = DrawingArea::new();
+    drawing_area.set_content_height(220);
+    drawing_area.set_hexpand(true);
+
+    let status_label = Label::new(None);
+    status_label.set_halign(Align::Start);
+    status_label.set_wrap(true);
+    status_label.set_selectable(true);
+
+    content_box.append(&title_label);
+    content_box.append(&grid);
+    content_box.append(&live_button);
+    content_box.append(&drawing_area);
+    content_box.append(&status_label);
+
+    let spins = [
+        monitor_w_spin.clone(),
+        monitor_h_spin.clone(),
+        window_w_spin.clone(),
+        window_h_spin.clone(),
+        cursor_x_spin.clone(),
+        cursor_y_spin.clone(),
+    ];
+    let read_context = {
+        let spins = spins.clone();
+        move || {
+            let [monitor_w, monitor_h, window_w, window_h, cursor_x, cursor_y] =
+                spins.each_ref().map(|spin| spin.value());
+            ExpressionContext {
+                monitor_w,
+                monitor_h,
+                window_x: (monitor_w - window_w) / 2.0,
+                window_y: (monitor_h - window_h) / 2.0,
+                window_w,
+                window_h,
+                cursor_x,
+                cursor_y,
+            }
+        }
+    };
+
+    let result = Rc::new(RefCell::new(evaluate_placement(
+        &value_entry.text(),
+        read_context(),
+    )));
+
+    let result_clone = result.clone();
+    let read_context_clone = read_context.clone();
+    drawing_area.set_draw_func(move |_, cr, width, height| {
+        if let Err(e) = draw_placement(
+            cr,
+            width as f64,
+            height as f64,
+            &read_context_clone(),
+            &result_clone.borrow(),
+        ) {
+            glib::g_warning!("hyprviz", "Cairo draw error (windowrule preview): {}", e);
+        }
+    });
+
+    let update = {
+        let value_entry = value_entry.clone();
+        let drawing_area = drawing_area.clone();
+        let status_label = status_label.clone();
+        move || {
+            let new_result = evaluate_placement(&value_entry.text(), read_context());
+            status_label.set_text(&describe_result(&new_result));
+            if new_result.issues.is_empty() {
+                status_label.remove_css_class("error");
+            } else {
+                status_label.add_css_class("error");
+            }
+            *result.borrow_mut() = new_result;
+            drawing_area.queue_draw();
+        }
+    };
+    let update = Rc::new(update);
+    update();
+
+    let update_clone = update.clone();
+    value_entry.connect_changed(move |_| update_clone());
+
+    for spin in &spins {
+        let update_clone = update.clone();
+        spin.connect_value_changed(move |_| update_clone());
+    }
+
+    live_button.connect_clicked(move |_| {
+        let Some(monitor) = get_focused_monitor_geometry() else {
+            return;
+        };
+
+        monitor_w_spin.set_value(monitor.width);
+        monitor_h_spin.set_value(monitor.height);
+        if let Some((x, y)) = get_cursor_position() {
+            cursor_x_spin.set_value(x - monitor.x);
+            cursor_y_spin.set_value(y - monitor.y);
+        }
+        update();
+    });
+
+    let popover = Popover::builder().build();
+    popover.set_child(Some(&content_box));
+    menu_button.set_popover(Some(&popover));
+
+    menu_button
+}
@@ -0,0 +1,256 @@
+use crate::{
+    diff::{add_diff_tags, set_unified_diff_text, unified_diff},
+    utils::{MARGIN_NORMAL, split_origin_raw},
+};
+use gtk::{
+    Align, ApplicationWindow, Box, Button, CheckButton, Label, ListBox, Orientation, Paned,
+    ScrolledWindow, SelectionMode, TextView, Window, prelude::*,
+};
+use rust_i18n::t;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// One item of the review list. The name, value and delete keys of a top-level row are
+/// reviewed together, since saving only one of them would not make sense.
+pub struct PendingChange {
+    pub label: String,
+    pub keys: Vec<(String, String)>,
+}
+
+pub fn group_pending_changes(state: &HashMap<(String, String), String>) -> Vec<PendingChange> {
+    let mut rows: HashMap<(String, String), Vec<(String, String)>> = HashMap::new();
+    let mut result = Vec::new();
+
+    for (category, key) in state.keys() {
+        let row_raw = ["_name", "_value", "_delete"]
+            .iter()
+            .find_map(|suffix| key.strip_suffix(suffix));
+
+        match row_raw {
+            Some(raw) => rows
+                .entry((category.clone(), raw.to_string()))
+                .or_default()
+                .push((category.clone(), key.clone())),
+            None => result.push(PendingChange {
+                label: format!(
+                    "{category}:{key} = {}",
+                    state[&(category.clone(), key.clone())]
+                ),
+                keys: vec![(category.clone(), key.clone())],
+            }),
+        }
+    }
+
+    for ((category, raw), keys) in rows {
+        let get = |suffix: &str| state.get(&(category.clone(), format!("{raw}{suffix}")));
+
+        let origin = split_origin_raw(&raw);
+        let line = origin.as_ref().map_or(raw.as_str(), |(_, line)| line);
+
+        let label = if get("_delete").is_some() {
+            t!("save_review.remove_line_", category = category, line = line).to_string()
+        } else {
+            format!(
+                "{category}: {} = {}",
+                get("_name").map_or("…", |name| name.as_str()),
+                get("_value").map_or("…", |value| value.as_str())
+            )
+        };
+        let label = match &origin {
+            Some((path, _)) => t!(
+                "save_review.in_file_",
+                change = label,
+                file = path.display()
+            )
+            .to_string(),
+            None => label,
+        };
+
+        result.push(PendingChange { label, keys });
+    }
+
+    result.sort_by(|a, b| a.label.cmp(&b.label));
+    result
+}
+
+/// Shows a unified diff of every file `render` returns as `(label, original, updated)` for the
+/// checked changes. `on_save` receives the checked keys and whether Hyprland should be
+/// reloaded.
+pub fn show_save_review<R, S>(
+    parent: &ApplicationWindow,
+    changes: Vec<PendingChange>,
+    render: R,
+    on_save: S,
+) where
+    R: Fn(&[(String, String)]) -> Vec<(String, String, String)> + 'static,
+    S: Fn(Vec<(String, String)>, bool) + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(t!("save_review.review_changes").to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(760)
+        .default_height(640)
+        .build();
+
+    let main_box = Box::new(Orientation::Vertical, 10);
+    main_box.set_margin_top(MARGIN_NORMAL);
+    main_box.set_margin_bottom(MARGIN_NORMAL);
+    main_box.set_margin_start(MARGIN_NORMAL);
+    main_box.set_margin_end(MARGIN_NORMAL);
+
+    let summary_label = Label::new(None);
+    summary_label.set_halign(Align::Start);
+    summary_label.add_css_class("heading");
+    main_box.append(&summary_label);
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::None);
+    list_box.add_css_class("boxed-list");
+
+    let check_buttons: Vec<CheckButton> = changes
+        .iter()
+        .map(|change| {
+            let check_button = CheckButton::with_label(&change.label);
+            check_button.set_active(true);
+            check_button.set_margin_start(MARGIN_NORMAL / 2);
+            check_button.set_margin_end(MARGIN_NORMAL / 2);
+            list_box.append(&check_button);
+            check_button
+        })
+        .collect();
+
+    let list_scrolled_window = ScrolledWindow::new();
+    list_scrolled_window.set_min_content_height(120);
+    list_scrolled_window.set_child(Some(&list_box));
+
+    let text_view = TextView::new();
+    text_view.set_editable(false);
+    text_view.set_cursor_visible(false);
+    text_view.set_monospace(true);
+    add_diff_tags(&text_view.buffer());
+
+    let diff_scrolled_window = ScrolledWindow::new();
+    diff_scrolled_window.set_min_content_height(240);
+    diff_scrolled_window.set_child(Some(&text_view));
+
+    let paned = Paned::new(Orientation::Vertical);
+    paned.set_start_child(Some(&list_scrolled_window));
+    paned.set_end_child(Some(&diff_scrolled_window));
+    paned.set_position(180);
+    paned.set_vexpand(true);
+    main_box.append(&paned);
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+
+    let copy_button = Button::with_label(&t!("save_review.copy_diff"));
+    let cancel_button = Button::with_label(&t!("save_review.cancel"));
+    let save_no_reload_button = Button::with_label(&t!("save_review.save_without_reload"));
+    let save_button = Button::with_label(&t!("save_review.save"));
+    save_button.add_css_class("suggested-action");
+
+    buttons_box.append(&copy_button);
+    buttons_box.append(&cancel_button);
+    buttons_box.append(&save_no_reload_button);
+    buttons_box.append(&save_button);
+    main_box.append(&buttons_box);
+
+    dialog_window.set_child(Some(&main_box));
+
+    let changes = Rc::new(changes);
+    let current_diff = Rc::new(RefCell::new(String::new()));
+
+    let included_keys = {
+        let changes = changes.clone();
+        let check_buttons = check_buttons.clone();
+        move || -> Vec<(String, String)> {
+            changes
+                .iter()
+                .zip(&check_buttons)
+                .filter(|(_, check_button)| check_button.is_active())
+                .flat_map(|(change, _)| change.keys.iter().cloned())
+                .collect()
+        }
+    };
+
+    let update = {
+        let included_keys = included_keys.clone();
+        let current_diff = current_diff.clone();
+        let check_buttons = check_buttons.clone();
+        let save_button = save_button.clone();
+        let save_no_reload_button = save_no_reload_button.clone();
+        let copy_button = copy_button.clone();
+        let total = changes.len();
+        move || {
+            let keys = included_keys();
+            let diff = if keys.is_empty() {
+                String::new()
+            } else {
+                render(&keys)
+                    .into_iter()
+                    .map(|(label, original, updated)| {
+                        unified_diff(&original, &updated, &label, &label)
+                    })
+                    .collect()
+            };
+
+            if diff.is_empty() {
+                text_view
+                    .buffer()
+                    .set_text(&t!("save_review.no_textual_changes"));
+            } else {
+                set_unified_diff_text(&text_view.buffer(), &diff);
+            }
+            *current_diff.borrow_mut() = diff;
+
+            let included = check_buttons.iter().filter(|c| c.is_active()).count();
+            summary_label.set_text(&t!(
+                "save_review.included_changes_",
+                included = included,
+                total = total
+            ));
+            save_button.set_sensitive(!keys.is_empty());
+            save_no_reload_button.set_sensitive(!keys.is_empty());
+            copy_button.set_sensitive(!current_diff.borrow().is_empty());
+        }
+    };
+    let update = Rc::new(update);
+
+    for check_button in &check_buttons {
+        let update = update.clone();
+        check_button.connect_toggled(move |_| update());
+    }
+
+    update();
+
+    let dialog_window_clone = dialog_window.clone();
+    copy_button.connect_clicked(move |_| {
+        dialog_window_clone
+            .clipboard()
+            .set_text(&current_diff.borrow());
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    cancel_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    let on_save = Rc::new(on_save);
+
+    let dialog_window_clone = dialog_window.clone();
+    let included_keys_clone = included_keys.clone();
+    let on_save_clone = on_save.clone();
+    save_no_reload_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+        on_save_clone(included_keys_clone(), false);
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    save_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+        on_save(included_keys(), true);
+    });
+
+    dialog_window.present();
+}
@@ -0,0 +1,175 @@
+use gtk::Widget;
+
+/// How a field of a [`SearchItem`] is matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Titles and option paths, matched fuzzily.
+    Name,
+    /// Values and category names, matched as substrings.
+    Context,
+    /// Descriptions and guides, matched as substrings with the lowest weight.
+    Text,
+}
+
+impl FieldKind {
+    fn weight(self) -> i64 {
+        match self {
+            FieldKind::Name => 3,
+            FieldKind::Context => 2,
+            FieldKind::Text => 1,
+        }
+    }
+}
+
+/// Something search can jump to: an option, a top-level row, a guide or a category page.
+#[derive(Clone)]
+pub struct SearchItem {
+    pub category: String,
+    pub title: String,
+    /// Where the item is, shown below the title.
+    pub subtitle: String,
+    pub fields: Vec<(FieldKind, String)>,
+    /// Widget to scroll to.
+    pub widget: Widget,
+}
+
+fn is_boundary(previous: Option<char>) -> bool {
+    previous.is_none_or(|c| !c.is_alphanumeric())
+}
+
+/// Scores `term` as a substring of `text`, preferring matches at the start of a word.
+fn substring_score(term: &[char], text: &[char]) -> Option<i64> {
+    if term.is_empty() || term.len() > text.len() {
+        return None;
+    }
+
+    (0..=text.len() - term.len())
+        .filter(|&start| text[start..start + term.len()] == *term)
+        .map(|start| {
+            let mut score = 100 + term.len() as i64 * 10;
+            if is_boundary(start.checked_sub(1).map(|i| text[i])) {
+                score += 50;
+            }
+            if start == 0 {
+                score += 25;
+            }
+            score - (text.len() - term.len()).min(50) as i64 / 5
+        })
+        .max()
+}
+
+/// Scores `term` as a subsequence of `text`: consecutive characters and characters starting
+/// a word count more, gaps count against it.
+fn subsequence_score(term: &[char], text: &[char]) -> Option<i64> {
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for c in term {
+        let offset = text[position..].iter().position(|t| t == c)?;
+        let index = position + offset;
+
+        score += 5;
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            score += 10;
+        } else if previous_match.is_some() {
+            score -= offset.min(10) as i64;
+        }
+        if is_boundary(index.checked_sub(1).map(|i| text[i])) {
+            score += 10;
+        }
+
+        previous_match = Some(index);
+        position = index + 1;
+    }
+
+    Some(score)
+}
+
+/// How well `term`, already lowercased, matches a field, or `None` if it does not.
+fn field_score(term: &[char], kind: FieldKind, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let score = match kind {
+        FieldKind::Name => substring_score(term, &text).or_else(|| subsequence_score(term, &text)),
+        FieldKind::Context | FieldKind::Text => substring_score(term, &text),
+    }?;
+    Some(score * kind.weight())
+}
+
+/// Scores `fields` for `query`. Every word of the query has to match one of them.
+pub fn score_fields(fields: &[(FieldKind, String)], query: &str) -> Option<i64> {
+    let mut total = 0;
+    for term in query.split_whitespace() {
+        let term: Vec<char> = term.to_lowercase().chars().collect();
+        total += fields
+            .iter()
+            .filter_map(|(kind, text)| field_score(&term, *kind, text))
+            .max()?;
+    }
+    Some(total)
+}
+
+/// The best `limit` matches for `query`, best first.
+pub fn rank<'a>(items: &'a [SearchItem], query: &str, limit: usize) -> Vec<&'a SearchItem> {
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let mut scored: Vec<(i64, &SearchItem)> = items
+        .iter()
+        .filter_map(|item| score_fields(&item.fields, query).map(|score| (score, item)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.title.cmp(&b.1.title)));
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, item)| item)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(text: &str) -> Vec<(FieldKind, String)> {
+        vec![(FieldKind::Name, text.to_string())]
+    }
+
+    #[test]
+    fn word_start_matches_rank_above_subsequences() {
+        let word = score_fields(&name("general:gaps_in"), "gaps").unwrap();
+        let inside = score_fields(&name("general:biggaps"), "gaps").unwrap();
+        let subsequence = score_fields(&name("general:gap_size"), "gaps").unwrap();
+        assert!(word > inside, "{word} {inside}");
+        assert!(inside > subsequence, "{inside} {subsequence}");
+    }
+
+    #[test]
+    fn every_word_of_the_query_has_to_match() {
+        let fields = vec![
+            (FieldKind::Name, "decoration:blur:size".to_string()),
+            (FieldKind::Text, "Blur size, in pixels".to_string()),
+        ];
+        assert!(score_fields(&fields, "blur pixels").is_some());
+        assert!(score_fields(&fields, "blur opacity").is_none());
+    }
+
+    #[test]
+    fn context_and_text_fields_are_not_matched_fuzzily() {
+        let fields = vec![(FieldKind::Text, "general gaps".to_string())];
+        assert!(score_fields(&fields, "gaps").is_some());
+        assert!(score_fields(&fields, "ggp").is_none());
+        assert!(score_fields(&name("general gaps"), "ggp").is_some());
+    }
+
+    #[test]
+    fn name_matches_outweigh_text_matches() {
+        let in_name = score_fields(&name("input:follow_mouse"), "mouse").unwrap();
+        let in_text = score_fields(
+            &[(FieldKind::Text, "input:follow_mouse".to_string())],
+            "mouse",
+        )
+        .unwrap();
+        assert!(in_name > in_text);
+    }
+}
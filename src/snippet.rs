@@ -0,0 +1,402 @@
+use crate::{
+    config_document::option_path,
+    export::{ExportRow, HyprvizExport},
+    profile_diff::option_key,
+    utils::{MARGIN_NORMAL, mute_stdout, transform_config},
+};
+use gtk::{
+    Align, Box, Button, CheckButton, DropDown, Label, Orientation, ScrolledWindow, TextView,
+    Window, prelude::*,
+};
+use hyprparser::parse_config;
+use rust_i18n::t;
+use std::{cell::RefCell, rc::Rc};
+
+/// Puts blocks opened or closed mid-line, as in `general { gaps_in = 5 }`, on lines of their
+/// own, since hyprlang only reads one statement per line.
+fn split_blocks(text: &str) -> String {
+    let mut lines = Vec::new();
+    let mut depth = 0;
+
+    for line in text.lines() {
+        let mut rest = line.trim();
+        loop {
+            if rest.starts_with('#') {
+                break;
+            }
+            if let Some((name, after)) = rest.split_once('{')
+                && !name.trim().is_empty()
+                && !name.contains('=')
+            {
+                lines.push(format!("{} {{", name.trim()));
+                depth += 1;
+                rest = after.trim();
+            } else if depth > 0
+                && let Some(after) = rest.strip_prefix('}')
+            {
+                lines.push("}".to_string());
+                depth -= 1;
+                rest = after.trim();
+            } else {
+                break;
+            }
+        }
+
+        // Braces closing a value belong to it, like in `exec, awk '{print}'`.
+        let mut closing = 0;
+        while closing < depth
+            && rest.ends_with('}')
+            && rest.matches('{').count() < rest.matches('}').count()
+        {
+            rest = rest[..rest.len() - 1].trim_end();
+            closing += 1;
+        }
+
+        if !rest.is_empty() {
+            lines.push(rest.to_string());
+        }
+        lines.extend((0..closing).map(|_| "}".to_string()));
+        depth -= closing;
+    }
+
+    lines.join("\n")
+}
+
+/// Options and rows of a pasted hyprlang snippet, with the paths of options no page edits.
+/// `source` lines are skipped, so a pasted snippet never reads local files.
+pub fn parse_snippet(text: &str) -> (HyprvizExport, Vec<String>) {
+    let text = split_blocks(text);
+    let (sources, lines): (Vec<&str>, Vec<&str>) = text.lines().partition(|line| {
+        line.split_once('=')
+            .is_some_and(|(key, _)| key.trim() == "source")
+    });
+    let config = mute_stdout(|| parse_config(&lines.join("\n"))).to_string();
+
+    let mut unknown: Vec<String> = sources.iter().map(|line| line.to_string()).collect();
+    unknown.extend(transform_config(config.clone()).lines().filter_map(|line| {
+        let (path, _) = line.split_once(" = ")?;
+        let path = path.trim();
+        (path.contains(':') && option_key(path).is_none()).then(|| path.to_string())
+    }));
+
+    (HyprvizExport::from_config("", &config), unknown)
+}
+
+/// Writes options, given by category and name, as nested blocks, followed by `rows`.
+pub fn to_hyprlang(options: &[(String, String, String)], rows: &[ExportRow]) -> String {
+    let mut options: Vec<(Vec<String>, &str)> = options
+        .iter()
+        .map(|(category, key, value)| {
+            let path = option_path(category, key);
+            (
+                path.split(':').map(str::to_string).collect(),
+                value.as_str(),
+            )
+        })
+        .collect();
+    options.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut out = String::new();
+    let mut open: Vec<String> = Vec::new();
+    for (path, value) in options {
+        let (name, blocks) = path.split_last().expect("option paths are never empty");
+        let shared = open
+            .iter()
+            .zip(blocks)
+            .take_while(|(open, block)| open == block)
+            .count();
+
+        while open.len() > shared {
+            open.pop();
+            out.push_str(&format!("{}}}\n", "    ".repeat(open.len())));
+        }
+        for block in &blocks[shared..] {
+            out.push_str(&format!("{}{} {{\n", "    ".repeat(open.len()), block));
+            open.push(block.clone());
+        }
+        out.push_str(&format!(
+            "{}{} = {}\n",
+            "    ".repeat(open.len()),
+            name,
+            value
+        ));
+    }
+    while !open.is_empty() {
+        open.pop();
+        out.push_str(&format!("{}}}\n", "    ".repeat(open.len())));
+    }
+
+    if !out.is_empty() && !rows.is_empty() {
+        out.push('\n');
+    }
+    for row in rows {
+        out.push_str(&format!("{} = {}\n", row.name, row.value));
+    }
+
+    out
+}
+
+/// Asks for a hyprlang snippet and passes its text to `on_preview`.
+pub fn show_paste_snippet<F>(parent: &impl IsA<Window>, on_preview: F)
+where
+    F: Fn(&str) + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(t!("snippet.paste_title").to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(640)
+        .default_height(420)
+        .build();
+
+    let main_box = Box::new(Orientation::Vertical, 10);
+    main_box.set_margin_top(MARGIN_NORMAL);
+    main_box.set_margin_bottom(MARGIN_NORMAL);
+    main_box.set_margin_start(MARGIN_NORMAL);
+    main_box.set_margin_end(MARGIN_NORMAL);
+
+    let hint_label = Label::new(Some(&t!("snippet.paste_hint")));
+    hint_label.set_halign(Align::Start);
+    hint_label.set_xalign(0.0);
+    hint_label.set_wrap(true);
+    hint_label.add_css_class("dim-label");
+    main_box.append(&hint_label);
+
+    let text_view = TextView::new();
+    text_view.set_monospace(true);
+    text_view.set_top_margin(MARGIN_NORMAL / 2);
+    text_view.set_left_margin(MARGIN_NORMAL / 2);
+
+    let scrolled_window = ScrolledWindow::new();
+    scrolled_window.set_vexpand(true);
+    scrolled_window.add_css_class("frame");
+    scrolled_window.set_child(Some(&text_view));
+    main_box.append(&scrolled_window);
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+    let cancel_button = Button::with_label(&t!("snippet.cancel"));
+    let preview_button = Button::with_label(&t!("snippet.preview"));
+    preview_button.add_css_class("suggested-action");
+    preview_button.set_sensitive(false);
+    buttons_box.append(&cancel_button);
+    buttons_box.append(&preview_button);
+    main_box.append(&buttons_box);
+
+    dialog_window.set_child(Some(&main_box));
+
+    let preview_button_clone = preview_button.clone();
+    text_view.buffer().connect_changed(move |buffer| {
+        let (start, end) = buffer.bounds();
+        preview_button_clone.set_sensitive(!buffer.text(&start, &end, false).trim().is_empty());
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    cancel_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    preview_button.connect_clicked(move |_| {
+        let buffer = text_view.buffer();
+        let (start, end) = buffer.bounds();
+        let text = buffer.text(&start, &end, false);
+        dialog_window_clone.close();
+        on_preview(&text);
+    });
+
+    text_view.grab_focus();
+    dialog_window.present();
+}
+
+/// One option or row that can be copied.
+enum CopyItem {
+    Option(String, String, String),
+    Row(ExportRow),
+}
+
+/// Shows the options and rows of `export` as hyprlang. A single entry, a whole category or
+/// everything can be picked and copied to the clipboard.
+pub fn show_copy_hyprlang(
+    parent: &impl IsA<Window>,
+    export: &HyprvizExport,
+    categories: Vec<(String, &'static str)>,
+) {
+    let dialog_window = Window::builder()
+        .title(t!("snippet.copy_title").to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(860)
+        .default_height(560)
+        .build();
+
+    let main_box = Box::new(Orientation::Vertical, 10);
+    main_box.set_margin_top(MARGIN_NORMAL);
+    main_box.set_margin_bottom(MARGIN_NORMAL);
+    main_box.set_margin_start(MARGIN_NORMAL);
+    main_box.set_margin_end(MARGIN_NORMAL);
+
+    // Only categories the profile sets something in, in sidebar order.
+    let categories: Vec<(String, &'static str)> = categories
+        .into_iter()
+        .filter(|(_, category)| {
+            export.options.contains_key(*category) || export.rows.contains_key(*category)
+        })
+        .collect();
+
+    let mut labels = vec![t!("snippet.all_categories").to_string()];
+    labels.extend(categories.iter().map(|(name, _)| name.clone()));
+    let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+
+    let top_box = Box::new(Orientation::Horizontal, 6);
+    let category_dropdown = DropDown::from_strings(&labels);
+    category_dropdown.set_hexpand(true);
+    category_dropdown.set_halign(Align::Start);
+    let select_all_button = Button::with_label(&t!("snippet.select_all"));
+    let select_none_button = Button::with_label(&t!("snippet.select_none"));
+    top_box.append(&category_dropdown);
+    top_box.append(&select_all_button);
+    top_box.append(&select_none_button);
+    main_box.append(&top_box);
+
+    let items_box = Box::new(Orientation::Vertical, 2);
+    let items_scrolled_window = ScrolledWindow::new();
+    items_scrolled_window.set_min_content_width(320);
+    items_scrolled_window.set_child(Some(&items_box));
+
+    let text_view = TextView::new();
+    text_view.set_editable(false);
+    text_view.set_monospace(true);
+    text_view.set_top_margin(MARGIN_NORMAL / 2);
+    text_view.set_left_margin(MARGIN_NORMAL / 2);
+
+    let text_scrolled_window = ScrolledWindow::new();
+    text_scrolled_window.set_hexpand(true);
+    text_scrolled_window.set_child(Some(&text_view));
+
+    let paned = gtk::Paned::new(Orientation::Horizontal);
+    paned.set_start_child(Some(&items_scrolled_window));
+    paned.set_end_child(Some(&text_scrolled_window));
+    paned.set_position(360);
+    paned.set_vexpand(true);
+    main_box.append(&paned);
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+    let close_button = Button::with_label(&t!("snippet.close"));
+    let copy_button = Button::with_label(&t!("snippet.copy"));
+    copy_button.add_css_class("suggested-action");
+    buttons_box.append(&close_button);
+    buttons_box.append(&copy_button);
+    main_box.append(&buttons_box);
+
+    dialog_window.set_child(Some(&main_box));
+
+    let items: Rc<RefCell<Vec<(CheckButton, CopyItem)>>> = Rc::new(RefCell::new(Vec::new()));
+    let text = Rc::new(RefCell::new(String::new()));
+
+    let update_text = {
+        let items = items.clone();
+        let text = text.clone();
+        let copy_button = copy_button.clone();
+        Rc::new(move || {
+            let mut options = Vec::new();
+            let mut rows = Vec::new();
+            for (check, item) in items.borrow().iter() {
+                if !check.is_active() {
+                    continue;
+                }
+                match item {
+                    CopyItem::Option(category, key, value) => {
+                        options.push((category.clone(), key.clone(), value.clone()))
+                    }
+                    CopyItem::Row(row) => rows.push(row.clone()),
+                }
+            }
+
+            *text.borrow_mut() = to_hyprlang(&options, &rows);
+            text_view.buffer().set_text(&text.borrow());
+            copy_button.set_sensitive(!text.borrow().is_empty());
+        })
+    };
+
+    let export = export.clone();
+    let fill_items = {
+        let items = items.clone();
+        let update_text = update_text.clone();
+        let category_dropdown = category_dropdown.clone();
+        Rc::new(move || {
+            while let Some(child) = items_box.first_child() {
+                items_box.remove(&child);
+            }
+
+            let selected = category_dropdown.selected() as usize;
+            let shown: Vec<&str> = match selected {
+                0 => categories.iter().map(|(_, category)| *category).collect(),
+                _ => categories
+                    .get(selected - 1)
+                    .map(|(_, category)| vec![*category])
+                    .unwrap_or_default(),
+            };
+
+            let mut new_items = Vec::new();
+            for category in shown {
+                for (key, value) in export.options.get(category).into_iter().flatten() {
+                    let label = format!("{} = {}", option_path(category, key), value);
+                    new_items.push((
+                        label,
+                        CopyItem::Option(category.to_string(), key.clone(), value.clone()),
+                    ));
+                }
+                for row in export.rows.get(category).into_iter().flatten() {
+                    let label = format!("{} = {}", row.name, row.value);
+                    new_items.push((label, CopyItem::Row(row.clone())));
+                }
+            }
+
+            let mut items = items.borrow_mut();
+            items.clear();
+            for (label, item) in new_items {
+                let check = CheckButton::with_label(&label);
+                check.set_active(true);
+                let update_text = update_text.clone();
+                check.connect_toggled(move |_| update_text());
+                items_box.append(&check);
+                items.push((check, item));
+            }
+        })
+    };
+
+    let refresh = {
+        let update_text = update_text.clone();
+        move || {
+            fill_items();
+            update_text();
+        }
+    };
+    refresh();
+    category_dropdown.connect_selected_notify(move |_| refresh());
+
+    for (button, active) in [(select_all_button, true), (select_none_button, false)] {
+        let items = items.clone();
+        button.connect_clicked(move |_| {
+            for (check, _) in items.borrow().iter() {
+                check.set_active(active);
+            }
+        });
+    }
+
+    let dialog_window_clone = dialog_window.clone();
+    close_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    copy_button.connect_clicked(move |_| {
+        dialog_window_clone.clipboard().set_text(&text.borrow());
+    });
+
+    dialog_window.present();
+}
@@ -1,6 +1,9 @@
-use crate::hyprland::MonitorSelector;
+use crate::{
+    config_document::{ConfigDocument, option_path},
+    hyprland::{LiveWorkspace, MonitorSelector, live_workspace::parse_live_workspaces},
+};
 use rust_i18n::t;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json::Value;
 use std::{
     borrow::Cow,
@@ -8,7 +11,7 @@ use std::{
     collections::{HashMap, HashSet, VecDeque},
     env,
     error::Error,
-    fs::{self, File},
+    fmt, fs,
     hash::{Hash, Hasher},
     io::{self, Write},
     ops::Deref,
@@ -77,6 +80,25 @@ pub fn is_development_mode() -> bool {
         .expect("Development mode not initialized")
 }
 
+/// `rel` in the home directory, where hyprviz keeps its settings and state.
+pub fn hyprviz_data_path(rel: impl AsRef<Path>) -> PathBuf {
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(&home).join(rel)
+}
+
+/// Reads the JSON file at `path`, falling back to the default when it is missing or invalid.
+pub fn load_json<T: DeserializeOwned + Default>(path: &Path) -> T {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    atomic_write(path, &json)
+}
+
 pub fn get_config_path(write: bool, profile: &str) -> PathBuf {
     let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
     let base_path = Path::new(&home);
@@ -270,6 +292,10 @@ pub fn atomic_write(path: &Path, data: &str) -> io::Result<()> {
         fs::create_dir_all(parent)?;
     }
 
+    if let Ok(previous) = fs::read_to_string(&final_path) {
+        back_up(&final_path, &previous, data);
+    }
+
     let temp_path = generate_temp_path(&final_path)?;
 
     let result = atomic_replace(&temp_path, &final_path, data);
@@ -281,6 +307,69 @@ pub fn atomic_write(path: &Path, data: &str) -> io::Result<()> {
     result
 }
 
+/// Writes several files at once. All temporary files are written before the first one is
+/// renamed into place, and files that were already replaced are restored if a later rename
+/// fails.
+pub fn atomic_write_all(files: &[(PathBuf, String)]) -> io::Result<()> {
+    let mut staged: Vec<(PathBuf, PathBuf, Option<String>)> = Vec::new();
+
+    let discard = |staged: &[(PathBuf, PathBuf, Option<String>)]| {
+        for (temp_path, _, _) in staged {
+            let _ = fs::remove_file(temp_path);
+        }
+    };
+
+    for (path, data) in files {
+        let stage = || -> io::Result<(PathBuf, PathBuf)> {
+            let final_path = resolve_symlink_fully(path)?;
+            if let Some(parent) = final_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            let temp_path = generate_temp_path(&final_path)?;
+            let mut temp_file = fs::File::create(&temp_path)?;
+            temp_file.write_all(data.as_bytes())?;
+            temp_file.sync_all()?;
+            Ok((temp_path, final_path))
+        };
+
+        match stage() {
+            Ok((temp_path, final_path)) => {
+                let previous = fs::read_to_string(&final_path).ok();
+                if let Some(previous) = &previous {
+                    back_up(&final_path, previous, data);
+                }
+                staged.push((temp_path, final_path, previous));
+            }
+            Err(e) => {
+                discard(&staged);
+                return Err(e);
+            }
+        }
+    }
+
+    for (i, (temp_path, final_path, _)) in staged.iter().enumerate() {
+        if let Err(e) = fs::rename(temp_path, final_path) {
+            discard(&staged[i..]);
+            for (_, final_path, previous) in &staged[..i] {
+                let _ = match previous {
+                    Some(previous) => atomic_write(final_path, previous),
+                    None => fs::remove_file(final_path),
+                };
+            }
+            return Err(e);
+        }
+    }
+
+    for (_, final_path, _) in &staged {
+        if let Some(parent) = final_path.parent() {
+            fs::File::open(parent)?.sync_all()?;
+        }
+    }
+
+    Ok(())
+}
+
 pub fn resolve_symlink_fully(path: &Path) -> io::Result<PathBuf> {
     let mut current = path.to_path_buf();
     let mut iterations = 0;
@@ -372,6 +461,455 @@ fn atomic_replace(temp_path: &Path, final_path: &Path, data: &str) -> io::Result
     }
 }
 
+/// How many backups are kept of each file and for how long. `0` means no limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupSettings {
+    pub max_count: usize,
+    pub max_age_days: u64,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self {
+            max_count: 20,
+            max_age_days: 30,
+        }
+    }
+}
+
+/// A copy of `original` as it was before hyprviz overwrote it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    pub original: PathBuf,
+    pub path: PathBuf,
+    /// Unix time in milliseconds, so saves in quick succession get their own backup.
+    pub created_ms: u64,
+}
+
+fn backups_dir() -> PathBuf {
+    hyprviz_data_path(HYPRVIZ_BACKUPS_DIR)
+}
+
+pub fn load_backup_settings() -> BackupSettings {
+    load_json(&hyprviz_data_path(HYPRVIZ_BACKUP_SETTINGS_PATH))
+}
+
+/// Saves `settings` and drops the backups they no longer keep.
+pub fn save_backup_settings(settings: &BackupSettings) -> io::Result<()> {
+    save_json(&hyprviz_data_path(HYPRVIZ_BACKUP_SETTINGS_PATH), settings)?;
+
+    if let Ok(entries) = fs::read_dir(backups_dir()) {
+        for entry in entries.flatten() {
+            prune_backups(&entry.path(), settings);
+        }
+    }
+    Ok(())
+}
+
+/// Backups of one file live in a directory named after its path, with `%` and `/` escaped.
+fn backup_dir_name(original: &Path) -> String {
+    original
+        .to_string_lossy()
+        .replace('%', "%25")
+        .replace('/', "%2F")
+}
+
+fn original_path(backup_dir_name: &str) -> PathBuf {
+    PathBuf::from(backup_dir_name.replace("%2F", "/").replace("%25", "%"))
+}
+
+/// hyprviz's own state (drafts, UI state, profile metadata, settings) is JSON and rewritten
+/// all the time, so only config files are backed up.
+fn is_backed_up(path: &Path) -> bool {
+    path.extension().is_none_or(|ext| ext != "json") && !path.starts_with(backups_dir())
+}
+
+/// Keeps `previous`, the content of `path` about to be replaced with `data`.
+fn back_up(path: &Path, previous: &str, data: &str) {
+    if previous == data || !is_backed_up(path) {
+        return;
+    }
+
+    let dir = backups_dir().join(backup_dir_name(path));
+    let created_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64);
+    let result = fs::create_dir_all(&dir)
+        .and_then(|_| fs::write(dir.join(created_ms.to_string()), previous));
+
+    match result {
+        Ok(_) => prune_backups(&dir, &load_backup_settings()),
+        Err(e) => eprintln!("Failed to back up {}: {}", path.display(), e),
+    }
+}
+
+fn backups_in(dir: &Path) -> Vec<Backup> {
+    let Some(original) = dir
+        .file_name()
+        .map(|name| original_path(&name.to_string_lossy()))
+    else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut backups: Vec<Backup> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let created_ms = entry.file_name().to_str()?.parse().ok()?;
+            Some(Backup {
+                original: original.clone(),
+                path: entry.path(),
+                created_ms,
+            })
+        })
+        .collect();
+
+    backups.sort_by(|a, b| b.created_ms.cmp(&a.created_ms));
+    backups
+}
+
+/// Removes the backups in `dir` beyond `settings.max_count` or older than
+/// `settings.max_age_days`.
+fn prune_backups(dir: &Path, settings: &BackupSettings) {
+    let now_ms = unix_timestamp() * 1000;
+    let max_age_ms = settings.max_age_days * 24 * 60 * 60 * 1000;
+
+    for (index, backup) in backups_in(dir).into_iter().enumerate() {
+        let too_many = settings.max_count > 0 && index >= settings.max_count;
+        let too_old = max_age_ms > 0 && now_ms.saturating_sub(backup.created_ms) > max_age_ms;
+        if (too_many || too_old)
+            && let Err(e) = fs::remove_file(&backup.path)
+        {
+            eprintln!("Failed to remove backup {}: {}", backup.path.display(), e);
+        }
+    }
+
+    // Only succeeds once the last backup of the file is gone.
+    let _ = fs::remove_dir(dir);
+}
+
+/// Returns the backups of every file, newest first.
+pub fn list_backups() -> Vec<Backup> {
+    let Ok(entries) = fs::read_dir(backups_dir()) else {
+        return Vec::new();
+    };
+
+    let mut backups: Vec<Backup> = entries
+        .flatten()
+        .flat_map(|entry| backups_in(&entry.path()))
+        .collect();
+    backups.sort_by(|a, b| b.created_ms.cmp(&a.created_ms));
+    backups
+}
+
+/// Writes `backup` back over its file. The content it replaces is backed up in turn, so a
+/// restore can be undone the same way.
+pub fn restore_backup(backup: &Backup) -> io::Result<()> {
+    let content = fs::read_to_string(&backup.path)?;
+    atomic_write(&backup.original, &content)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitSettings {
+    /// Commit the written files after every save.
+    pub enabled: bool,
+}
+
+/// A commit that touched a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitRevision {
+    pub hash: String,
+    pub time: u64,
+    pub summary: String,
+}
+
+impl GitRevision {
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+}
+
+pub fn load_git_settings() -> GitSettings {
+    load_json(&hyprviz_data_path(HYPRVIZ_GIT_SETTINGS_PATH))
+}
+
+pub fn save_git_settings(settings: &GitSettings) -> io::Result<()> {
+    save_json(&hyprviz_data_path(HYPRVIZ_GIT_SETTINGS_PATH), settings)
+}
+
+/// Number of commands the command palette remembers.
+pub const MAX_RECENT_COMMANDS: usize = 8;
+
+/// Which options the category pages show.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViewFilter {
+    #[default]
+    All,
+    /// Options with a pending change.
+    Modified,
+    /// Options the profile's own file sets.
+    SetInProfile,
+    /// Options the profile sets that the main config sets as well.
+    Overridden,
+    /// Options whose value is not Hyprland's default.
+    DiffersFromDefault,
+}
+
+impl ViewFilter {
+    pub const ALL: [ViewFilter; 5] = [
+        ViewFilter::All,
+        ViewFilter::Modified,
+        ViewFilter::SetInProfile,
+        ViewFilter::Overridden,
+        ViewFilter::DiffersFromDefault,
+    ];
+}
+
+/// Preferences of the main window that are shared by all profiles.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UiSettings {
+    /// Hide the desktop preview and layout simulator cards.
+    #[serde(default)]
+    pub hide_previews: bool,
+    #[serde(default)]
+    pub view_filter: ViewFilter,
+    /// Ids of the commands last run from the command palette, most recent first.
+    #[serde(default)]
+    pub recent_commands: Vec<String>,
+}
+
+impl UiSettings {
+    pub fn push_recent_command(&mut self, id: &str) {
+        self.recent_commands.retain(|recent| recent != id);
+        self.recent_commands.insert(0, id.to_string());
+        self.recent_commands.truncate(MAX_RECENT_COMMANDS);
+    }
+}
+
+pub fn load_ui_settings() -> UiSettings {
+    load_json(&hyprviz_data_path(HYPRVIZ_UI_SETTINGS_PATH))
+}
+
+pub fn save_ui_settings(settings: &UiSettings) -> io::Result<()> {
+    save_json(&hyprviz_data_path(HYPRVIZ_UI_SETTINGS_PATH), settings)
+}
+
+/// Directory of the main Hyprland config, with symlinks resolved so a config kept in a
+/// dotfiles repository is found.
+pub fn hypr_config_dir() -> PathBuf {
+    let config_path = get_config_path(false, "Default");
+    let config_path = resolve_symlink_fully(&config_path).unwrap_or(config_path);
+    config_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+/// Runs git in `dir` and returns its output, or its error message if it fails.
+fn run_git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| format!("git: {}", e))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Root of the git repository the Hyprland config is in, if any.
+pub fn git_repository() -> Option<PathBuf> {
+    run_git(&hypr_config_dir(), &["rev-parse", "--show-toplevel"])
+        .ok()
+        .map(|toplevel| PathBuf::from(toplevel.trim()))
+}
+
+/// Creates a repository in the Hyprland config directory.
+pub fn git_init() -> Result<PathBuf, String> {
+    let dir = hypr_config_dir();
+    run_git(&dir, &["init"])?;
+    Ok(dir)
+}
+
+/// `path` relative to `repository`, as git names it, or `None` when it is outside.
+fn git_relative_path(repository: &Path, path: &Path) -> Option<String> {
+    // Both the file and any directory above it may be symlinks, as with a Hyprland config
+    // directory linked into a dotfiles repository. The file itself may not exist yet.
+    let path = resolve_symlink_fully(path).unwrap_or_else(|_| path.to_path_buf());
+    let path = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => parent
+            .canonicalize()
+            .map_or_else(|_| path.clone(), |parent| parent.join(name)),
+        _ => path,
+    };
+    let repository = repository
+        .canonicalize()
+        .unwrap_or_else(|_| repository.to_path_buf());
+    path.strip_prefix(&repository)
+        .ok()
+        .map(|relative| relative.to_string_lossy().to_string())
+}
+
+/// Commit message for saving `changes` to `profile`: the transaction label or the single
+/// change as the summary, then one line per change.
+pub fn git_commit_message(profile: &str, changes: &[ConfigChange]) -> String {
+    let mut groups: Vec<&str> = changes
+        .iter()
+        .filter_map(|change| change.group.as_ref().map(|group| group.label.as_str()))
+        .collect();
+    groups.sort();
+    groups.dedup();
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut rows: Vec<(&str, &str)> = Vec::new();
+    for change in changes {
+        let new_value = change.new_value.as_deref().unwrap_or("");
+        let row = ["_name", "_value", "_delete"]
+            .iter()
+            .find_map(|suffix| change.key.strip_suffix(suffix));
+
+        match row {
+            None => lines.push(format!(
+                "{}: {} -> {}",
+                option_path(&change.category, &change.key),
+                change.old_value.as_deref().unwrap_or("unset"),
+                new_value
+            )),
+            Some(raw) if !rows.contains(&(change.category.as_str(), raw)) => {
+                rows.push((change.category.as_str(), raw))
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (category, raw) in rows {
+        let value_of = |suffix: &str| {
+            changes
+                .iter()
+                .find(|c| c.category == category && c.key == format!("{raw}{suffix}"))
+                .and_then(|c| c.new_value.as_deref())
+        };
+        let line = split_origin_raw(raw).map_or(raw, |(_, line)| line).trim();
+
+        if value_of("_delete").is_some() {
+            lines.push(format!("{category}: - {line}"));
+        } else {
+            let (old_name, old_value) = line.split_once('=').unwrap_or((line, ""));
+            let name = value_of("_name").unwrap_or(old_name.trim());
+            let value = value_of("_value").unwrap_or(old_value.trim());
+            if raw.parse::<u64>().is_ok() {
+                lines.push(format!("{category}: + {name} = {value}"));
+            } else {
+                lines.push(format!("{category}: {line} -> {name} = {value}"));
+            }
+        }
+    }
+
+    let summary = match (groups.as_slice(), lines.as_slice()) {
+        ([label], _) if changes.iter().all(|change| change.group.is_some()) => {
+            format!("{profile}: {label}")
+        }
+        (_, [line]) => format!("{profile}: {line}"),
+        _ => format!("{profile}: update {} settings", lines.len()),
+    };
+
+    format!("{summary}\n\n{}\n", lines.join("\n"))
+}
+
+/// Commits `paths` with `message`. Nothing is committed when they did not change. Files
+/// outside the repository are left out and reported as an error after the others are
+/// committed.
+pub fn git_commit(paths: &[PathBuf], message: &str) -> Result<(), String> {
+    let Some(repository) = git_repository() else {
+        return Err(t!("utils.no_git_repository").to_string());
+    };
+
+    let mut relative: Vec<String> = Vec::new();
+    let mut skipped: Vec<String> = Vec::new();
+    for path in paths {
+        match git_relative_path(&repository, path) {
+            Some(path) => relative.push(path),
+            None => skipped
+                .push(t!("utils.outside_git_repository_", file = display_path(path)).to_string()),
+        }
+    }
+    let skipped = || {
+        if skipped.is_empty() {
+            Ok(())
+        } else {
+            Err(skipped.join("\n"))
+        }
+    };
+    if relative.is_empty() {
+        return skipped();
+    }
+    let with_paths = |args: &[&str]| -> Vec<String> {
+        args.iter()
+            .map(|arg| arg.to_string())
+            .chain(relative.iter().cloned())
+            .collect()
+    };
+    let run = |args: Vec<String>| {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        run_git(&repository, &args)
+    };
+
+    run(with_paths(&["add", "--"]))?;
+    // Exits successfully when nothing is staged for these files.
+    if run(with_paths(&["diff", "--cached", "--quiet", "--"])).is_err() {
+        run(with_paths(&["commit", "-m", message, "--"]))?;
+    }
+    skipped()
+}
+
+/// Commits that touched `path`, newest first.
+pub fn git_file_log(path: &Path) -> Result<Vec<GitRevision>, String> {
+    let Some(repository) = git_repository() else {
+        return Err(t!("utils.no_git_repository").to_string());
+    };
+    let Some(relative) = git_relative_path(&repository, path) else {
+        return Err(t!("utils.outside_git_repository_", file = display_path(path)).to_string());
+    };
+
+    let log = run_git(
+        &repository,
+        &["log", "--format=%H%x1f%at%x1f%s", "--", &relative],
+    )?;
+    Ok(log
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\u{1f}');
+            Some(GitRevision {
+                hash: fields.next()?.to_string(),
+                time: fields.next()?.parse().ok()?,
+                summary: fields.next().unwrap_or_default().to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Content of `path` as of `revision`.
+pub fn git_file_at(path: &Path, revision: &GitRevision) -> Result<String, String> {
+    let Some(repository) = git_repository() else {
+        return Err(t!("utils.no_git_repository").to_string());
+    };
+    let Some(relative) = git_relative_path(&repository, path) else {
+        return Err(t!("utils.outside_git_repository_", file = display_path(path)).to_string());
+    };
+
+    run_git(
+        &repository,
+        &["show", &format!("{}:{}", revision.hash, relative)],
+    )
+}
+
 /// Updates the source line in Hyprland config for the specified profile
 /// For "Default" profile: `source = ./hyprviz.conf`
 /// For other profiles: `source = ./hyprviz/{profile}.conf`
@@ -472,46 +1010,624 @@ pub fn find_all_profiles() -> Option<Vec<String>> {
     }
 }
 
-/// Reads the main Hyprland config, filters out HyprViz profile sources, and expands it.
-pub fn expand_base_config() -> Result<String, Box<dyn Error>> {
-    let config_path = get_config_path(false, "Default");
-    let content = fs::read_to_string(&config_path)?;
+/// Description, icon, timestamps and parent of a profile, kept in a `.json` file next to its
+/// `.conf` file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileMeta {
+    #[serde(default)]
+    pub description: String,
+    /// Icon name, empty for the default icon.
+    #[serde(default)]
+    pub icon: String,
+    #[serde(default)]
+    pub created: u64,
+    #[serde(default)]
+    pub modified: u64,
+    /// Profile this one inherits from. Its file is sourced at the top of this profile, so only
+    /// overrides are stored here.
+    #[serde(default)]
+    pub extends: Option<String>,
+}
+
+fn profile_meta_path(profile: &str) -> PathBuf {
+    get_config_path(true, profile).with_extension("json")
+}
+
+/// Returns the metadata of `profile`. Profiles without a sidecar file get their timestamps
+/// from the file system.
+pub fn load_profile_meta(profile: &str) -> ProfileMeta {
+    if let Ok(content) = fs::read_to_string(profile_meta_path(profile))
+        && let Ok(meta) = serde_json::from_str(&content)
+    {
+        return meta;
+    }
+
+    let timestamp = |time: io::Result<std::time::SystemTime>| {
+        time.ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_secs())
+    };
+    let metadata = fs::metadata(get_config_path(true, profile)).ok();
+    ProfileMeta {
+        created: metadata.as_ref().map_or(0, |m| timestamp(m.created())),
+        modified: metadata.as_ref().map_or(0, |m| timestamp(m.modified())),
+        ..ProfileMeta::default()
+    }
+}
+
+pub fn save_profile_meta(profile: &str, meta: &ProfileMeta) -> io::Result<()> {
+    save_json(&profile_meta_path(profile), meta)
+}
+
+/// Records that `profile` was just saved.
+pub fn touch_profile_meta(profile: &str) -> io::Result<()> {
+    let mut meta = load_profile_meta(profile);
+    meta.modified = unix_timestamp();
+    save_profile_meta(profile, &meta)
+}
+
+/// The line that makes a profile inherit the values of `parent`.
+pub fn inherit_source_line(parent: &str) -> String {
+    format!("source = {}", display_path(&get_config_path(true, parent)))
+}
+
+/// Returns the profiles `profile` inherits from, nearest first. Stops at a cycle.
+pub fn profile_ancestors(profile: &str) -> Vec<String> {
+    let mut ancestors: Vec<String> = Vec::new();
+    let mut current = profile.to_string();
+
+    while let Some(parent) = load_profile_meta(&current).extends {
+        if parent == profile || ancestors.contains(&parent) {
+            break;
+        }
+        ancestors.push(parent.clone());
+        current = parent;
+    }
+
+    ancestors
+}
+
+/// Maps the canonical file of every profile `profile` inherits from to its name, to tell
+/// inherited values apart by their [`LineOrigin`].
+pub fn inherited_profile_files(profile: &str) -> HashMap<PathBuf, String> {
+    profile_ancestors(profile)
+        .into_iter()
+        .map(|parent| {
+            let path = get_config_path(true, &parent);
+            (path.canonicalize().unwrap_or(path), parent)
+        })
+        .collect()
+}
+
+/// Returns the profiles that directly inherit from `profile`.
+pub fn profile_children(profile: &str) -> Vec<String> {
+    let mut profiles = find_all_profiles().unwrap_or_default();
+    profiles.push("Default".to_string());
+    profiles
+        .into_iter()
+        .filter(|child| load_profile_meta(child).extends.as_deref() == Some(profile))
+        .collect()
+}
+
+/// Returns whether `profile` may inherit from `parent` without creating a cycle.
+pub fn can_extend(profile: &str, parent: &str) -> bool {
+    parent != profile && !profile_ancestors(parent).iter().any(|p| p == profile)
+}
+
+/// Makes `profile` inherit from `parent`, replacing the source line of its previous parent.
+pub fn set_profile_parent(profile: &str, parent: Option<&str>) -> io::Result<()> {
+    let path = get_config_path(true, profile);
+    let mut meta = load_profile_meta(profile);
+    if meta.extends.as_deref() == parent {
+        return Ok(());
+    }
+
+    let mut document = ConfigDocument::parse(&fs::read_to_string(&path).unwrap_or_default());
+    if let Some(old_parent) = &meta.extends {
+        document.remove_line(&inherit_source_line(old_parent));
+    }
+    if let Some(parent) = parent {
+        document.insert_raw_line(0, &inherit_source_line(parent));
+    }
+    atomic_write(&path, &document.to_string())?;
+
+    meta.extends = parent.map(str::to_string);
+    meta.modified = unix_timestamp();
+    save_profile_meta(profile, &meta)
+}
+
+/// Creates an empty profile that inherits from `parent`.
+pub fn create_inheriting_profile(profile: &str, parent: &str) -> io::Result<()> {
+    atomic_write(
+        &get_config_path(true, profile),
+        &format!("{}\n", inherit_source_line(parent)),
+    )?;
+
+    let now = unix_timestamp();
+    save_profile_meta(
+        profile,
+        &ProfileMeta {
+            created: now,
+            modified: now,
+            extends: Some(parent.to_string()),
+            ..ProfileMeta::default()
+        },
+    )
+}
+
+/// Copies the file and metadata of `source` to a new profile `target`.
+pub fn duplicate_profile(source: &str, target: &str) -> io::Result<()> {
+    let content = fs::read_to_string(get_config_path(true, source))?;
+    atomic_write(&get_config_path(true, target), &content)?;
+
+    let now = unix_timestamp();
+    let meta = ProfileMeta {
+        created: now,
+        modified: now,
+        ..load_profile_meta(source)
+    };
+    save_profile_meta(target, &meta)
+}
+
+/// Renames `old` to `new` with its metadata, pending changes and drafts, and points the
+/// profiles inheriting from it and the profile rules to the new name.
+pub fn rename_profile(old: &str, new: &str) -> io::Result<()> {
+    let children = profile_children(old);
+
+    fs::rename(get_config_path(true, old), get_config_path(true, new))?;
+    if profile_meta_path(old).exists() {
+        fs::rename(profile_meta_path(old), profile_meta_path(new))?;
+    }
+    if ui_state_path(old).exists() {
+        fs::rename(ui_state_path(old), ui_state_path(new))?;
+    }
+
+    for mut draft in load_drafts(old) {
+        delete_draft(&draft)?;
+        draft.profile = new.to_string();
+        save_draft(&draft).map_err(|e| io::Error::other(e.to_string()))?;
+    }
+
+    for child in children {
+        let path = get_config_path(true, &child);
+        let mut document = ConfigDocument::parse(&fs::read_to_string(&path)?);
+        if let Some(index) = document.find_line(&inherit_source_line(old)) {
+            document.update_line(
+                index,
+                None,
+                Some(&display_path(&get_config_path(true, new))),
+            );
+            atomic_write(&path, &document.to_string())?;
+        }
+
+        let mut meta = load_profile_meta(&child);
+        meta.extends = Some(new.to_string());
+        save_profile_meta(&child, &meta)?;
+    }
+
+    let mut rules = load_profile_rules();
+    if rules.uses_profile(old) {
+        rules.rename_profile(old, new);
+        save_profile_rules(&rules)?;
+    }
+
+    Ok(())
+}
+
+/// Deletes the file and metadata of `profile`.
+pub fn delete_profile(profile: &str) -> io::Result<()> {
+    fs::remove_file(get_config_path(true, profile))?;
+    match fs::remove_file(profile_meta_path(profile)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Picks `profile` depending on whether a monitor is connected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileRule {
+    /// Monitor name (`DP-1`) or description prefix (`desc:Dell U2720Q`).
+    pub monitor: String,
+    /// Whether the rule applies when the monitor is connected or when it is not.
+    pub connected: bool,
+    pub profile: String,
+}
+
+/// Rules for switching profiles automatically. The first matching rule wins; `fallback` is
+/// used when none matches.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileRules {
+    #[serde(default)]
+    pub rules: Vec<ProfileRule>,
+    #[serde(default)]
+    pub fallback: Option<String>,
+}
+
+pub fn load_profile_rules() -> ProfileRules {
+    load_json(&hyprviz_data_path(HYPRVIZ_PROFILE_RULES_PATH))
+}
+
+pub fn save_profile_rules(rules: &ProfileRules) -> io::Result<()> {
+    save_json(&hyprviz_data_path(HYPRVIZ_PROFILE_RULES_PATH), rules)
+}
+
+impl ProfileRule {
+    /// `monitors` are the names and `desc:` descriptions of the connected monitors, as
+    /// returned by [`get_available_monitors`].
+    pub fn matches(&self, monitors: &HashSet<String>) -> bool {
+        let selector = self.monitor.trim();
+        let connected = monitors.iter().any(|monitor| {
+            monitor == selector || (selector.starts_with("desc:") && monitor.starts_with(selector))
+        });
+        connected == self.connected
+    }
+}
+
+impl ProfileRules {
+    /// The profile for the connected `monitors`, or `None` when no rule decides. Hyprland
+    /// always has a monitor, so an empty set means the monitor query failed and nothing is
+    /// decided either.
+    pub fn profile_for(&self, monitors: &HashSet<String>) -> Option<&str> {
+        if monitors.is_empty() {
+            return None;
+        }
+
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(monitors))
+            .map(|rule| rule.profile.as_str())
+            .or(self.fallback.as_deref())
+    }
+
+    /// Whether a rule or the fallback picks `profile`.
+    pub fn uses_profile(&self, profile: &str) -> bool {
+        self.rules.iter().any(|rule| rule.profile == profile)
+            || self.fallback.as_deref() == Some(profile)
+    }
+
+    /// Points the rules and the fallback that pick `old` to `new`.
+    pub fn rename_profile(&mut self, old: &str, new: &str) {
+        for rule in &mut self.rules {
+            if rule.profile == old {
+                rule.profile = new.to_string();
+            }
+        }
+        if self.fallback.as_deref() == Some(old) {
+            self.fallback = Some(new.to_string());
+        }
+    }
+}
+
+/// Switches the main config to the profile the rules pick for the connected monitors and
+/// reloads Hyprland. Returns the profile if it was switched; nothing is switched when the
+/// monitors cannot be queried.
+pub fn apply_profile_rules() -> io::Result<Option<String>> {
+    let rules = load_profile_rules();
+    let Some(target) = rules.profile_for(&get_available_monitors(false)) else {
+        return Ok(None);
+    };
+
+    let config_path = get_config_path(false, "Default");
+    let current = get_current_profile(&fs::read_to_string(&config_path)?);
+    if target == current {
+        return Ok(None);
+    }
+    if !get_config_path(true, target).exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("profile {target} does not exist"),
+        ));
+    }
+
+    update_source_line(&config_path, target)?;
+    reload_hyprland();
+    Ok(Some(target.to_string()))
+}
+
+/// Reads the main Hyprland config, filters out HyprViz profile sources, and expands it.
+pub fn expand_base_config() -> Result<String, Box<dyn Error>> {
+    let config_path = get_config_path(false, "Default");
+    let content = fs::read_to_string(&config_path)?;
+
+    // Blank out the profile source lines instead of dropping them, so line numbers still
+    // match the file.
+    let filtered_content = content
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("source")
+                && (trimmed.contains("./hyprviz") || trimmed.contains("hyprviz/"))
+            {
+                ""
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    expand_source_str(&config_path, &filtered_content)
+}
+
+/// File and 1-based line an expanded config line was read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineOrigin {
+    pub path: PathBuf,
+    pub line: usize,
+}
+
+impl fmt::Display for LineOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", display_path(&self.path), self.line)
+    }
+}
+
+/// `path` with the home directory shortened to `~`.
+pub fn display_path(path: &Path) -> String {
+    let home = env::var("HOME").unwrap_or_default();
+    match path.strip_prefix(&home) {
+        Ok(relative) if !home.is_empty() => format!("~/{}", relative.display()),
+        _ => path.display().to_string(),
+    }
+}
+
+fn has_glob_chars(s: &str) -> bool {
+    s.contains(['*', '?', '['])
+}
+
+/// Matches a file name against a glob(7) pattern with `*`, `?` and `[...]` classes.
+fn glob_match(pattern: &[char], name: &[char]) -> bool {
+    match pattern.first() {
+        None => name.is_empty(),
+        Some('*') => (0..=name.len()).any(|skip| glob_match(&pattern[1..], &name[skip..])),
+        Some('?') => !name.is_empty() && glob_match(&pattern[1..], &name[1..]),
+        Some('[') => {
+            let Some(&c) = name.first() else {
+                return false;
+            };
+
+            let mut i = 1;
+            let negated = matches!(pattern.get(i), Some('!') | Some('^'));
+            if negated {
+                i += 1;
+            }
+
+            let mut matched = false;
+            let mut first = true;
+            while i < pattern.len() && (first || pattern[i] != ']') {
+                first = false;
+                if pattern.get(i + 1) == Some(&'-') && pattern.get(i + 2).is_some_and(|&e| e != ']')
+                {
+                    matched |= pattern[i] <= c && c <= pattern[i + 2];
+                    i += 3;
+                } else {
+                    matched |= pattern[i] == c;
+                    i += 1;
+                }
+            }
+
+            // An unterminated class matches a literal '['.
+            if i >= pattern.len() {
+                return c == '[' && glob_match(&pattern[1..], &name[1..]);
+            }
+
+            matched != negated && glob_match(&pattern[i + 1..], &name[1..])
+        }
+        Some(&p) => name.first() == Some(&p) && glob_match(&pattern[1..], &name[1..]),
+    }
+}
+
+/// Expands wildcards in `pattern` the way glob(3), which Hyprland uses for `source`, does:
+/// matches are sorted and hidden files only match components starting with a dot. A path
+/// without wildcards is returned unchanged, whether it exists or not.
+pub fn expand_source_glob(pattern: &Path) -> Vec<PathBuf> {
+    if !has_glob_chars(&pattern.to_string_lossy()) {
+        return vec![pattern.to_path_buf()];
+    }
+
+    let mut candidates = vec![PathBuf::new()];
+
+    for component in pattern.components() {
+        let component = component.as_os_str().to_string_lossy();
+
+        if !has_glob_chars(&component) {
+            for candidate in &mut candidates {
+                candidate.push(component.as_ref());
+            }
+            continue;
+        }
+
+        let component_chars: Vec<char> = component.chars().collect();
+        let mut matches = Vec::new();
+
+        for candidate in &candidates {
+            let dir = if candidate.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                candidate.as_path()
+            };
+            let Ok(entries) = fs::read_dir(dir) else {
+                continue;
+            };
+
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.starts_with('.') && !component.starts_with('.') {
+                    continue;
+                }
+
+                let name_chars: Vec<char> = name.chars().collect();
+                if glob_match(&component_chars, &name_chars) {
+                    matches.push(candidate.join(&name));
+                }
+            }
+        }
+
+        candidates = matches;
+    }
+
+    candidates.retain(|path| path.exists());
+    candidates.sort();
+    candidates
+}
+
+/// Problem found with one `source` entry, see [`IncludeNode`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IncludeError {
+    Missing(String),
+    Cycle,
+    NoMatches,
+}
+
+/// One file of the `source` tree of a config.
+#[derive(Debug, Clone)]
+pub struct IncludeNode {
+    pub path: PathBuf,
+    /// The `source` value this file was included by, if it is a glob pattern.
+    pub pattern: Option<String>,
+    pub option_count: usize,
+    pub error: Option<IncludeError>,
+    pub children: Vec<IncludeNode>,
+}
+
+/// Builds the tree of files included from `entry_path`, following the same rules as
+/// [`expand_source`] but recording missing files and cycles instead of failing.
+pub fn build_include_graph(entry_path: &Path) -> IncludeNode {
+    let mut env_vars = HashMap::new();
+    env_vars.insert("HOME".to_string(), env::var("HOME").unwrap_or_default());
+
+    include_node_recursive(entry_path, None, &mut Vec::new(), &mut env_vars)
+}
+
+fn include_node_recursive(
+    path: &Path,
+    pattern: Option<String>,
+    stack: &mut Vec<PathBuf>,
+    env_vars: &mut HashMap<String, String>,
+) -> IncludeNode {
+    let mut node = IncludeNode {
+        path: path.to_path_buf(),
+        pattern,
+        option_count: 0,
+        error: None,
+        children: Vec::new(),
+    };
+
+    let resolved = match expand_tilde(path) {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            node.error = Some(IncludeError::Missing(e.to_string()));
+            return node;
+        }
+    };
+    let (canonical, content) = match resolved
+        .canonicalize()
+        .and_then(|canonical| Ok((canonical, fs::read_to_string(&resolved)?)))
+    {
+        Ok(result) => result,
+        Err(e) => {
+            node.error = Some(IncludeError::Missing(e.to_string()));
+            return node;
+        }
+    };
+    node.path = canonical.clone();
+
+    if stack.contains(&canonical) {
+        node.error = Some(IncludeError::Cycle);
+        return node;
+    }
+    stack.push(canonical);
+
+    for line in content.lines() {
+        if let Some((name, value)) = parse_env_var_line(line) {
+            env_vars.insert(name, value);
+            continue;
+        }
+
+        let processed_line = substitute_env_vars(line, env_vars);
 
-    let filtered_content = content
-        .lines()
-        .filter(|line| {
-            let trimmed = line.trim_start();
-            if trimmed.starts_with("source") {
-                return !trimmed.contains("./hyprviz") && !trimmed.contains("hyprviz/");
+        if let Some(include_path_str) = parse_source_line(&processed_line) {
+            let include_pattern = resolve_relative(&include_path_str, &resolved);
+            let is_glob = has_glob_chars(&include_path_str);
+            let matches = expand_source_glob(&include_pattern);
+
+            if matches.is_empty() {
+                node.children.push(IncludeNode {
+                    path: include_pattern,
+                    pattern: Some(include_path_str),
+                    option_count: 0,
+                    error: Some(IncludeError::NoMatches),
+                    children: Vec::new(),
+                });
             }
-            true
-        })
-        .collect::<Vec<&str>>()
-        .join("\n");
 
-    expand_source_str(&config_path, &filtered_content)
+            for include_path in matches {
+                let pattern = is_glob.then(|| include_path_str.clone());
+                node.children.push(include_node_recursive(
+                    &include_path,
+                    pattern,
+                    stack,
+                    env_vars,
+                ));
+            }
+            continue;
+        }
+
+        let code = processed_line.split('#').next().unwrap_or_default().trim();
+        if code.contains('=') {
+            node.option_count += 1;
+        }
+    }
+
+    stack.pop();
+    node
 }
 
 /// Expands all `source = <path>` occurrences in file `entry_path` recursively from str
 pub fn expand_source_str(entry_path: &Path, entry: &str) -> Result<String, Box<dyn Error>> {
+    expand_source_str_with_origins(entry_path, entry).map(|(expanded, _)| expanded)
+}
+
+/// Like [`expand_source_str`], also returning the origin of every line of the result.
+pub fn expand_source_str_with_origins(
+    entry_path: &Path,
+    entry: &str,
+) -> Result<(String, Vec<LineOrigin>), Box<dyn Error>> {
     let mut visited = HashSet::new();
+    let mut origins = Vec::new();
 
     let mut env_vars = HashMap::new();
     let home = env::var("HOME").unwrap_or_default();
     env_vars.insert("HOME".to_string(), home.clone());
 
-    expand_file_recursive(entry_path, &mut visited, &mut env_vars, entry)
+    let expanded =
+        expand_file_recursive(entry_path, &mut visited, &mut env_vars, entry, &mut origins)?;
+    Ok((expanded, origins))
 }
 
 /// Expand all `source = <path>` occurrences in file `entry_path` recursively.
 pub fn expand_source(entry_path: &Path) -> Result<String, Box<dyn Error>> {
-    let mut visited = HashSet::new();
+    expand_source_with_origins(entry_path).map(|(expanded, _)| expanded)
+}
 
-    let mut env_vars = HashMap::new();
-    let home = env::var("HOME").unwrap_or_default();
-    env_vars.insert("HOME".to_string(), home.clone());
+/// Like [`expand_source`], also returning the origin of every line of the result.
+pub fn expand_source_with_origins(
+    entry_path: &Path,
+) -> Result<(String, Vec<LineOrigin>), Box<dyn Error>> {
+    expand_source_str_with_origins(entry_path, "")
+}
 
-    expand_file_recursive(entry_path, &mut visited, &mut env_vars, "")
+/// Where each option of the config at `entry_path` is defined, by option path. Later
+/// definitions win, as in Hyprland.
+pub fn option_origins(entry_path: &Path) -> HashMap<String, LineOrigin> {
+    match expand_source_with_origins(entry_path) {
+        Ok((expanded, origins)) => ConfigDocument::parse(&expanded)
+            .option_lines()
+            .into_iter()
+            .filter_map(|(index, path)| Some((path, origins.get(index)?.clone())))
+            .collect(),
+        Err(_) => HashMap::new(),
+    }
 }
 
 fn expand_file_recursive(
@@ -519,6 +1635,7 @@ fn expand_file_recursive(
     visited: &mut HashSet<PathBuf>,
     env_vars: &mut HashMap<String, String>,
     entry: &str,
+    origins: &mut Vec<LineOrigin>,
 ) -> Result<String, Box<dyn Error>> {
     let resolved = expand_tilde(path)?;
     let canonical = resolved
@@ -542,7 +1659,7 @@ fn expand_file_recursive(
 
     let mut out = String::with_capacity(content.len());
 
-    for line in content.lines() {
+    for (index, line) in content.lines().enumerate() {
         if let Some((name, value)) = parse_env_var_line(line) {
             env_vars.insert(name, value);
             continue;
@@ -551,15 +1668,34 @@ fn expand_file_recursive(
         let processed_line = substitute_env_vars(line, env_vars);
 
         if let Some(include_path_str) = parse_source_line(&processed_line) {
-            let include_path = resolve_relative(&include_path_str, &resolved);
-            let included_text = expand_file_recursive(&include_path, visited, env_vars, "")
-                .map_err(|e| format!("while including {}: {}", include_path.display(), e))?;
-            out.push_str(&included_text);
-            out.push('\n');
+            let include_pattern = resolve_relative(&include_path_str, &resolved);
+            let include_paths = expand_source_glob(&include_pattern);
+            // Hyprland reports this as a config error, so it is not skipped silently here.
+            if include_paths.is_empty() {
+                let origin = LineOrigin {
+                    path: canonical.clone(),
+                    line: index + 1,
+                };
+                eprintln!(
+                    "Warning: source = {} at {} matches no files",
+                    include_path_str, origin
+                );
+            }
+            for include_path in include_paths {
+                let included_text =
+                    expand_file_recursive(&include_path, visited, env_vars, "", origins).map_err(
+                        |e| format!("while including {}: {}", include_path.display(), e),
+                    )?;
+                out.push_str(&included_text);
+            }
         } else {
             out.push_str(&processed_line);
-            out.push('\n');
         }
+        out.push('\n');
+        origins.push(LineOrigin {
+            path: canonical.clone(),
+            line: index + 1,
+        });
     }
 
     visited.remove(&canonical);
@@ -828,11 +1964,13 @@ pub fn resolve_relative(p: &str, base_file: &Path) -> PathBuf {
     }
 }
 
-pub fn parse_top_level_options(config_str: &str, raw: bool) -> Vec<(String, String)> {
+/// Returns the top-level `name = value` options of `config_str` with the 0-based line index
+/// of each. With `raw`, the name is the whole line and the value is empty.
+pub fn parse_top_level_option_lines(config_str: &str, raw: bool) -> Vec<(usize, (String, String))> {
     let mut options = Vec::new();
     let mut brace_depth: usize = 0;
 
-    for line in config_str.lines() {
+    for (index, line) in config_str.lines().enumerate() {
         let trimmed_line = line.trim_start();
 
         if trimmed_line.is_empty() || trimmed_line.starts_with('#') {
@@ -856,9 +1994,9 @@ pub fn parse_top_level_options(config_str: &str, raw: bool) -> Vec<(String, Stri
             let value = trimmed_line[eq_pos + 1..].trim_start();
 
             if raw {
-                options.push((line.to_string(), "".to_string()));
+                options.push((index, (line.to_string(), "".to_string())));
             } else {
-                options.push((key.to_string(), value.to_string()));
+                options.push((index, (key.to_string(), value.to_string())));
             }
         }
     }
@@ -866,6 +2004,20 @@ pub fn parse_top_level_options(config_str: &str, raw: bool) -> Vec<(String, Stri
     options
 }
 
+const ORIGIN_RAW_SEPARATOR: char = '\u{1f}';
+
+/// Raw id of a top-level row that is edited in the file it is defined in rather than in the
+/// profile.
+pub fn origin_raw(path: &Path, line: &str) -> String {
+    format!("{}{ORIGIN_RAW_SEPARATOR}{line}", path.display())
+}
+
+/// Splits a raw id created by [`origin_raw`] into the file and the line.
+pub fn split_origin_raw(raw: &str) -> Option<(PathBuf, &str)> {
+    raw.split_once(ORIGIN_RAW_SEPARATOR)
+        .map(|(path, line)| (PathBuf::from(path), line))
+}
+
 /// Expand `~` in a Path if present.
 pub fn expand_tilde(path: &Path) -> Result<PathBuf, Box<dyn Error>> {
     let s = path.to_string_lossy();
@@ -1441,6 +2593,80 @@ pub fn get_available_monitors(only_names: bool) -> HashSet<String> {
     monitors
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorGeometry {
+    pub x: f64,
+    pub y: f64,
+    /// Logical width, after scale and transform are applied.
+    pub width: f64,
+    /// Logical height, after scale and transform are applied.
+    pub height: f64,
+}
+
+/// Returns the layout geometry of the focused monitor, as reported by `hyprctl monitors -j`
+pub fn get_focused_monitor_geometry() -> Option<MonitorGeometry> {
+    let output = match Command::new("hyprctl").arg("monitors").arg("-j").output() {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to get monitor geometry: {}", e);
+            return None;
+        }
+    };
+
+    let json_str = String::from_utf8(output.stdout).ok()?;
+    let monitors = serde_json::from_str::<Vec<Value>>(&json_str).ok()?;
+    let monitor = monitors
+        .iter()
+        .find(|m| m.get("focused").and_then(|f| f.as_bool()) == Some(true))
+        .or_else(|| monitors.first())?;
+
+    let number = |key: &str| monitor.get(key).and_then(|v| v.as_f64());
+    let scale = number("scale").filter(|s| *s > 0.0).unwrap_or(1.0);
+    let (mut width, mut height) = (number("width")? / scale, number("height")? / scale);
+    if number("transform").unwrap_or(0.0) as i64 % 2 == 1 {
+        std::mem::swap(&mut width, &mut height);
+    }
+
+    Some(MonitorGeometry {
+        x: number("x").unwrap_or(0.0),
+        y: number("y").unwrap_or(0.0),
+        width,
+        height,
+    })
+}
+
+/// Returns the global cursor position, as reported by `hyprctl cursorpos -j`
+pub fn get_cursor_position() -> Option<(f64, f64)> {
+    let output = Command::new("hyprctl")
+        .arg("cursorpos")
+        .arg("-j")
+        .output()
+        .ok()?;
+    let json_str = String::from_utf8(output.stdout).ok()?;
+    let position = serde_json::from_str::<Value>(&json_str).ok()?;
+
+    Some((position.get("x")?.as_f64()?, position.get("y")?.as_f64()?))
+}
+
+/// Returns the current workspaces with their windows, or `None` if Hyprland is not reachable
+pub fn get_live_workspaces() -> Option<Vec<LiveWorkspace>> {
+    let hyprctl_json = |what: &str| {
+        Command::new("hyprctl")
+            .arg(what)
+            .arg("-j")
+            .output()
+            .ok()
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .unwrap_or_default()
+    };
+
+    parse_live_workspaces(
+        &hyprctl_json("workspaces"),
+        &hyprctl_json("clients"),
+        &hyprctl_json("monitors"),
+    )
+}
+
 pub fn find_matching_bracket(input: &str, prefix: &str, closing: char) -> Option<usize> {
     if !input.starts_with(prefix) {
         return None;
@@ -1517,7 +2743,7 @@ where
 
     let saved_stdout = unsafe { libc::dup(stdout_fd) };
 
-    let null = File::open("/dev/null").unwrap();
+    let null = fs::File::open("/dev/null").unwrap();
     let null_fd = null.as_raw_fd();
 
     unsafe {
@@ -1561,12 +2787,12 @@ pub fn strip_outer_parens(s: &str) -> Option<&str> {
     }
 }
 
-fn compute_config_hash(content: &[String]) -> u64 {
+fn compute_config_hash(config_str: &str) -> u64 {
     let mut hasher = Xxh3::new();
 
-    content.len().hash(&mut hasher);
+    config_str.lines().count().hash(&mut hasher);
 
-    for line in content {
+    for line in config_str.lines() {
         line.hash(&mut hasher);
     }
 
@@ -1620,6 +2846,93 @@ pub struct ConfigChange {
     pub key: String,
     pub old_value: Option<String>,
     pub new_value: Option<String>,
+    /// Seconds since the Unix epoch, 0 for changes persisted before timestamps were recorded.
+    #[serde(default)]
+    pub timestamp: u64,
+    /// Set for changes recorded inside a transaction; consecutive changes of the same group
+    /// are undone and redone together.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<ChangeGroup>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeGroup {
+    pub id: u64,
+    pub label: String,
+}
+
+impl ConfigChange {
+    pub fn is_same_unit(&self, other: &ConfigChange) -> bool {
+        matches!((&self.group, &other.group), (Some(a), Some(b)) if a.id == b.id)
+    }
+}
+
+pub fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DraftReason {
+    /// The previous session ended without closing the window.
+    Crash,
+    /// The config changed on disk since the pending changes were made.
+    Conflict,
+}
+
+/// Pending changes that were not applied automatically, kept until the user applies or
+/// discards them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Draft {
+    /// User-given name, empty until the draft is named.
+    pub name: String,
+    pub profile: String,
+    pub created: u64,
+    pub reason: DraftReason,
+    #[serde(with = "tuple_map_serde")]
+    pub changes: HashMap<(String, String), String>,
+}
+
+fn drafts_dir(profile: &str) -> PathBuf {
+    hyprviz_data_path(HYPRVIZ_DRAFTS_DIR).join(profile)
+}
+
+fn draft_path(draft: &Draft) -> PathBuf {
+    drafts_dir(&draft.profile).join(format!("{}.json", draft.created))
+}
+
+pub fn save_draft(draft: &Draft) -> Result<(), Box<dyn Error>> {
+    let json = serde_json::to_string_pretty(draft)?;
+    fs::create_dir_all(drafts_dir(&draft.profile))?;
+    atomic_write(&draft_path(draft), &json)?;
+    Ok(())
+}
+
+pub fn delete_draft(draft: &Draft) -> io::Result<()> {
+    fs::remove_file(draft_path(draft))
+}
+
+/// Returns the drafts saved for `profile`, newest first. Unreadable files are skipped.
+pub fn load_drafts(profile: &str) -> Vec<Draft> {
+    let Ok(entries) = fs::read_dir(drafts_dir(profile)) else {
+        return Vec::new();
+    };
+
+    let mut drafts: Vec<Draft> = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+        .filter_map(|content| serde_json::from_str(&content).ok())
+        .collect();
+
+    drafts.sort_by(|a, b| b.created.cmp(&a.created));
+    drafts
+}
+
+fn ui_state_path(profile: &str) -> PathBuf {
+    hyprviz_data_path(HYPRVIZ_UI_STATE_DIR).join(format!("{profile}.json"))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1638,6 +2951,25 @@ pub struct HistoryManager {
     coalesce_threshold_ms: u128,
     #[serde(skip, default = "Arc::default")]
     save_generation: Arc<AtomicU64>,
+    #[serde(default)]
+    next_group_id: u64,
+    #[serde(skip)]
+    active_group: Option<ChangeGroup>,
+    #[serde(skip)]
+    transaction_depth: usize,
+    /// Profile whose state file this history is persisted to.
+    #[serde(skip, default = "default_profile")]
+    profile: String,
+    /// Set while a window is open, so a state file that still has it set on startup means
+    /// the previous session crashed.
+    #[serde(default)]
+    session_active: bool,
+    #[serde(skip)]
+    recovered_draft: Option<Draft>,
+}
+
+fn default_profile() -> String {
+    "Default".to_string()
 }
 
 fn default_instant() -> Instant {
@@ -1657,6 +2989,12 @@ impl HistoryManager {
             last_change_time: Instant::now(),
             coalesce_threshold_ms,
             save_generation: Arc::default(),
+            next_group_id: 0,
+            active_group: None,
+            transaction_depth: 0,
+            profile: default_profile(),
+            session_active: false,
+            recovered_draft: None,
         }
     }
 
@@ -1671,8 +3009,7 @@ impl HistoryManager {
             }
         };
 
-        let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
-        let path = PathBuf::from(&home).join(HYPRVIZ_UI_STATE_PATH);
+        let path = ui_state_path(&self.profile);
         let gen_counter = Arc::clone(&self.save_generation);
         let threshold = self.coalesce_threshold_ms as u64;
 
@@ -1690,9 +3027,32 @@ impl HistoryManager {
         });
     }
 
-    fn load_current_state_of_ui() -> Result<Option<Self>, Box<dyn Error>> {
-        let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
-        let path = PathBuf::from(&home).join(HYPRVIZ_UI_STATE_PATH);
+    /// Writes the state file right away, superseding any scheduled save.
+    fn save_now(&self) {
+        self.save_generation.fetch_add(1, atomic::Ordering::Release);
+
+        let json = match serde_json::to_string_pretty(self) {
+            Ok(j) => j,
+            Err(e) => {
+                eprintln!("[HistoryManager] JSON serialization failed: {}", e);
+                return;
+            }
+        };
+
+        let path = ui_state_path(&self.profile);
+        let _ = std::fs::create_dir_all(path.parent().unwrap());
+        if let Err(e) = atomic_write(&path, &json) {
+            eprintln!("[HistoryManager] Failed to save UI state: {}", e);
+        }
+    }
+
+    fn load_current_state_of_ui(profile: &str) -> Result<Option<Self>, Box<dyn Error>> {
+        let mut path = ui_state_path(profile);
+
+        if !path.exists() && profile == "Default" {
+            // State saved before it was split per profile.
+            path = hyprviz_data_path(HYPRVIZ_LEGACY_UI_STATE_PATH);
+        }
 
         if !path.exists() {
             return Ok(None);
@@ -1703,29 +3063,92 @@ impl HistoryManager {
         Ok(Some(result))
     }
 
-    pub fn restore_persisted_ui_state(&mut self, new_config_content: &[String]) {
-        if let Ok(Some(old_state)) = Self::load_current_state_of_ui() {
-            let new_hash = compute_config_hash(new_config_content);
+    /// Restores the state persisted for the current profile. Undo and redo stacks are only
+    /// restored when the config is unchanged and the previous session closed normally;
+    /// otherwise pending changes are saved as a draft, see [`Self::take_recovered_draft`].
+    pub fn restore_persisted_ui_state(&mut self, new_config_str: &str) {
+        if let Ok(Some(old_state)) = Self::load_current_state_of_ui(&self.profile) {
+            let new_hash = compute_config_hash(new_config_str);
             let old_hash = old_state.initial_config_hash;
 
             eprintln!(
-                "[HistoryManager] Config hash - New: {}, Old: {}, Match: {}",
+                "[HistoryManager] Config hash - New: {}, Old: {}, Match: {}, Crashed: {}",
                 new_hash,
                 old_hash,
-                new_hash == old_hash
+                new_hash == old_hash,
+                old_state.session_active
             );
 
-            if new_hash == old_hash {
+            if new_hash == old_hash && !old_state.session_active {
                 self.current_state = old_state.current_state;
                 self.initial_state = old_state.initial_state;
+                self.undo_stack = old_state.undo_stack;
+                self.redo_stack = old_state.redo_stack;
+            } else if !old_state.current_state.is_empty() {
+                let draft = Draft {
+                    name: String::new(),
+                    profile: self.profile.clone(),
+                    created: unix_timestamp(),
+                    reason: if old_state.session_active {
+                        DraftReason::Crash
+                    } else {
+                        DraftReason::Conflict
+                    },
+                    changes: old_state.current_state,
+                };
+
+                if let Err(e) = save_draft(&draft) {
+                    eprintln!("[HistoryManager] Failed to save draft: {}", e);
+                }
+                self.recovered_draft = Some(draft);
             }
-            self.undo_stack = old_state.undo_stack;
-            self.redo_stack = old_state.redo_stack;
+            self.next_group_id = old_state.next_group_id;
         }
+
+        self.session_active = true;
+        self.schedule_save();
+    }
+
+    /// Returns the draft created by the last restore, if pending changes could not be
+    /// restored directly.
+    pub fn take_recovered_draft(&mut self) -> Option<Draft> {
+        self.recovered_draft.take()
+    }
+
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    pub fn set_profile(&mut self, profile: &str) {
+        self.profile = profile.to_string();
+    }
+
+    /// Persists the state of the current profile and loads the one of `profile`.
+    pub fn switch_profile(&mut self, profile: &str, new_config_str: &str) {
+        self.session_active = false;
+        self.save_now();
+
+        self.profile = profile.to_string();
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.initial_state.clear();
+        self.current_state.clear();
+        self.last_change_key = None;
+        self.active_group = None;
+        self.transaction_depth = 0;
+
+        self.set_initial_config_hash(new_config_str);
+        self.restore_persisted_ui_state(new_config_str);
+    }
+
+    /// Marks the session as closed normally and writes the state file.
+    pub fn end_session(&mut self) {
+        self.session_active = false;
+        self.save_now();
     }
 
-    pub fn set_initial_config_hash(&mut self, new_config_content: &[String]) {
-        self.initial_config_hash = compute_config_hash(new_config_content);
+    pub fn set_initial_config_hash(&mut self, new_config_str: &str) {
+        self.initial_config_hash = compute_config_hash(new_config_str);
     }
 
     pub fn resolve_value_with_history(
@@ -1776,8 +3199,10 @@ impl HistoryManager {
         if self.last_change_key == Some(change_key.clone())
             && now.duration_since(self.last_change_time).as_millis() < self.coalesce_threshold_ms
             && let Some(last) = self.undo_stack.back_mut()
+            && last.group == self.active_group
         {
             last.new_value = Some(new_value.clone());
+            last.timestamp = unix_timestamp();
             self.last_change_time = now;
             self.current_state.insert(change_key, new_value);
 
@@ -1794,13 +3219,13 @@ impl HistoryManager {
             key,
             old_value,
             new_value: Some(new_value),
+            timestamp: unix_timestamp(),
+            group: self.active_group.clone(),
         });
         self.last_change_key = Some(change_key);
         self.last_change_time = now;
 
-        if self.undo_stack.len() > self.max_history {
-            self.undo_stack.pop_front();
-        }
+        self.trim_undo_stack();
 
         self.schedule_save();
     }
@@ -1818,19 +3243,78 @@ impl HistoryManager {
             key,
             old_value,
             new_value: None,
+            timestamp: unix_timestamp(),
+            group: self.active_group.clone(),
         });
         self.last_change_key = None;
         self.last_change_time = Instant::now();
 
-        if self.undo_stack.len() > self.max_history {
-            self.undo_stack.pop_front();
-        }
+        self.trim_undo_stack();
 
         self.schedule_save();
     }
 
-    pub fn undo(&mut self) -> Option<ConfigChange> {
-        if let Some(change) = self.undo_stack.pop_back() {
+    /// Drops the oldest undo steps beyond `max_history`. A transaction is dropped whole, so an
+    /// undo never restores only part of one, and the newest step is always kept.
+    fn trim_undo_stack(&mut self) {
+        while self.undo_stack.len() > self.max_history {
+            let Some(oldest) = self.undo_stack.front() else {
+                break;
+            };
+            let step_len = self
+                .undo_stack
+                .iter()
+                .take_while(|change| change.is_same_unit(oldest))
+                .count()
+                .max(1);
+            if step_len == self.undo_stack.len() {
+                break;
+            }
+            self.undo_stack.drain(..step_len);
+        }
+    }
+
+    /// Starts grouping recorded changes into a single undo step shown as `label`.
+    /// Nested calls join the outermost transaction.
+    pub fn begin_transaction(&mut self, label: impl Into<String>) {
+        self.transaction_depth += 1;
+        if self.transaction_depth > 1 {
+            return;
+        }
+
+        self.active_group = Some(ChangeGroup {
+            id: self.next_group_id,
+            label: label.into(),
+        });
+        self.next_group_id += 1;
+        self.last_change_key = None;
+    }
+
+    pub fn commit_transaction(&mut self) {
+        self.transaction_depth = self.transaction_depth.saturating_sub(1);
+        if self.transaction_depth > 0 {
+            return;
+        }
+
+        self.active_group = None;
+        self.last_change_key = None;
+    }
+
+    /// Undoes the last change, or every change of the last transaction. The changes are
+    /// returned newest first.
+    pub fn undo(&mut self) -> Vec<ConfigChange> {
+        let mut undone: Vec<ConfigChange> = Vec::new();
+
+        while let Some(change) = self.undo_stack.back() {
+            if let Some(first) = undone.first()
+                && !first.is_same_unit(change)
+            {
+                break;
+            }
+
+            let Some(change) = self.undo_stack.pop_back() else {
+                break;
+            };
             let key = (change.category.clone(), change.key.clone());
             if let Some(old) = &change.old_value {
                 self.current_state.insert(key, old.clone());
@@ -1838,17 +3322,32 @@ impl HistoryManager {
                 self.current_state.remove(&key);
             }
             self.redo_stack.push_back(change.clone());
+            undone.push(change);
+        }
 
+        if !undone.is_empty() {
+            self.last_change_key = None;
             self.schedule_save();
-
-            Some(change)
-        } else {
-            None
         }
+
+        undone
     }
 
-    pub fn redo(&mut self) -> Option<ConfigChange> {
-        if let Some(change) = self.redo_stack.pop_back() {
+    /// Redoes the next change, or every change of the next transaction. The changes are
+    /// returned oldest first.
+    pub fn redo(&mut self) -> Vec<ConfigChange> {
+        let mut redone: Vec<ConfigChange> = Vec::new();
+
+        while let Some(change) = self.redo_stack.back() {
+            if let Some(first) = redone.first()
+                && !first.is_same_unit(change)
+            {
+                break;
+            }
+
+            let Some(change) = self.redo_stack.pop_back() else {
+                break;
+            };
             let key = (change.category.clone(), change.key.clone());
             if let Some(new) = &change.new_value {
                 self.current_state.insert(key, new.clone());
@@ -1856,13 +3355,114 @@ impl HistoryManager {
                 self.current_state.remove(&key);
             }
             self.undo_stack.push_back(change.clone());
+            redone.push(change);
+        }
 
+        if !redone.is_empty() {
+            self.last_change_key = None;
             self.schedule_save();
+        }
+
+        redone
+    }
+
+    /// Undoes or redoes changes until exactly `position` changes are on the undo stack.
+    /// Returns the changes that were stepped over, paired with `true` for undone ones.
+    pub fn jump_to(&mut self, position: usize) -> Vec<(ConfigChange, bool)> {
+        let mut stepped = Vec::new();
+
+        while self.undo_stack.len() > position {
+            let undone = self.undo();
+            if undone.is_empty() {
+                break;
+            }
+            stepped.extend(undone.into_iter().map(|change| (change, true)));
+        }
+        while self.undo_stack.len() < position {
+            let redone = self.redo();
+            if redone.is_empty() {
+                break;
+            }
+            stepped.extend(redone.into_iter().map(|change| (change, false)));
+        }
+
+        stepped
+    }
+
+    /// Records new changes that restore the values replaced by the change at `index` of the
+    /// undo stack, or by its whole transaction, leaving every later change in place.
+    pub fn revert_change(&mut self, index: usize) -> Vec<ConfigChange> {
+        let Some(target) = self.undo_stack.get(index).cloned() else {
+            return Vec::new();
+        };
+
+        let mut first = index;
+        while first > 0 && self.undo_stack[first - 1].is_same_unit(&target) {
+            first -= 1;
+        }
+        let mut last = index;
+        while last + 1 < self.undo_stack.len() && self.undo_stack[last + 1].is_same_unit(&target) {
+            last += 1;
+        }
+
+        // The oldest change of each key holds the value from before the unit.
+        let mut targets: Vec<((String, String), Option<String>)> = Vec::new();
+        for change in self.undo_stack.range(first..=last) {
+            let key = (change.category.clone(), change.key.clone());
+            if !targets.iter().any(|(k, _)| *k == key) {
+                targets.push((key, change.old_value.clone()));
+            }
+        }
 
-            Some(change)
+        if let Some(group) = &target.group {
+            self.begin_transaction(t!("utils.revert_", label = group.label));
         } else {
-            None
+            self.last_change_key = None;
         }
+
+        let mut reverted = Vec::new();
+        for (change_key, old_value) in targets.into_iter().rev() {
+            let current_value = match self.current_state.get(&change_key) {
+                Some(current_value) => Some(current_value.clone()),
+                None => self.initial_state.get(&change_key).cloned(),
+            };
+
+            if current_value == old_value {
+                continue;
+            }
+
+            match &old_value {
+                Some(value) => self.current_state.insert(change_key.clone(), value.clone()),
+                None => self.current_state.remove(&change_key),
+            };
+
+            let change = ConfigChange {
+                category: change_key.0,
+                key: change_key.1,
+                old_value: current_value,
+                new_value: old_value,
+                timestamp: unix_timestamp(),
+                group: self.active_group.clone(),
+            };
+            self.undo_stack.push_back(change.clone());
+            reverted.push(change);
+        }
+
+        if target.group.is_some() {
+            self.commit_transaction();
+        }
+
+        if !reverted.is_empty() {
+            self.redo_stack.clear();
+            self.last_change_key = None;
+            self.last_change_time = Instant::now();
+
+            self.trim_undo_stack();
+
+            self.schedule_save();
+        }
+
+        reverted
     }
 
     pub fn get_current_state(&self) -> &HashMap<(String, String), String> {
@@ -1900,6 +3500,56 @@ impl HistoryManager {
         self.schedule_save();
     }
 
+    /// Drops the given keys from the pending changes once they have been written to disk.
+    /// Makes the saved values the new initial state. Deleted rows are dropped entirely.
+    pub fn mark_saved(&mut self, saved: &HashMap<(String, String), String>) {
+        for ((category, key), value) in saved {
+            self.current_state.remove(&(category.clone(), key.clone()));
+
+            match key.strip_suffix("_delete") {
+                Some(raw) => {
+                    for suffix in ["_name", "_value"] {
+                        self.initial_state
+                            .remove(&(category.clone(), format!("{raw}{suffix}")));
+                    }
+                }
+                None => {
+                    self.initial_state
+                        .insert((category.clone(), key.clone()), value.clone());
+                }
+            }
+        }
+
+        self.schedule_save();
+    }
+
+    /// Forgets the pending changes of `keys`, e.g. after the files on disk got the same edit or
+    /// the user chose the version on disk while merging.
+    pub fn drop_pending_changes(&mut self, keys: &[(String, String)]) {
+        for key in keys {
+            self.current_state.remove(key);
+        }
+
+        self.schedule_save();
+    }
+
+    /// Moves the state of a top-level row to a new raw id, e.g. after saving changed its line.
+    pub fn rename_row(&mut self, category: &str, old_raw: &str, new_raw: &str) {
+        for suffix in ["_name", "_value", "_delete"] {
+            let old_key = (category.to_string(), format!("{old_raw}{suffix}"));
+            let new_key = (category.to_string(), format!("{new_raw}{suffix}"));
+
+            if let Some(value) = self.initial_state.remove(&old_key) {
+                self.initial_state.insert(new_key.clone(), value);
+            }
+            if let Some(value) = self.current_state.remove(&old_key) {
+                self.current_state.insert(new_key, value);
+            }
+        }
+
+        self.schedule_save();
+    }
+
     pub fn clear_undo_stack(&mut self) {
         self.undo_stack.clear();
 
@@ -1920,6 +3570,32 @@ impl HistoryManager {
         self.schedule_save();
     }
 
+    /// `changes` as one change each from the saved value, with the transaction it was last
+    /// made in.
+    pub fn applied_changes(
+        &self,
+        changes: &HashMap<(String, String), String>,
+    ) -> Vec<ConfigChange> {
+        let mut keys: Vec<_> = changes.iter().collect();
+        keys.sort();
+
+        keys.into_iter()
+            .map(|((category, key), value)| ConfigChange {
+                category: category.clone(),
+                key: key.clone(),
+                old_value: self.get_initial_value(category, key),
+                new_value: Some(value.clone()),
+                timestamp: unix_timestamp(),
+                group: self
+                    .undo_stack
+                    .iter()
+                    .rev()
+                    .find(|change| change.category == *category && change.key == *key)
+                    .and_then(|change| change.group.clone()),
+            })
+            .collect()
+    }
+
     pub fn get_initial_value(&self, category: &str, key: &str) -> Option<String> {
         self.initial_state
             .get(&(category.to_string(), key.to_string()))
@@ -1942,6 +3618,8 @@ impl HistoryManager {
             key,
             old_value: current_value,
             new_value: target_value.clone(),
+            timestamp: unix_timestamp(),
+            group: self.active_group.clone(),
         });
 
         self.redo_stack.clear();
@@ -1959,7 +3637,15 @@ impl HistoryManager {
 pub const CONFIG_PATH: &str = ".config/hypr/hyprland.conf";
 pub const HYPRVIZ_CONFIG_PATH: &str = ".config/hypr/hyprviz.conf";
 pub const HYPRVIZ_PROFILES_PATH: &str = ".config/hypr/hyprviz/";
-pub const HYPRVIZ_UI_STATE_PATH: &str = ".local/share/hyprviz/ui_state.json";
+pub const HYPRVIZ_UI_STATE_DIR: &str = ".local/share/hyprviz/ui_state/";
+pub const HYPRVIZ_LEGACY_UI_STATE_PATH: &str = ".local/share/hyprviz/ui_state.json";
+pub const HYPRVIZ_DRAFTS_DIR: &str = ".local/share/hyprviz/drafts/";
+pub const HYPRVIZ_PROFILE_RULES_PATH: &str = ".config/hyprviz/profile_rules.json";
+pub const HYPRVIZ_BACKUPS_DIR: &str = ".local/share/hyprviz/backups/";
+pub const HYPRVIZ_BACKUP_SETTINGS_PATH: &str = ".config/hyprviz/backups.json";
+pub const HYPRVIZ_GIT_SETTINGS_PATH: &str = ".config/hyprviz/git.json";
+pub const HYPRVIZ_UI_SETTINGS_PATH: &str = ".config/hyprviz/ui.json";
+pub const HYPRPAPER_CONFIG_PATH: &str = ".config/hypr/hyprpaper.conf";
 
 /// 1 / 255
 pub const ONE_OVER_255: f64 = 1.0 / 255.0;
@@ -1972,3 +3658,89 @@ pub const MAX_SAFE_STEP_0_01_F64: f64 = (1u64 << 47) as f64; // 2^47
 /// -140737488355328
 pub const MIN_SAFE_STEP_0_01_F64: f64 = -MAX_SAFE_STEP_0_01_F64; // -2^47
 pub const MARGIN_NORMAL: i32 = 12;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, name: &str) -> bool {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let name: Vec<char> = name.chars().collect();
+        glob_match(&pattern, &name)
+    }
+
+    /// Creates `files` in a fresh directory under the temporary directory.
+    fn temp_tree(name: &str, files: &[&str]) -> PathBuf {
+        let dir = env::temp_dir().join(format!("hyprviz-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for file in files {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn glob_wildcards() {
+        assert!(matches("*.conf", "binds.conf"));
+        assert!(matches("*.conf", ".conf"));
+        assert!(!matches("*.conf", "binds.conf.bak"));
+        assert!(matches("b?nds.conf", "binds.conf"));
+        assert!(!matches("b?nds.conf", "bnds.conf"));
+        assert!(matches("*", ""));
+        assert!(!matches("?", ""));
+    }
+
+    #[test]
+    fn glob_classes() {
+        assert!(matches("[0-9]-*.conf", "1-binds.conf"));
+        assert!(!matches("[0-9]-*.conf", "a-binds.conf"));
+        assert!(matches("[!0-9]*", "binds.conf"));
+        assert!(!matches("[!0-9]*", "10-binds.conf"));
+        assert!(matches("[^ab]", "c"));
+        assert!(matches("[]]", "]"));
+        assert!(matches("[a-]", "-"));
+        // An unterminated class is a literal '['.
+        assert!(matches("[ab", "[ab"));
+        assert!(!matches("[ab", "a"));
+    }
+
+    #[test]
+    fn expand_source_glob_sorts_and_skips_hidden_files() {
+        let dir = temp_tree(
+            "glob-sorted",
+            &["b.conf", "a.conf", ".hidden.conf", "c.txt", "sub/d.conf"],
+        );
+
+        assert_eq!(
+            expand_source_glob(&dir.join("*.conf")),
+            vec![dir.join("a.conf"), dir.join("b.conf")]
+        );
+        assert_eq!(
+            expand_source_glob(&dir.join(".*.conf")),
+            vec![dir.join(".hidden.conf")]
+        );
+        assert_eq!(
+            expand_source_glob(&dir.join("*/?.conf")),
+            vec![dir.join("sub/d.conf")]
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn expand_source_glob_without_matches() {
+        let dir = temp_tree("glob-empty", &["a.conf"]);
+
+        assert!(expand_source_glob(&dir.join("*.txt")).is_empty());
+        assert!(expand_source_glob(&dir.join("missing/*.conf")).is_empty());
+        // Paths without wildcards are kept, so a missing file is reported when it is read.
+        assert_eq!(
+            expand_source_glob(&dir.join("missing.conf")),
+            vec![dir.join("missing.conf")]
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
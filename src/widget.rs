@@ -1,17 +1,18 @@
 use gtk::{
     Align, ApplicationWindow, ArrowType, Box, Button, ColorDialog, ColorDialogButton, DropDown,
     Entry, Expander, IconSize, Image, Label, MenuButton, Orientation, PolicyType, Popover,
-    PositionType, ScrolledWindow, SpinButton, StringList, StringObject, Switch, Widget, gdk, glib,
-    prelude::*,
+    PositionType, ScrolledWindow, SpinButton, StringList, StringObject, Switch, ToggleButton,
+    Widget, gdk, glib, prelude::*,
 };
 use hyprparser::HyprlandConfig;
 use rust_i18n::t;
 use std::{
     cell::{Cell, RefCell},
     cmp::Ordering,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::Display,
     fs,
+    path::PathBuf,
     rc::Rc,
     str::FromStr,
 };
@@ -20,6 +21,9 @@ use crate::{
     advanced_editors::{
         create_bind_editor, create_curve_editor, create_entry, create_fancy_boxline,
     },
+    categories::LAYOUT_BLOCKS,
+    config_document::option_path,
+    desktop_preview::{DESKTOP_PREVIEW_CATEGORIES, create_desktop_preview},
     gtk_converters::{
         FieldLabel, ToGtkBox, ToGtkBoxImplementation, ToGtkBoxWithSeparator,
         ToGtkBoxWithSeparatorAndNamesImplementation, ToGtkBoxWithSeparatorImplementation,
@@ -27,17 +31,24 @@ use crate::{
     gui::set_widget_value,
     guides::create_guide,
     hyprland::{CssGaps, FontWeight, HyprGradient, PosFloat0_01, Vec2},
+    layout_simulator::{LAYOUT_SIMULATOR_CATEGORY, create_layout_simulator},
     utils::{
-        HistoryManager, MARGIN_NORMAL, MAX_SAFE_INTEGER_F64, compare_versions, expand_source,
-        expand_source_str, extract_value, get_available_monitors, get_config_path,
-        get_latest_version, parse_top_level_options, transform_config,
+        HistoryManager, LineOrigin, MARGIN_NORMAL, MAX_SAFE_INTEGER_F64, compare_versions,
+        expand_source_str_with_origins, expand_source_with_origins, extract_value,
+        get_available_monitors, get_config_path, get_latest_version, inherited_profile_files,
+        load_ui_settings, origin_raw, parse_top_level_option_lines, split_origin_raw,
+        transform_config,
     },
+    workspace_matrix::{MatrixEdit, show_workspace_matrix},
 };
 
 use crate::system_info::*;
 
 #[derive(Clone)]
 pub struct DynamicTopLevelRow {
+    /// Line the row was read from, or a counter for rows that are not saved yet. Shared with
+    /// the row's callbacks so saving can re-key the row without rebuilding it.
+    pub raw: Rc<RefCell<String>>,
     pub vbox: Box,
     pub name_entry: Entry,
     pub fancy_name_entry: Box,
@@ -46,10 +57,30 @@ pub struct DynamicTopLevelRow {
     pub is_programmatic_update: Rc<Cell<bool>>,
 }
 
+/// CSS class of the section headers on a category page.
+pub const SECTION_CSS_CLASS: &str = "option-section";
+
+/// Appends a new editable top-level row with the given name and value.
+pub type AppendRow = Rc<dyn Fn(&str, &str)>;
+
 pub struct WidgetData {
     pub widget: Widget,
     pub visual_widget: Option<Widget>,
     pub default: String,
+    /// Title and description shown next to the widget.
+    pub label: String,
+    pub description: String,
+}
+
+/// Where the value an option was loaded with comes from.
+#[derive(Debug, Clone, Default)]
+pub struct OptionSource {
+    /// Set in the active profile's own file.
+    pub in_profile: bool,
+    /// Value in the main config, without profiles.
+    pub base_value: Option<String>,
+    /// Line the option is defined on, if it is set anywhere.
+    pub origin: Option<LineOrigin>,
 }
 
 pub struct RenderArgs {
@@ -59,12 +90,24 @@ pub struct RenderArgs {
     pub profile: String,
     pub history: Rc<RefCell<HistoryManager>>,
     pub top_level_rows: Rc<RefCell<HashMap<(String, String), DynamicTopLevelRow>>>,
+    pub option_origins: Rc<HashMap<String, LineOrigin>>,
 }
 pub struct ConfigWidget {
     pub options: HashMap<String, WidgetData>,
     pub scrolled_window: ScrolledWindow,
     pub container: Box,
     pub is_programmatic_update: Rc<Cell<bool>>,
+    /// Set once a category with top-level rows is rendered.
+    pub append_row: Rc<RefCell<Option<AppendRow>>>,
+    /// Name and header of every guide on the page.
+    pub guides: Rc<RefCell<Vec<(String, Widget)>>>,
+    /// Desktop preview or layout simulator card, if the page has one.
+    pub preview: Option<Widget>,
+    /// Filled in by [`ConfigWidget::bind_data`], by option name.
+    pub option_sources: RefCell<HashMap<String, OptionSource>>,
+    /// Options whose changes are written to the file they are defined in instead of the
+    /// profile.
+    pub in_place_options: Rc<RefCell<HashSet<String>>>,
     // Lazy state
     pub category: String,
     pub display_name: String,
@@ -79,6 +122,7 @@ fn add_section(container: &Box, title: &str, description: &str, first_section: R
         .margin_top(MARGIN_NORMAL * 2)
         .margin_bottom(MARGIN_NORMAL)
         .build();
+    section_box.add_css_class(SECTION_CSS_CLASS);
 
     let title_label = Label::new(Some(title));
     title_label.set_use_markup(true);
@@ -339,6 +383,8 @@ fn add_dropdown_option(
             widget: dropdown.upcast(),
             visual_widget: None,
             default: default.to_string(),
+            label: label.to_string(),
+            description: description.to_string(),
         },
     );
 }
@@ -417,6 +463,8 @@ fn add_bool_option(
             widget: switch.upcast(),
             visual_widget: None,
             default: default.to_string(),
+            label: label.to_string(),
+            description: description.to_string(),
         },
     );
 }
@@ -495,6 +543,8 @@ fn add_bool_int_option(
             widget: switch.upcast(),
             visual_widget: None,
             default: default.to_string(),
+            label: label.to_string(),
+            description: description.to_string(),
         },
     );
 }
@@ -577,6 +627,8 @@ fn add_int_option(
             widget: spin_button.upcast(),
             visual_widget: None,
             default: default.to_string(),
+            label: label.to_string(),
+            description: description.to_string(),
         },
     );
 }
@@ -659,6 +711,8 @@ fn add_float_option(
             widget: spin_button.upcast(),
             visual_widget: None,
             default: default.to_string(),
+            label: label.to_string(),
+            description: description.to_string(),
         },
     );
 }
@@ -739,6 +793,8 @@ fn add_string_option(
             widget: entry.upcast(),
             visual_widget: None,
             default: default.to_string(),
+            label: label.to_string(),
+            description: description.to_string(),
         },
     );
 }
@@ -872,6 +928,8 @@ fn add_color_option(
             widget: color_button.upcast(),
             visual_widget: None,
             default: default.to_string(),
+            label: label.to_string(),
+            description: description.to_string(),
         },
     );
 }
@@ -952,6 +1010,8 @@ fn add_to_gtk_box_option<T: ToGtkBox + FromStr + Display + 'static>(
             widget: entry.upcast(),
             visual_widget: Some(fancy_box.upcast()),
             default: default.to_string(),
+            label: label.to_string(),
+            description: description.to_string(),
         },
     );
 }
@@ -1077,6 +1137,8 @@ fn add_vec_to_gtk_box_option<T: ToGtkBoxWithSeparator + 'static>(
             widget: entry.upcast(),
             visual_widget: Some(fancy_box.upcast()),
             default: default.to_string(),
+            label: label.to_string(),
+            description: description.to_string(),
         },
     );
 }
@@ -1101,6 +1163,155 @@ fn add_pos_float_vec_option(
     );
 }
 
+/// A read-only `name = value` row.
+fn read_only_option_row(name: &str, value: &str) -> Box {
+    let option_row = Box::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(12)
+        .build();
+    option_row.add_css_class("row");
+
+    let name_label = Label::new(Some(name));
+    name_label.set_xalign(0.0);
+    name_label.set_selectable(true);
+    name_label.set_margin_start(MARGIN_NORMAL);
+    name_label.set_margin_top(MARGIN_NORMAL * 2 / 3);
+    name_label.set_margin_bottom(MARGIN_NORMAL * 2 / 3);
+    name_label.add_css_class("monospace");
+
+    let equals_label = Label::new(Some("="));
+    equals_label.set_xalign(0.5);
+    equals_label.set_margin_top(MARGIN_NORMAL * 2 / 3);
+    equals_label.set_margin_bottom(MARGIN_NORMAL * 2 / 3);
+
+    let value_label = Label::new(Some(value));
+    value_label.set_xalign(0.0);
+    value_label.set_selectable(true);
+    value_label.set_wrap(true);
+    value_label.set_margin_end(MARGIN_NORMAL);
+    value_label.set_margin_top(MARGIN_NORMAL * 2 / 3);
+    value_label.set_margin_bottom(MARGIN_NORMAL * 2 / 3);
+    value_label.add_css_class("monospace");
+
+    option_row.append(&name_label);
+    option_row.append(&equals_label);
+    option_row.append(&value_label);
+
+    option_row
+}
+
+/// Returns the line `origin` points to, reading each file once.
+fn origin_line(files: &mut HashMap<PathBuf, Vec<String>>, origin: &LineOrigin) -> Option<String> {
+    files
+        .entry(origin.path.clone())
+        .or_insert_with(|| {
+            fs::read_to_string(&origin.path)
+                .map(|content| content.lines().map(String::from).collect())
+                .unwrap_or_default()
+        })
+        .get(origin.line - 1)
+        .cloned()
+}
+
+/// Toggle that makes changes to the option `name` go to the file `origin` points to instead
+/// of the profile, for options defined outside of it.
+fn create_edit_in_place_button(
+    name: &str,
+    origin: &LineOrigin,
+    origin_line: Option<&str>,
+    in_place_options: &Rc<RefCell<HashSet<String>>>,
+) -> ToggleButton {
+    let edit_button = ToggleButton::new();
+    edit_button.set_icon_name("document-edit-symbolic");
+    edit_button.set_tooltip_text(Some(&t!("widget.edit_in_place_", file = origin)));
+    edit_button.set_valign(Align::Center);
+    edit_button.add_css_class("flat");
+    // Only a plain `key = value` line of this option can be edited in place.
+    let key = name.rsplit(':').next().unwrap_or(name);
+    let editable = origin_line.is_some_and(|line| {
+        line.split_once('=').is_some_and(|(line_key, _)| {
+            let line_key = line_key.trim();
+            line_key == key || line_key.ends_with(&format!(":{}", key))
+        })
+    });
+    if !editable {
+        in_place_options.borrow_mut().remove(name);
+    }
+    edit_button.set_sensitive(editable);
+    edit_button.set_active(in_place_options.borrow().contains(name));
+
+    let in_place_options = in_place_options.clone();
+    let name = name.to_string();
+    edit_button.connect_toggled(move |button| {
+        let mut in_place_options = in_place_options.borrow_mut();
+        if button.is_active() {
+            in_place_options.insert(name.clone());
+        } else {
+            in_place_options.remove(&name);
+        }
+    });
+
+    edit_button
+}
+
+/// Adds a caption saying where the row comes from (`origin_text`) and buttons to override
+/// the row in the profile and to edit it in its file.
+fn append_origin_actions(
+    option_row: &Box,
+    origin_text: &str,
+    origin: &LineOrigin,
+    origin_line: Option<&str>,
+    name: &str,
+) -> (Button, Button) {
+    let origin_label = Label::new(Some(origin_text));
+    origin_label.set_hexpand(true);
+    origin_label.set_halign(Align::End);
+    origin_label.set_selectable(true);
+    origin_label.add_css_class("dim-label");
+    origin_label.add_css_class("caption");
+    option_row.append(&origin_label);
+
+    let override_button = Button::from_icon_name("document-new-symbolic");
+    override_button.set_tooltip_text(Some(&t!("widget.override_in_profile")));
+    override_button.set_valign(Align::Center);
+    override_button.add_css_class("flat");
+    option_row.append(&override_button);
+
+    let edit_button = Button::from_icon_name("document-edit-symbolic");
+    edit_button.set_tooltip_text(Some(&t!("widget.edit_in_place_", file = origin)));
+    edit_button.set_valign(Align::Center);
+    edit_button.set_margin_end(MARGIN_NORMAL);
+    edit_button.add_css_class("flat");
+    // Lines built from variables or includes cannot be edited in place.
+    edit_button.set_sensitive(
+        origin_line.is_some_and(|line| line.trim_start().starts_with(name) && line.contains('=')),
+    );
+    option_row.append(&edit_button);
+
+    (override_button, edit_button)
+}
+
+fn remove_option_row(
+    gtkbox: &Box,
+    vbox: &Box,
+    history: &Rc<RefCell<HistoryManager>>,
+    category: &str,
+    raw: &str,
+) {
+    gtkbox.remove(vbox);
+
+    let mut history = history.borrow_mut();
+
+    history.record_removal(category.to_string(), format!("{}_name", raw));
+    history.record_removal(category.to_string(), format!("{}_value", raw));
+
+    history.record_change(
+        category.to_string(),
+        format!("{}_delete", raw),
+        "DELETE".to_string(),
+    );
+}
+
 fn append_option_row(
     window: &ApplicationWindow,
     gtkbox: &Box,
@@ -1123,6 +1334,8 @@ fn append_option_row(
         value.clone(),
     );
 
+    let raw = Rc::new(RefCell::new(raw));
+
     let vbox = Box::builder()
         .orientation(Orientation::Vertical)
         .spacing(8)
@@ -1186,7 +1399,7 @@ fn append_option_row(
         let new_name = entry.text().to_string();
         history.record_change(
             category_str.clone(),
-            format!("{}_name", raw_clone),
+            format!("{}_name", raw_clone.borrow()),
             new_name.clone(),
         );
 
@@ -1230,7 +1443,7 @@ fn append_option_row(
         let new_value = entry.text().to_string();
         history.record_change(
             category_str.clone(),
-            format!("{}_value", raw_clone),
+            format!("{}_value", raw_clone.borrow()),
             new_value,
         );
     });
@@ -1251,17 +1464,12 @@ fn append_option_row(
     let raw_clone = raw.clone();
 
     delete_button.connect_clicked(move |_| {
-        gtkbox_clone.remove(&vbox_clone);
-
-        let mut history = history_clone.borrow_mut();
-
-        history.record_removal(category_str.clone(), format!("{}_name", raw_clone));
-        history.record_removal(category_str.clone(), format!("{}_value", raw_clone));
-
-        history.record_change(
-            category_str.clone(),
-            format!("{}_delete", raw_clone),
-            "DELETE".to_string(),
+        remove_option_row(
+            &gtkbox_clone,
+            &vbox_clone,
+            &history_clone,
+            &category_str,
+            &raw_clone.borrow(),
         );
     });
 
@@ -1274,6 +1482,7 @@ fn append_option_row(
 
     {
         let row = DynamicTopLevelRow {
+            raw: raw.clone(),
             vbox: vbox.clone(),
             name_entry: name_entry.clone(),
             fancy_name_entry,
@@ -1283,7 +1492,7 @@ fn append_option_row(
         };
         top_level_rows
             .borrow_mut()
-            .insert((category.to_string(), raw), row);
+            .insert((category.to_string(), raw.borrow().clone()), row);
     }
 
     main_box.append(&fancy_boxline);
@@ -1317,13 +1526,28 @@ fn append_option_row(
     main_box.append(&delete_button);
     scrolled_window.set_child(Some(&main_box));
 
+    if let Some((path, _)) = split_origin_raw(&raw.borrow()) {
+        let origin_label = Label::new(Some(&t!("widget.editing_in_place_", file = path.display())));
+        origin_label.set_halign(Align::Start);
+        origin_label.set_margin_start(MARGIN_NORMAL);
+        origin_label.set_margin_top(MARGIN_NORMAL / 2);
+        origin_label.add_css_class("dim-label");
+        origin_label.add_css_class("caption");
+        vbox.append(&origin_label);
+    }
+
     vbox.append(&scrolled_window);
     vbox.append(&editor_box);
 
     gtkbox.append(&vbox);
 }
 
-fn add_guide(container: &Box, name: &str, default_collapsed: bool) {
+fn add_guide(
+    container: &Box,
+    guides: &RefCell<Vec<(String, Widget)>>,
+    name: &str,
+    default_collapsed: bool,
+) {
     let guide_header_box = Box::builder()
         .orientation(Orientation::Horizontal)
         .spacing(8)
@@ -1344,6 +1568,9 @@ fn add_guide(container: &Box, name: &str, default_collapsed: bool) {
     guide_header_box.append(&title_label);
 
     container.append(&guide_header_box);
+    guides
+        .borrow_mut()
+        .push((name.to_string(), guide_header_box.clone().upcast()));
 
     let expander_content_box = Box::builder()
         .orientation(Orientation::Vertical)
@@ -1428,6 +1655,11 @@ impl ConfigWidget {
             scrolled_window,
             container,
             is_programmatic_update: Rc::new(Cell::new(false)),
+            append_row: Rc::new(RefCell::new(None)),
+            guides: Rc::new(RefCell::new(Vec::new())),
+            preview: None,
+            option_sources: RefCell::new(HashMap::new()),
+            in_place_options: Rc::new(RefCell::new(HashSet::new())),
             category: category.to_string(),
             display_name: display_name.to_string(),
             is_rendered: false,
@@ -1455,10 +1687,30 @@ impl ConfigWidget {
             &args.config.borrow(),
             &args.base_config.borrow(),
             &args.profile,
-            args.history,
+            args.history.clone(),
             args.top_level_rows,
+            &args.option_origins,
         );
 
+        if DESKTOP_PREVIEW_CATEGORIES.contains(&self.category.as_str()) {
+            let preview =
+                create_desktop_preview(args.history, &args.config.borrow(), &self.options);
+            let section_header = self.container.first_child();
+            self.container
+                .insert_child_after(&preview, section_header.as_ref());
+            self.preview = Some(preview.upcast());
+        } else if self.category == LAYOUT_SIMULATOR_CATEGORY {
+            let simulator =
+                create_layout_simulator(args.history, &args.config.borrow(), &self.options);
+            let section_header = self.container.first_child();
+            self.container
+                .insert_child_after(&simulator, section_header.as_ref());
+            self.preview = Some(simulator.upcast());
+        }
+        if let Some(preview) = &self.preview {
+            preview.set_visible(!load_ui_settings().hide_previews);
+        }
+
         self.is_rendered = true;
     }
 
@@ -1468,6 +1720,8 @@ impl ConfigWidget {
         let display_name = self.display_name.as_str();
 
         let mut options: HashMap<String, WidgetData> = HashMap::new();
+        let guides = self.guides.clone();
+        guides.borrow_mut().clear();
 
         let first_section = Rc::new(RefCell::new(true));
 
@@ -1492,7 +1746,7 @@ impl ConfigWidget {
                     "layout",
                     &t!("widget.general_category.layout_label"),
                     &t!("widget.general_category.layout_description"),
-                    &["dwindle", "master", "scrolling", "monocle"],
+                    LAYOUT_BLOCKS,
                     "dwindle",
                 );
 
@@ -4280,7 +4534,7 @@ impl ConfigWidget {
                     &t!("widget.layouts_category.dwindle.layout_section_description"),
                     first_section.clone(),
                 );
-                add_guide(&container, "Dwindle-Layout", false);
+                add_guide(&container, &guides, "Dwindle-Layout", false);
                 add_bool_option(
                     &container,
                     &mut options,
@@ -4398,7 +4652,7 @@ impl ConfigWidget {
                     &t!("widget.layouts_category.master.layout_section_description"),
                     first_section.clone(),
                 );
-                add_guide(&container, "Master-Layout", true);
+                add_guide(&container, &guides, "Master-Layout", true);
                 add_bool_option(
                     &container,
                     &mut options,
@@ -4509,7 +4763,7 @@ impl ConfigWidget {
                     &t!("widget.layouts_category.scrolling.layout_section_description"),
                     first_section.clone(),
                 );
-                add_guide(&container, "Scrolling-Layout", true);
+                add_guide(&container, &guides, "Scrolling-Layout", true);
                 add_bool_option(
                     &container,
                     &mut options,
@@ -4581,7 +4835,7 @@ impl ConfigWidget {
                     &t!("widget.layouts_category.monocle.layout_section_description"),
                     first_section.clone(),
                 );
-                add_guide(&container, "Monocle-Layout", true);
+                add_guide(&container, &guides, "Monocle-Layout", true);
             }
             "systeminfo" => {
                 add_section(
@@ -4984,7 +5238,7 @@ impl ConfigWidget {
                             &t!("widget.monitor_category.monitors_section_description"),
                             first_section.clone(),
                         );
-                        add_guide(&container, "Monitors", true);
+                        add_guide(&container, &guides, "Monitors", true);
                     }
                     "workspace" => {
                         add_section(
@@ -4993,7 +5247,7 @@ impl ConfigWidget {
                             &t!("widget.workspace_category.workspaces_section_description"),
                             first_section.clone(),
                         );
-                        add_guide(&container, "Workspace-Rules", true);
+                        add_guide(&container, &guides, "Workspace-Rules", true);
                     }
                     "animation" => {
                         add_section(
@@ -5002,7 +5256,7 @@ impl ConfigWidget {
                             &t!("widget.animation_category.animations_section_description"),
                             first_section.clone(),
                         );
-                        add_guide(&container, "Animations", true);
+                        add_guide(&container, &guides, "Animations", true);
                     }
                     "bind" => {
                         add_section(
@@ -5011,7 +5265,7 @@ impl ConfigWidget {
                             &t!("widget.bind_category.binds_section_description"),
                             first_section.clone(),
                         );
-                        add_guide(&container, "Binds", true);
+                        add_guide(&container, &guides, "Binds", true);
                     }
                     "gesture" => {
                         add_section(
@@ -5020,7 +5274,7 @@ impl ConfigWidget {
                             &t!("widget.gesture_category.gestures_section_description"),
                             first_section.clone(),
                         );
-                        add_guide(&container, "Gestures", true);
+                        add_guide(&container, &guides, "Gestures", true);
                     }
                     "windowrule" => {
                         add_section(
@@ -5029,7 +5283,7 @@ impl ConfigWidget {
                             &t!("widget.windowrule_category.window_rules_section_description"),
                             first_section.clone(),
                         );
-                        add_guide(&container, "Window-Rules", true);
+                        add_guide(&container, &guides, "Window-Rules", true);
                     }
                     "layerrule" => {
                         add_section(
@@ -5038,7 +5292,7 @@ impl ConfigWidget {
                             &t!("widget.layerrule_category.layer_rules_section_description"),
                             first_section.clone(),
                         );
-                        add_guide(&container, "Layer-Rules", true);
+                        add_guide(&container, &guides, "Layer-Rules", true);
                     }
                     "exec" => {
                         add_section(
@@ -5047,7 +5301,7 @@ impl ConfigWidget {
                             &t!("widget.exec_category.execs_section_description"),
                             first_section.clone(),
                         );
-                        add_guide(&container, "Execs", false);
+                        add_guide(&container, &guides, "Execs", false);
                     }
                     "permission" => {
                         add_section(
@@ -5056,7 +5310,7 @@ impl ConfigWidget {
                             &t!("widget.permission_category.permissions_section_description"),
                             first_section.clone(),
                         );
-                        add_guide(&container, "Permissions", true);
+                        add_guide(&container, &guides, "Permissions", true);
                     }
                     "env" => {
                         add_section(
@@ -5065,7 +5319,7 @@ impl ConfigWidget {
                             &t!("widget.env_category.envs_section_description"),
                             first_section.clone(),
                         );
-                        add_guide(&container, "Environment-variables", true);
+                        add_guide(&container, &guides, "Environment-variables", true);
                     }
                     "top_level" => {
                         add_section(
@@ -5074,7 +5328,7 @@ impl ConfigWidget {
                             &t!("widget.top_level_category.top_level_section_description"),
                             first_section.clone(),
                         );
-                        add_guide(&container, "Dispatchers", true);
+                        add_guide(&container, &guides, "Dispatchers", true);
                     }
                     _ => add_section(
                         &container,
@@ -5099,6 +5353,8 @@ impl ConfigWidget {
                         widget: gtkbox.upcast(),
                         visual_widget: None,
                         default: format!("This is a {} as widget", category),
+                        label: display_name.to_string(),
+                        description: String::new(),
                     },
                 );
             }
@@ -5115,6 +5371,7 @@ impl ConfigWidget {
         profile: String,
         history: Rc<RefCell<HistoryManager>>,
         top_level_rows: Rc<RefCell<HashMap<(String, String), DynamicTopLevelRow>>>,
+        option_origins: Rc<HashMap<String, LineOrigin>>,
     ) {
         self.render_args = Some(RenderArgs {
             window,
@@ -5123,6 +5380,7 @@ impl ConfigWidget {
             profile,
             history,
             top_level_rows,
+            option_origins,
         });
         self.is_rendered = false;
     }
@@ -5135,10 +5393,20 @@ impl ConfigWidget {
         profile: &str,
         history: Rc<RefCell<HistoryManager>>,
         top_level_rows: Rc<RefCell<HashMap<(String, String), DynamicTopLevelRow>>>,
+        option_origins: &HashMap<String, LineOrigin>,
     ) {
         let category = &self.category;
         let transformed_config = transform_config(config.to_string());
         let transformed_base_config = transform_config(base_config.to_string());
+
+        let inherited_from = inherited_profile_files(profile);
+        let profile_file = {
+            let path = get_config_path(true, profile);
+            path.canonicalize().unwrap_or(path)
+        };
+        self.option_sources.borrow_mut().clear();
+        let mut origin_files = HashMap::new();
+
         for (name, widget_data) in &self.options {
             let widget = &widget_data.widget;
             let default_value = &widget_data.default;
@@ -5157,14 +5425,44 @@ impl ConfigWidget {
                 );
 
                 let base_value = extract_value(&transformed_base_config, category, name);
+                let origin = option_origins.get(&option_path(category, name));
+                self.option_sources.borrow_mut().insert(
+                    name.clone(),
+                    OptionSource {
+                        in_profile: origin.is_some_and(|origin| origin.path == profile_file),
+                        base_value: base_value.clone(),
+                        origin: origin.cloned(),
+                    },
+                );
 
                 let visual_widget = &widget_data
                     .visual_widget
                     .clone()
                     .unwrap_or(widget_data.widget.clone());
+                if let Some(origin) = origin {
+                    let tooltip = match inherited_from.get(&origin.path) {
+                        Some(parent) => {
+                            t!("widget.inherited_from_", profile = parent, file = origin)
+                        }
+                        None => t!("widget.defined_in_", file = origin),
+                    };
+                    visual_widget.set_tooltip_text(Some(&tooltip));
+                }
                 if let Some(parent) = visual_widget.parent()
                     && let Ok(box_container) = parent.downcast::<gtk::Box>()
                 {
+                    if let Some(origin) = origin.filter(|origin| origin.path != profile_file) {
+                        let origin_line = origin_line(&mut origin_files, origin);
+                        box_container.append(&create_edit_in_place_button(
+                            name,
+                            origin,
+                            origin_line.as_deref(),
+                            &self.in_place_options,
+                        ));
+                    } else {
+                        self.in_place_options.borrow_mut().remove(name);
+                    }
+
                     let button_box = create_option_actions_menu(
                         history.clone(),
                         category,
@@ -5333,28 +5631,22 @@ impl ConfigWidget {
                     format!("./hyprviz/{}.conf", profile)
                 };
 
+                // The profile's source line is blanked rather than dropped, so line numbers
+                // still match the file.
                 let read_only_config_raw = match fs::read_to_string(&read_only_path) {
                     Ok(read_only_config) => read_only_config
                         .lines()
-                        .filter(|line| {
-                            !line
-                                .trim_start()
-                                .starts_with(&format!("source = {}", profile_path))
-                        })
-                        .filter(|line| {
-                            !line
-                                .trim_start()
-                                .starts_with(&format!("source ={}", profile_path))
-                        })
-                        .filter(|line| {
-                            !line
-                                .trim_start()
-                                .contains(&format!("source= {}", profile_path))
-                        })
-                        .filter(|line| {
-                            !line
-                                .trim_start()
-                                .starts_with(&format!("source={}", profile_path))
+                        .map(|line| {
+                            let trimmed = line.trim_start();
+                            if trimmed.starts_with(&format!("source = {}", profile_path))
+                                || trimmed.starts_with(&format!("source ={}", profile_path))
+                                || trimmed.contains(&format!("source= {}", profile_path))
+                                || trimmed.starts_with(&format!("source={}", profile_path))
+                            {
+                                ""
+                            } else {
+                                line
+                            }
                         })
                         .collect::<Vec<&str>>()
                         .join("\n"),
@@ -5373,9 +5665,9 @@ impl ConfigWidget {
                     }
                 };
 
-                let read_only_config =
-                    match expand_source_str(&read_only_path, &read_only_config_raw) {
-                        Ok(read_only_config) => read_only_config,
+                let (read_only_config, read_only_origins) =
+                    match expand_source_str_with_origins(&read_only_path, &read_only_config_raw) {
+                        Ok(expanded) => expanded,
                         Err(_) => {
                             let error_label = Label::new(Some(&t!(
                                 "widget.error_reading_",
@@ -5387,12 +5679,14 @@ impl ConfigWidget {
                             error_label.set_margin_top(MARGIN_NORMAL * 2 / 3);
                             error_label.set_margin_bottom(MARGIN_NORMAL * 2 / 3);
                             read_only_container.append(&error_label);
-                            String::new()
+                            (String::new(), Vec::new())
                         }
                     };
 
                 let parsed_headless_readonly_options =
-                    parse_top_level_options(&read_only_config, false);
+                    parse_top_level_option_lines(&read_only_config, false);
+                let mut origin_files: HashMap<PathBuf, Vec<String>> = HashMap::new();
+                let mut origin_actions = Vec::new();
 
                 let options_list = Box::builder()
                     .orientation(Orientation::Vertical)
@@ -5403,7 +5697,7 @@ impl ConfigWidget {
                     .margin_bottom(MARGIN_NORMAL)
                     .build();
 
-                for (name, value) in parsed_headless_readonly_options.iter() {
+                for (index, (name, value)) in parsed_headless_readonly_options.iter() {
                     if !name.starts_with(category)
                         && category != "top_level"
                         && !((category == "bind" && name.starts_with("unbind"))
@@ -5412,37 +5706,28 @@ impl ConfigWidget {
                         continue;
                     }
 
-                    let option_row = Box::builder()
-                        .orientation(Orientation::Horizontal)
-                        .spacing(12)
-                        .build();
-                    option_row.add_css_class("row");
-
-                    let name_label = Label::new(Some(name));
-                    name_label.set_xalign(0.0);
-                    name_label.set_selectable(true);
-                    name_label.set_margin_start(MARGIN_NORMAL);
-                    name_label.set_margin_top(MARGIN_NORMAL * 2 / 3);
-                    name_label.set_margin_bottom(MARGIN_NORMAL * 2 / 3);
-                    name_label.add_css_class("monospace");
-
-                    let equals_label = Label::new(Some("="));
-                    equals_label.set_xalign(0.5);
-                    equals_label.set_margin_top(MARGIN_NORMAL * 2 / 3);
-                    equals_label.set_margin_bottom(MARGIN_NORMAL * 2 / 3);
-
-                    let value_label = Label::new(Some(value));
-                    value_label.set_xalign(0.0);
-                    value_label.set_selectable(true);
-                    value_label.set_wrap(true);
-                    value_label.set_margin_end(MARGIN_NORMAL);
-                    value_label.set_margin_top(MARGIN_NORMAL * 2 / 3);
-                    value_label.set_margin_bottom(MARGIN_NORMAL * 2 / 3);
-                    value_label.add_css_class("monospace");
-
-                    option_row.append(&name_label);
-                    option_row.append(&equals_label);
-                    option_row.append(&value_label);
+                    let option_row = read_only_option_row(name, value);
+
+                    if let Some(origin) = read_only_origins.get(*index) {
+                        let origin_line = origin_line(&mut origin_files, origin);
+                        let (override_button, edit_button) = append_origin_actions(
+                            &option_row,
+                            &origin.to_string(),
+                            origin,
+                            origin_line.as_deref(),
+                            name,
+                        );
+
+                        origin_actions.push((
+                            option_row.clone(),
+                            override_button,
+                            edit_button,
+                            name.clone(),
+                            value.clone(),
+                            origin_line.map(|line| origin_raw(&origin.path, &line)),
+                        ));
+                    }
+
                     options_list.append(&option_row);
                 }
 
@@ -5481,6 +5766,75 @@ impl ConfigWidget {
                     .build();
                 rw_container.add_css_class("card");
 
+                // Rows of the profiles this one inherits from are listed separately; they can
+                // be overridden here or edited in the parent profile.
+                let inherited_from = inherited_profile_files(profile);
+                let rw_expanded = expand_source_with_origins(&rw_path);
+                if let Ok((rw_config, rw_origins)) = &rw_expanded
+                    && !inherited_from.is_empty()
+                {
+                    let inherited_list = Box::builder()
+                        .orientation(Orientation::Vertical)
+                        .spacing(4)
+                        .margin_start(MARGIN_NORMAL)
+                        .margin_end(MARGIN_NORMAL)
+                        .margin_top(MARGIN_NORMAL * 2 / 3)
+                        .margin_bottom(MARGIN_NORMAL)
+                        .build();
+                    let mut inherited_count = 0;
+
+                    for (index, (name, value)) in parse_top_level_option_lines(rw_config, false) {
+                        let Some(origin) = rw_origins.get(index) else {
+                            continue;
+                        };
+                        let Some(parent) = inherited_from.get(&origin.path) else {
+                            continue;
+                        };
+                        if !name.starts_with(category)
+                            && category != "top_level"
+                            && !((category == "bind" && name.starts_with("unbind"))
+                                || (category == "animation" && name.starts_with("bezier")))
+                        {
+                            continue;
+                        }
+
+                        let option_row = read_only_option_row(&name, &value);
+                        let origin_line = origin_line(&mut origin_files, origin);
+                        let (override_button, edit_button) = append_origin_actions(
+                            &option_row,
+                            &t!("widget.inherited_from_", profile = parent, file = origin),
+                            origin,
+                            origin_line.as_deref(),
+                            &name,
+                        );
+                        origin_actions.push((
+                            option_row.clone(),
+                            override_button,
+                            edit_button,
+                            name,
+                            value,
+                            origin_line.map(|line| origin_raw(&origin.path, &line)),
+                        ));
+
+                        inherited_list.append(&option_row);
+                        inherited_count += 1;
+                    }
+
+                    if inherited_count > 0 {
+                        let expander = Expander::new(Some(&t!(
+                            "widget.show_inherited_options_",
+                            count = inherited_count
+                        )));
+                        expander.set_margin_start(MARGIN_NORMAL);
+                        expander.set_margin_end(MARGIN_NORMAL);
+                        expander.set_margin_top(MARGIN_NORMAL * 2 / 3);
+                        expander.set_expanded(false);
+                        expander.add_css_class("card");
+                        expander.set_child(Some(&inherited_list));
+                        rw_container.append(&expander);
+                    }
+                }
+
                 let create_button = Button::with_label(&t!("widget.create"));
                 create_button.add_css_class("suggested-action");
                 create_button.set_margin_start(MARGIN_NORMAL);
@@ -5491,6 +5845,252 @@ impl ConfigWidget {
                 rw_container.append(&create_button);
 
                 let id_new = Rc::new(RefCell::new(0));
+
+                for (option_row, override_button, edit_button, name, value, in_place_raw) in
+                    origin_actions
+                {
+                    let window_clone = window.clone();
+                    let rw_container_clone = rw_container.clone();
+                    let history_clone = history.clone();
+                    let top_level_rows_clone = top_level_rows.clone();
+                    let is_programmatic_update_clone = self.is_programmatic_update.clone();
+                    let category_string = category.to_string();
+                    let id_new = id_new.clone();
+                    let name_clone = name.clone();
+                    let value_clone = value.clone();
+                    override_button.connect_clicked(move |_| {
+                        let raw = {
+                            let mut id = id_new.borrow_mut();
+                            let raw = id.to_string();
+                            *id += 1;
+                            raw
+                        };
+                        append_option_row(
+                            &window_clone,
+                            &rw_container_clone,
+                            raw.clone(),
+                            "".to_string(),
+                            "".to_string(),
+                            &history_clone,
+                            &category_string,
+                            &top_level_rows_clone,
+                            &is_programmatic_update_clone,
+                        );
+                        let row = top_level_rows_clone
+                            .borrow()
+                            .get(&(category_string.clone(), raw))
+                            .cloned();
+                        if let Some(row) = row {
+                            row.name_entry.set_text(&name_clone);
+                            row.value_entry.set_text(&value_clone);
+                        }
+                    });
+
+                    let Some(raw) = in_place_raw else {
+                        continue;
+                    };
+
+                    let window_clone = window.clone();
+                    let rw_container_clone = rw_container.clone();
+                    let history_clone = history.clone();
+                    let top_level_rows_clone = top_level_rows.clone();
+                    let is_programmatic_update_clone = self.is_programmatic_update.clone();
+                    let category_string = category.to_string();
+                    let raw_clone = raw.clone();
+                    edit_button.connect_clicked(move |_| {
+                        option_row.set_visible(false);
+
+                        append_option_row(
+                            &window_clone,
+                            &rw_container_clone,
+                            raw_clone.clone(),
+                            name.clone(),
+                            value.clone(),
+                            &history_clone,
+                            &category_string,
+                            &top_level_rows_clone,
+                            &is_programmatic_update_clone,
+                        );
+
+                        // The file's line is the initial state; pending edits are shown on top.
+                        let (pending_name, pending_value) = {
+                            let history = history_clone.borrow();
+                            (
+                                history.lookup_transient_override(
+                                    &category_string,
+                                    &format!("{}_name", raw_clone),
+                                    &name,
+                                ),
+                                history.lookup_transient_override(
+                                    &category_string,
+                                    &format!("{}_value", raw_clone),
+                                    &value,
+                                ),
+                            )
+                        };
+                        let row = top_level_rows_clone
+                            .borrow()
+                            .get(&(category_string.clone(), raw_clone.clone()))
+                            .cloned();
+                        if let Some(row) = row {
+                            is_programmatic_update_clone.set(true);
+                            row.name_entry.set_text(&pending_name);
+                            row.value_entry.set_text(&pending_value);
+                            is_programmatic_update_clone.set(false);
+                        }
+                    });
+
+                    // Rows with pending in-place edits are shown as editable again.
+                    let has_pending_edit =
+                        history
+                            .borrow()
+                            .get_current_state()
+                            .keys()
+                            .any(|(row_category, key)| {
+                                row_category == category
+                                    && ["_name", "_value"].iter().any(|suffix| {
+                                        key.strip_suffix(suffix) == Some(raw.as_str())
+                                    })
+                            });
+                    if has_pending_edit {
+                        edit_button.emit_clicked();
+                    }
+                }
+
+                if category == "workspace" {
+                    let matrix_button =
+                        Button::with_label(&t!("widget.workspace_category.assignment_matrix"));
+                    matrix_button.set_margin_start(MARGIN_NORMAL);
+                    matrix_button.set_margin_end(MARGIN_NORMAL);
+                    matrix_button.set_margin_bottom(MARGIN_NORMAL);
+                    matrix_button.set_halign(Align::Fill);
+                    rw_container.append(&matrix_button);
+
+                    let id_new = id_new.clone();
+                    let window_clone = window.clone();
+                    let rw_container_clone = rw_container.clone();
+                    let history_clone = history.clone();
+                    let top_level_rows_clone = top_level_rows.clone();
+                    let is_programmatic_update_clone = self.is_programmatic_update.clone();
+                    matrix_button.connect_clicked(move |_| {
+                        let rows: Vec<(String, String)> = top_level_rows_clone
+                            .borrow()
+                            .iter()
+                            .filter(|((row_category, _), row)| {
+                                row_category == "workspace"
+                                    && row.vbox.parent().is_some()
+                                    && row.name_entry.text().trim() == "workspace"
+                            })
+                            .map(|((_, raw), row)| {
+                                (raw.clone(), row.value_entry.text().to_string())
+                            })
+                            .collect();
+
+                        let id_new = id_new.clone();
+                        let window = window_clone.clone();
+                        let rw_container = rw_container_clone.clone();
+                        let history = history_clone.clone();
+                        let top_level_rows = top_level_rows_clone.clone();
+                        let is_programmatic_update = is_programmatic_update_clone.clone();
+                        show_workspace_matrix(&window_clone, rows, move |edits| {
+                            history.borrow_mut().begin_transaction(t!(
+                                "widget.workspace_category.assignment_matrix"
+                            ));
+                            for edit in edits {
+                                match edit {
+                                    MatrixEdit::Update { raw, value } => {
+                                        let row = top_level_rows
+                                            .borrow()
+                                            .get(&("workspace".to_string(), raw))
+                                            .cloned();
+                                        if let Some(row) = row {
+                                            row.value_entry.set_text(&value);
+                                        }
+                                    }
+                                    MatrixEdit::Remove { raw } => {
+                                        let row = top_level_rows
+                                            .borrow()
+                                            .get(&("workspace".to_string(), raw.clone()))
+                                            .cloned();
+                                        if let Some(row) = row {
+                                            remove_option_row(
+                                                &rw_container,
+                                                &row.vbox,
+                                                &history,
+                                                "workspace",
+                                                &raw,
+                                            );
+                                        }
+                                    }
+                                    MatrixEdit::Add { value } => {
+                                        let raw = {
+                                            let mut id = id_new.borrow_mut();
+                                            let raw = id.to_string();
+                                            *id += 1;
+                                            raw
+                                        };
+                                        append_option_row(
+                                            &window,
+                                            &rw_container,
+                                            raw.clone(),
+                                            "".to_string(),
+                                            "".to_string(),
+                                            &history,
+                                            "workspace",
+                                            &top_level_rows,
+                                            &is_programmatic_update,
+                                        );
+                                        let row = top_level_rows
+                                            .borrow()
+                                            .get(&("workspace".to_string(), raw))
+                                            .cloned();
+                                        if let Some(row) = row {
+                                            row.name_entry.set_text("workspace");
+                                            row.value_entry.set_text(&value);
+                                        }
+                                    }
+                                }
+                            }
+                            history.borrow_mut().commit_transaction();
+                        });
+                    });
+                }
+
+                let id_new_clone = id_new.clone();
+                let window_clone = window.clone();
+                let rw_container_clone = rw_container.clone();
+                let history_clone = history.clone();
+                let top_level_rows_clone = top_level_rows.clone();
+                let is_programmatic_update_clone = self.is_programmatic_update.clone();
+                let category_string = category.to_string();
+                *self.append_row.borrow_mut() = Some(Rc::new(move |name: &str, value: &str| {
+                    let raw = {
+                        let mut id = id_new_clone.borrow_mut();
+                        let raw = id.to_string();
+                        *id += 1;
+                        raw
+                    };
+                    append_option_row(
+                        &window_clone,
+                        &rw_container_clone,
+                        raw.clone(),
+                        "".to_string(),
+                        "".to_string(),
+                        &history_clone,
+                        &category_string,
+                        &top_level_rows_clone,
+                        &is_programmatic_update_clone,
+                    );
+                    let row = top_level_rows_clone
+                        .borrow()
+                        .get(&(category_string.clone(), raw))
+                        .cloned();
+                    if let Some(row) = row {
+                        row.name_entry.set_text(name);
+                        row.value_entry.set_text(value);
+                    }
+                }));
+
                 let window_clone = window.clone();
                 let rw_container_clone = rw_container.clone();
                 let history_clone = history.clone();
@@ -5513,8 +6113,8 @@ impl ConfigWidget {
                     *id += 1;
                 });
 
-                let rw_config = match expand_source(&rw_path) {
-                    Ok(rw_config) => rw_config,
+                let (rw_config, rw_origins) = match rw_expanded {
+                    Ok(rw_expanded) => rw_expanded,
                     Err(_) => {
                         let error_label = Label::new(Some(&t!(
                             "widget.error_reading_",
@@ -5526,16 +6126,23 @@ impl ConfigWidget {
                         error_label.set_margin_top(MARGIN_NORMAL * 2 / 3);
                         error_label.set_margin_bottom(MARGIN_NORMAL * 2 / 3);
                         rw_container.append(&error_label);
-                        String::new()
+                        (String::new(), Vec::new())
                     }
                 };
 
-                let parsed_headless_options_raw = parse_top_level_options(&rw_config, true);
-                let parsed_headless_options = parse_top_level_options(&rw_config, false);
-                for ((raw, _), (name, value)) in parsed_headless_options_raw
+                let parsed_headless_options_raw = parse_top_level_option_lines(&rw_config, true);
+                let parsed_headless_options = parse_top_level_option_lines(&rw_config, false);
+                for ((index, (raw, _)), (_, (name, value))) in parsed_headless_options_raw
                     .into_iter()
                     .zip(parsed_headless_options)
                 {
+                    if rw_origins
+                        .get(index)
+                        .is_some_and(|origin| inherited_from.contains_key(&origin.path))
+                    {
+                        continue;
+                    }
+
                     let restored_name = history.borrow().lookup_transient_override(
                         category,
                         &format!("{}_name", raw),
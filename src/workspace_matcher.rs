@@ -0,0 +1,168 @@
+use crate::{
+    hyprland::{
+        IdOrNameOrWorkspaceSelector, LiveWorkspace, WindowRuleEffectOrProp, WindowRuleProp,
+        live_workspace::sample_workspaces, workspace::parse_workspace,
+    },
+    utils::{MARGIN_NORMAL, get_live_workspaces},
+};
+use gtk::{
+    Align, ArrowType, Box, Button, Entry, FlowBox, Label, MenuButton, Orientation, Popover,
+    SelectionMode, prelude::*,
+};
+use rust_i18n::t;
+use std::{cell::RefCell, rc::Rc, str::FromStr};
+
+/// Returns `None` when `value` does not refer to workspaces at all.
+pub type WorkspaceMatcher = fn(&str, &LiveWorkspace) -> Option<bool>;
+
+pub fn match_workspace_rule(value: &str, workspace: &LiveWorkspace) -> Option<bool> {
+    Some(parse_workspace(value).workspace_type.matches(workspace))
+}
+
+pub fn match_window_rule(value: &str, workspace: &LiveWorkspace) -> Option<bool> {
+    value
+        .split(',')
+        .filter_map(|part| WindowRuleEffectOrProp::from_str(part).ok())
+        .find_map(|effect| match effect {
+            WindowRuleEffectOrProp::Prop(WindowRuleProp::Workspace(selector)) => Some(selector),
+            _ => None,
+        })
+        .map(|selector: IdOrNameOrWorkspaceSelector| selector.matches(workspace))
+}
+
+fn workspace_chip(workspace: &LiveWorkspace, is_match: bool) -> Label {
+    let chip = Label::new(Some(&workspace.name));
+    chip.set_tooltip_text(Some(&t!(
+        "workspace_matcher.workspace_tooltip",
+        id = workspace.id,
+        monitor = workspace.monitor,
+        windows = workspace.windows.len()
+    )));
+    chip.set_margin_start(MARGIN_NORMAL / 4);
+    chip.set_margin_end(MARGIN_NORMAL / 4);
+    chip.set_margin_top(MARGIN_NORMAL / 4);
+    chip.set_margin_bottom(MARGIN_NORMAL / 4);
+    if is_match {
+        chip.add_css_class("success");
+        chip.add_css_class("heading");
+    } else {
+        chip.add_css_class("dim-label");
+    }
+    chip
+}
+
+/// Creates a button that opens a popover listing the current workspaces, highlighting the
+/// ones the workspace reference in `value_entry` matches. Workspaces are fetched each time
+/// the popover opens, falling back to sample workspaces when `hyprctl` is not available.
+pub fn create_workspace_match_preview(
+    value_entry: &Entry,
+    matcher: WorkspaceMatcher,
+) -> MenuButton {
+    let menu_button = MenuButton::new();
+    menu_button.set_icon_name("view-grid-symbolic");
+    menu_button.set_direction(ArrowType::Down);
+    menu_button.set_valign(Align::Center);
+    menu_button.set_tooltip_text(Some(&t!("workspace_matcher.matching_workspaces")));
+
+    let content_box = Box::new(Orientation::Vertical, 6);
+    content_box.set_margin_start(MARGIN_NORMAL / 2);
+    content_box.set_margin_end(MARGIN_NORMAL / 2);
+    content_box.set_margin_top(MARGIN_NORMAL / 2);
+    content_box.set_margin_bottom(MARGIN_NORMAL / 2);
+    content_box.set_width_request(320);
+
+    let title_label = Label::new(Some(&t!("workspace_matcher.matching_workspaces")));
+    title_label.set_halign(Align::Start);
+    title_label.add_css_class("heading");
+
+    let source_label = Label::new(None);
+    source_label.set_halign(Align::Start);
+    source_label.set_wrap(true);
+    source_label.add_css_class("dim-label");
+
+    let flow_box = FlowBox::new();
+    flow_box.set_selection_mode(SelectionMode::None);
+    flow_box.set_max_children_per_line(8);
+    flow_box.set_homogeneous(false);
+
+    let summary_label = Label::new(None);
+    summary_label.set_halign(Align::Start);
+    summary_label.set_wrap(true);
+
+    let refresh_button = Button::with_label(&t!("workspace_matcher.refresh"));
+    refresh_button.set_halign(Align::Start);
+
+    content_box.append(&title_label);
+    content_box.append(&source_label);
+    content_box.append(&flow_box);
+    content_box.append(&summary_label);
+    content_box.append(&refresh_button);
+
+    let workspaces = Rc::new(RefCell::new(Vec::new()));
+
+    let update = {
+        let value_entry = value_entry.clone();
+        let workspaces = workspaces.clone();
+        move || {
+            flow_box.remove_all();
+
+            let value = value_entry.text();
+            let workspaces = workspaces.borrow();
+            let mut matched = 0;
+            let mut has_reference = false;
+
+            for workspace in workspaces.iter() {
+                let is_match = matcher(&value, workspace);
+                has_reference |= is_match.is_some();
+                let is_match = is_match.unwrap_or(false);
+                if is_match {
+                    matched += 1;
+                }
+                flow_box.append(&workspace_chip(workspace, is_match));
+            }
+
+            summary_label.set_text(&if has_reference || workspaces.is_empty() {
+                t!(
+                    "workspace_matcher.matches_count",
+                    matched = matched,
+                    total = workspaces.len()
+                )
+            } else {
+                t!("workspace_matcher.no_workspace_reference")
+            });
+        }
+    };
+    let update = Rc::new(update);
+
+    let reload = {
+        let update = update.clone();
+        move || {
+            *workspaces.borrow_mut() = match get_live_workspaces() {
+                Some(live) => {
+                    source_label.set_text(&t!("workspace_matcher.live_workspaces"));
+                    live
+                }
+                None => {
+                    source_label.set_text(&t!("workspace_matcher.sample_workspaces"));
+                    sample_workspaces()
+                }
+            };
+            update();
+        }
+    };
+    let reload = Rc::new(reload);
+
+    let reload_clone = reload.clone();
+    refresh_button.connect_clicked(move |_| reload_clone());
+
+    let update_clone = update.clone();
+    value_entry.connect_changed(move |_| update_clone());
+
+    // Querying hyprctl for every rule row up front would slow down opening the editor.
+    let popover = Popover::builder().build();
+    popover.connect_show(move |_| reload());
+    popover.set_child(Some(&content_box));
+    menu_button.set_popover(Some(&popover));
+
+    menu_button
+}
@@ -0,0 +1,432 @@
+use crate::{
+    advanced_editors::create_spin_button,
+    hyprland::{
+        HyprLayout, Workspace, WorkspaceType, workspace::parse_workspace,
+        workspace_type::parse_workspace_type,
+    },
+    utils::{MARGIN_NORMAL, get_available_monitors},
+};
+use gtk::{
+    Align, ApplicationWindow, Box, Button, CheckButton, DropDown, Entry, Grid, Label, Orientation,
+    PolicyType, ScrolledWindow, SpinButton, StringList, Window, prelude::*,
+};
+use rust_i18n::t;
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+use strum::IntoEnumIterator;
+
+const DEFAULT_NUMBERED_WORKSPACES: u32 = 10;
+const UNSET_GAPS: f64 = -1.0;
+
+/// A change to the `workspace =` rows of the profile, produced by the matrix dialog.
+pub enum MatrixEdit {
+    Update { raw: String, value: String },
+    Remove { raw: String },
+    Add { value: String },
+}
+
+struct MatrixRow {
+    /// The workspace as written before the first comma, e.g. `3` or `name:web`.
+    key: String,
+    /// Rows that currently configure this workspace, with their values.
+    rows: Vec<(String, String)>,
+    /// All rules of `rows` merged into a single line; later rows win, like in Hyprland.
+    merged: String,
+    monitor_buttons: Vec<(Option<String>, CheckButton)>,
+    default_check: CheckButton,
+    persistent_check: CheckButton,
+    layout_dropdown: DropDown,
+    gaps_in_spin: SpinButton,
+    gaps_out_spin: SpinButton,
+}
+
+impl MatrixRow {
+    fn workspace(&self) -> Workspace {
+        let mut workspace = parse_workspace(&self.merged);
+        let rules = &mut workspace.rules;
+
+        rules.monitor = self
+            .monitor_buttons
+            .iter()
+            .find(|(_, button)| button.is_active())
+            .and_then(|(monitor, _)| monitor.clone());
+        rules.default = toggle_value(self.default_check.is_active(), rules.default);
+        rules.persistent = toggle_value(self.persistent_check.is_active(), rules.persistent);
+        rules.layout = match self.layout_dropdown.selected() {
+            0 => None,
+            index => HyprLayout::iter().nth(index as usize - 1),
+        };
+        rules.gaps_in = gaps_value(&self.gaps_in_spin);
+        rules.gaps_out = gaps_value(&self.gaps_out_spin);
+
+        workspace
+    }
+}
+
+/// Keeps an explicit `false` from the config when the box stays unchecked.
+fn toggle_value(active: bool, original: Option<bool>) -> Option<bool> {
+    if active {
+        Some(true)
+    } else {
+        original.filter(|value| !value)
+    }
+}
+
+fn gaps_value(spin: &SpinButton) -> Option<i32> {
+    let value = spin.value();
+    (value > UNSET_GAPS).then_some(value as i32)
+}
+
+fn has_rules(workspace: &Workspace) -> bool {
+    workspace.to_string() != workspace.workspace_type.to_string()
+}
+
+/// Returns the key of a `workspace =` value, or `None` for selector-based rules which
+/// cannot be shown as a single matrix row.
+fn workspace_key(value: &str) -> Option<String> {
+    let key = value.split(',').next()?.trim();
+    match parse_workspace_type(key) {
+        WorkspaceType::Selector(_) => None,
+        workspace_type => Some(workspace_type.to_string()),
+    }
+}
+
+fn collect_rows(rows: &[(String, String)]) -> BTreeMap<String, Vec<(String, String)>> {
+    let mut grouped: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+
+    for (raw, value) in rows {
+        if let Some(key) = workspace_key(value) {
+            grouped
+                .entry(key)
+                .or_default()
+                .push((raw.clone(), value.clone()));
+        }
+    }
+
+    for id in 1..=DEFAULT_NUMBERED_WORKSPACES {
+        grouped.entry(id.to_string()).or_default();
+    }
+
+    grouped
+}
+
+fn sort_key(key: &str) -> (u8, u32, String) {
+    match key.parse::<u32>() {
+        Ok(id) => (0, id, String::new()),
+        Err(_) if key.starts_with("special:") => (2, 0, key.to_string()),
+        Err(_) => (1, 0, key.to_string()),
+    }
+}
+
+fn merge_rules(key: &str, rows: &[(String, String)]) -> String {
+    let mut merged = key.to_string();
+    for (_, value) in rows {
+        if let Some((_, rules)) = value.split_once(',') {
+            merged.push_str(", ");
+            merged.push_str(rules.trim());
+        }
+    }
+    merged
+}
+
+fn duplicate_defaults(rows: &[MatrixRow]) -> Vec<String> {
+    let mut defaults: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for row in rows {
+        let workspace = row.workspace();
+        if workspace.rules.default == Some(true)
+            && let Some(monitor) = workspace.rules.monitor
+        {
+            defaults.entry(monitor).or_default().push(row.key.clone());
+        }
+    }
+
+    defaults
+        .into_iter()
+        .filter(|(_, workspaces)| workspaces.len() > 1)
+        .map(|(monitor, workspaces)| {
+            t!(
+                "workspace_matrix.duplicate_default",
+                monitor = monitor,
+                workspaces = workspaces.join(", ")
+            )
+            .to_string()
+        })
+        .collect()
+}
+
+fn build_edits(rows: &[MatrixRow]) -> Vec<MatrixEdit> {
+    let mut edits = Vec::new();
+
+    for row in rows {
+        let workspace = row.workspace();
+        let value = workspace.to_string();
+
+        match row.rows.split_first() {
+            Some(((raw, original), rest)) => {
+                if !has_rules(&workspace) {
+                    edits.push(MatrixEdit::Remove { raw: raw.clone() });
+                } else if parse_workspace(original).to_string() != value || !rest.is_empty() {
+                    edits.push(MatrixEdit::Update {
+                        raw: raw.clone(),
+                        value,
+                    });
+                }
+                edits.extend(
+                    rest.iter()
+                        .map(|(raw, _)| MatrixEdit::Remove { raw: raw.clone() }),
+                );
+            }
+            None if has_rules(&workspace) => edits.push(MatrixEdit::Add { value }),
+            None => {}
+        }
+    }
+
+    edits
+}
+
+fn attach_header(grid: &Grid, column: i32, text: &str) {
+    let label = Label::new(Some(text));
+    label.add_css_class("heading");
+    label.set_halign(Align::Center);
+    grid.attach(&label, column, 0, 1, 1);
+}
+
+/// Builds one matrix row in `grid` and returns the widgets that hold its state.
+fn attach_row(
+    grid: &Grid,
+    row_index: i32,
+    key: String,
+    rows: Vec<(String, String)>,
+    monitors: &[String],
+    on_change: &Rc<dyn Fn()>,
+) -> MatrixRow {
+    let merged = merge_rules(&key, &rows);
+    let rules = parse_workspace(&merged).rules;
+
+    let key_label = Label::new(Some(&key));
+    key_label.set_halign(Align::Start);
+    key_label.add_css_class("monospace");
+    grid.attach(&key_label, 0, row_index, 1, 1);
+
+    let unassigned = CheckButton::new();
+    unassigned.set_halign(Align::Center);
+    unassigned.set_active(rules.monitor.is_none());
+    grid.attach(&unassigned, 1, row_index, 1, 1);
+
+    let mut monitor_buttons = vec![(None, unassigned.clone())];
+    for (column, monitor) in monitors.iter().enumerate() {
+        let button = CheckButton::new();
+        button.set_group(Some(&unassigned));
+        button.set_halign(Align::Center);
+        button.set_tooltip_text(Some(monitor));
+        button.set_active(rules.monitor.as_deref() == Some(monitor.as_str()));
+        grid.attach(&button, column as i32 + 2, row_index, 1, 1);
+        monitor_buttons.push((Some(monitor.clone()), button));
+    }
+
+    let column = monitors.len() as i32 + 2;
+
+    let default_check = CheckButton::new();
+    default_check.set_halign(Align::Center);
+    default_check.set_active(rules.default == Some(true));
+    grid.attach(&default_check, column, row_index, 1, 1);
+
+    let persistent_check = CheckButton::new();
+    persistent_check.set_halign(Align::Center);
+    persistent_check.set_active(rules.persistent == Some(true));
+    grid.attach(&persistent_check, column + 1, row_index, 1, 1);
+
+    let layouts: Vec<String> = std::iter::once("—".to_string())
+        .chain(HyprLayout::iter().map(|layout| layout.to_string()))
+        .collect();
+    let layouts: Vec<&str> = layouts.iter().map(|s| s.as_str()).collect();
+    let layout_dropdown = DropDown::new(Some(StringList::new(&layouts)), None::<gtk::Expression>);
+    layout_dropdown.set_selected(
+        rules
+            .layout
+            .and_then(|layout| HyprLayout::iter().position(|l| l == layout))
+            .map_or(0, |index| index as u32 + 1),
+    );
+    grid.attach(&layout_dropdown, column + 2, row_index, 1, 1);
+
+    let gaps_in_spin = create_spin_button(UNSET_GAPS, 500.0, 1.0);
+    gaps_in_spin.set_value(rules.gaps_in.map_or(UNSET_GAPS, |gaps| gaps as f64));
+    gaps_in_spin.set_tooltip_text(Some(&t!("workspace_matrix.gaps_unset_hint")));
+    grid.attach(&gaps_in_spin, column + 3, row_index, 1, 1);
+
+    let gaps_out_spin = create_spin_button(UNSET_GAPS, 500.0, 1.0);
+    gaps_out_spin.set_value(rules.gaps_out.map_or(UNSET_GAPS, |gaps| gaps as f64));
+    gaps_out_spin.set_tooltip_text(Some(&t!("workspace_matrix.gaps_unset_hint")));
+    grid.attach(&gaps_out_spin, column + 4, row_index, 1, 1);
+
+    for (_, button) in &monitor_buttons {
+        let on_change = on_change.clone();
+        button.connect_toggled(move |_| on_change());
+    }
+    let on_change_clone = on_change.clone();
+    default_check.connect_toggled(move |_| on_change_clone());
+
+    MatrixRow {
+        key,
+        rows,
+        merged,
+        monitor_buttons,
+        default_check,
+        persistent_check,
+        layout_dropdown,
+        gaps_in_spin,
+        gaps_out_spin,
+    }
+}
+
+/// Shows a matrix of workspaces against monitors for the given `(raw, value)` pairs of
+/// `workspace =` rows. Selector-based rows are left out and never edited.
+pub fn show_workspace_matrix<F>(
+    parent: &ApplicationWindow,
+    rows: Vec<(String, String)>,
+    on_apply: F,
+) where
+    F: Fn(Vec<MatrixEdit>) + 'static,
+{
+    let dialog_window = Window::builder()
+        .title(t!("workspace_matrix.workspace_matrix").to_string())
+        .modal(true)
+        .transient_for(parent)
+        .destroy_with_parent(true)
+        .default_width(900)
+        .default_height(600)
+        .build();
+
+    let dialog_box = Box::new(Orientation::Vertical, 10);
+    dialog_box.set_margin_top(MARGIN_NORMAL);
+    dialog_box.set_margin_bottom(MARGIN_NORMAL);
+    dialog_box.set_margin_start(MARGIN_NORMAL);
+    dialog_box.set_margin_end(MARGIN_NORMAL);
+
+    let description_label = Label::new(Some(&t!("workspace_matrix.description")));
+    description_label.set_halign(Align::Start);
+    description_label.set_wrap(true);
+    description_label.add_css_class("dim-label");
+    dialog_box.append(&description_label);
+
+    let grouped = collect_rows(&rows);
+
+    let mut monitors: Vec<String> = get_available_monitors(true).into_iter().collect();
+    for (key, group) in &grouped {
+        let rules = parse_workspace(&merge_rules(key, group)).rules;
+        if let Some(monitor) = rules.monitor
+            && !monitors.contains(&monitor)
+        {
+            monitors.push(monitor);
+        }
+    }
+    monitors.sort();
+
+    let grid = Grid::builder().row_spacing(6).column_spacing(12).build();
+    attach_header(&grid, 0, &t!("workspace_matrix.workspace"));
+    attach_header(&grid, 1, &t!("workspace_matrix.unassigned"));
+    for (column, monitor) in monitors.iter().enumerate() {
+        attach_header(&grid, column as i32 + 2, monitor);
+    }
+    let column = monitors.len() as i32 + 2;
+    attach_header(&grid, column, &t!("workspace_matrix.default"));
+    attach_header(&grid, column + 1, &t!("workspace_matrix.persistent"));
+    attach_header(&grid, column + 2, &t!("workspace_matrix.layout"));
+    attach_header(&grid, column + 3, &t!("workspace_matrix.gaps_in"));
+    attach_header(&grid, column + 4, &t!("workspace_matrix.gaps_out"));
+
+    let scrolled_window = ScrolledWindow::new();
+    scrolled_window.set_hscrollbar_policy(PolicyType::Automatic);
+    scrolled_window.set_vexpand(true);
+    scrolled_window.set_child(Some(&grid));
+    dialog_box.append(&scrolled_window);
+
+    let warning_label = Label::new(None);
+    warning_label.set_halign(Align::Start);
+    warning_label.set_wrap(true);
+    warning_label.add_css_class("error");
+    dialog_box.append(&warning_label);
+
+    let matrix_rows: Rc<RefCell<Vec<MatrixRow>>> = Rc::new(RefCell::new(Vec::new()));
+
+    let update_warnings: Rc<dyn Fn()> = {
+        let matrix_rows = matrix_rows.clone();
+        let warning_label = warning_label.clone();
+        Rc::new(move || {
+            let warnings = duplicate_defaults(&matrix_rows.borrow());
+            warning_label.set_visible(!warnings.is_empty());
+            warning_label.set_text(&warnings.join("\n"));
+        })
+    };
+
+    let mut sorted: Vec<(String, Vec<(String, String)>)> = grouped.into_iter().collect();
+    sorted.sort_by_key(|(key, _)| sort_key(key));
+    for (index, (key, group)) in sorted.into_iter().enumerate() {
+        let row = attach_row(
+            &grid,
+            index as i32 + 1,
+            key,
+            group,
+            &monitors,
+            &update_warnings,
+        );
+        matrix_rows.borrow_mut().push(row);
+    }
+    update_warnings();
+
+    let add_box = Box::new(Orientation::Horizontal, 5);
+    let add_entry = Entry::new();
+    add_entry.set_hexpand(true);
+    add_entry.set_placeholder_text(Some(&t!("workspace_matrix.add_workspace_placeholder")));
+    let add_button = Button::with_label(&t!("workspace_matrix.add_workspace"));
+    add_box.append(&add_entry);
+    add_box.append(&add_button);
+    dialog_box.append(&add_box);
+
+    let buttons_box = Box::new(Orientation::Horizontal, 5);
+    buttons_box.set_halign(Align::End);
+    let cancel_button = Button::with_label(&t!("workspace_matrix.cancel"));
+    let apply_button = Button::with_label(&t!("workspace_matrix.apply"));
+    apply_button.add_css_class("suggested-action");
+    buttons_box.append(&cancel_button);
+    buttons_box.append(&apply_button);
+    dialog_box.append(&buttons_box);
+
+    dialog_window.set_child(Some(&dialog_box));
+
+    let matrix_rows_clone = matrix_rows.clone();
+    let add_entry_clone = add_entry.clone();
+    add_button.connect_clicked(move |_| {
+        let Some(key) = workspace_key(&add_entry_clone.text()) else {
+            return;
+        };
+        if matrix_rows_clone.borrow().iter().any(|row| row.key == key) {
+            return;
+        }
+
+        let row_index = matrix_rows_clone.borrow().len() as i32 + 1;
+        let row = attach_row(
+            &grid,
+            row_index,
+            key,
+            Vec::new(),
+            &monitors,
+            &update_warnings,
+        );
+        matrix_rows_clone.borrow_mut().push(row);
+        add_entry_clone.set_text("");
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    cancel_button.connect_clicked(move |_| {
+        dialog_window_clone.close();
+    });
+
+    let dialog_window_clone = dialog_window.clone();
+    apply_button.connect_clicked(move |_| {
+        on_apply(build_edits(&matrix_rows.borrow()));
+        dialog_window_clone.close();
+    });
+
+    dialog_window.present();
+}